@@ -1,6 +1,7 @@
 use rocket::serde::{Deserialize, Serialize};
 
 use crate::dto::game_progress_dto::QuestionDto;
+use crate::model::power_up::PowerUp;
 
 ///AnswerDto is used to interact with the game frontend in the [GameResource](crate::resource::game_resource::GameResource)
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -11,4 +12,10 @@ pub struct GameAnswerDto {
     pub question_index: i8,
     pub correct_answer: i8,
     pub question: QuestionDto,
+    #[serde(default)]
+    pub wager: Option<i32>,
+    #[serde(default)]
+    pub power_up: Option<PowerUp>,
+    #[serde(default)]
+    pub answer_seconds: Option<u64>,
 }
@@ -0,0 +1,26 @@
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::model::api_key::ApiKeyScope;
+
+///NewApiKeyDto is used to create an API key in the [ApiKeyResource](crate::resource::api_key_resource)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct NewApiKeyDto {
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+///ApiKeyDto is used to list API keys in the [ApiKeyResource](crate::resource::api_key_resource).
+///`key` is only given once, when the key is created. Dates are RFC 3339 formatted.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ApiKeyDto {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    pub hint: String,
+    pub scopes: Vec<ApiKeyScope>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
@@ -0,0 +1,66 @@
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::dto::game_progress_dto::QuestionDto;
+
+///DailyChallengeDto is used to show the challenge of the day in the [DailyChallengeResource](crate::resource::daily_challenge_resource).
+///`attempt` and `streak` are the ones of the authenticated player.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DailyChallengeDto {
+    pub day: String,
+    pub topics: Vec<String>,
+    pub question_number: i8,
+    pub starts_at: String,
+    pub ends_at: String,
+    pub attempt: Option<DailyAttemptDto>,
+    pub streak: Option<DailyStreakDto>,
+}
+
+///DailyAttemptDto is used to play the daily challenge in the [DailyChallengeResource](crate::resource::daily_challenge_resource).
+///`question` is the question to answer, none once the attempt is finished.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DailyAttemptDto {
+    pub day: String,
+    pub question_number: i8,
+    pub current_question: i8,
+    pub question: Option<QuestionDto>,
+    pub remaining_time: Option<u64>,
+    pub score: i32,
+    pub answer_seconds: i64,
+    pub finished: bool,
+}
+
+///DailyFeedbackDto is used to tell the player whether they answered correctly
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DailyFeedbackDto {
+    pub answer: i8,
+    pub correct: bool,
+    pub timed_out: bool,
+    pub attempt: DailyAttemptDto,
+}
+
+///DailyLeaderboardDto is used to show a page of the leaderboard of a day in the [DailyChallengeResource](crate::resource::daily_challenge_resource).
+///`me` is the attempt of the authenticated player, whichever page they are on.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DailyLeaderboardDto {
+    pub day: String,
+    pub page: u64,
+    pub size: i64,
+    pub attempts: Vec<DailyRankDto>,
+    pub me: Option<DailyRankDto>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DailyRankDto {
+    pub rank: u64,
+    pub user: String,
+    pub score: i32,
+    pub answer_seconds: i64,
+    pub finished: bool,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DailyStreakDto {
+    pub current: u32,
+    pub best: u32,
+    pub last_day: Option<String>,
+}
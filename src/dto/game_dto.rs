@@ -1,5 +1,9 @@
 use rocket::serde::{Deserialize, Serialize};
 
+use crate::model::game::{GameHostActionKind, GameMode, GameStatus, DEFAULT_MIN_PLAYERS};
+use crate::model::power_up::PowerUp;
+use crate::model::team::TeamScoring;
+
 ///GameDto is used to interact with the game frontend in the [GameResource](crate::resource::game_resource::GameResource)
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct GameDto {
@@ -8,8 +12,226 @@ pub struct GameDto {
     pub topics: Vec<String>,
     pub question_number: i8,
     pub is_private: bool,
-    pub is_started: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub join_code: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spectate_code: Option<String>,
+    #[serde(default)]
+    pub status: GameStatus,
+    #[serde(default)]
+    pub status_history: Vec<GameStatusChangeDto>,
+    #[serde(default)]
+    pub mode: GameMode,
     pub creator: Option<String>,
     #[serde(skip)]
     pub users: Vec<String>,
+    #[serde(default)]
+    pub lobby_settings: LobbySettingsDto,
+    #[serde(default)]
+    pub ready_users: Vec<String>,
+    #[serde(default)]
+    pub spectator_count: usize,
+    #[serde(default)]
+    pub host_actions: Vec<GameHostActionDto>,
+    #[serde(default)]
+    pub standings: Vec<GameStandingDto>,
+    #[serde(default)]
+    pub eliminations: Vec<GameEliminationDto>,
+    #[serde(default)]
+    pub hit_points: Vec<HitPointsDto>,
+    #[serde(default)]
+    pub damages: Vec<GameDamageDto>,
+    #[serde(default)]
+    pub buzzes: Vec<GameBuzzDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub miss_limit: Option<i8>,
+    #[serde(default)]
+    pub power_ups: bool,
+    #[serde(default)]
+    pub used_power_ups: Vec<GamePowerUpDto>,
+    #[serde(default)]
+    pub hint_penalty: Option<i32>,
+    #[serde(default)]
+    pub revealed_hints: Vec<GameHintDto>,
+    #[serde(default)]
+    pub teams: Vec<GameTeamDto>,
+    #[serde(default)]
+    pub team_scoring: TeamScoring,
+    #[serde(default)]
+    pub team_standings: Vec<TeamStandingDto>,
+}
+
+///JoinGameDto is used to join a private game with its join code
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct JoinGameDto {
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+///LobbySettingsDto is used to choose the lobby settings of a game when creating it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LobbySettingsDto {
+    pub max_players: Option<i8>,
+    #[serde(default = "default_min_players")]
+    pub min_players: i8,
+    #[serde(default)]
+    pub ready_check: bool,
+}
+
+impl Default for LobbySettingsDto {
+    fn default() -> Self {
+        LobbySettingsDto {
+            max_players: None,
+            min_players: DEFAULT_MIN_PLAYERS,
+            ready_check: false,
+        }
+    }
+}
+
+fn default_min_players() -> i8 {
+    DEFAULT_MIN_PLAYERS
+}
+
+///LobbyDto shows who is present and ready in a game lobby, and how many spectators watch it.
+///It is sent in `lobby` events of the game progress stream and returned when registering a player.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LobbyDto {
+    pub game_id: String,
+    pub creator: Option<String>,
+    pub users: Vec<String>,
+    pub ready_users: Vec<String>,
+    pub max_players: Option<i8>,
+    pub min_players: i8,
+    pub ready_check: bool,
+    pub can_start: bool,
+    pub teams: Vec<GameTeamDto>,
+    pub spectator_count: usize,
+}
+
+///SpectatorsDto gives the number of spectators watching a game.
+///It is sent in `spectators` events of the game progress stream once the game started, when the number changes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SpectatorsDto {
+    pub game_id: String,
+    pub spectator_count: usize,
+}
+
+///GameStatusChangeDto exposes a timestamped status transition of a game, `changed_at` is RFC 3339 formatted
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct GameStatusChangeDto {
+    pub status: GameStatus,
+    pub changed_at: String,
+}
+
+///GameHostActionDto exposes an action of the game host, `performed_at` is RFC 3339 formatted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameHostActionDto {
+    pub kind: GameHostActionKind,
+    pub host: String,
+    pub player: Option<String>,
+    pub question_index: Option<i8>,
+    pub performed_at: String,
+}
+
+///GameStandingDto exposes the final standing of a player in a finished game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameStandingDto {
+    pub user: String,
+    pub score: i32,
+    pub rank: i32,
+}
+
+///GameEliminationDto exposes the knock out of a player from an elimination game, `eliminated_at` is RFC 3339 formatted.
+///It is sent in `elimination` events of the game progress stream too.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameEliminationDto {
+    pub user: String,
+    pub question_index: i8,
+    pub eliminated_at: String,
+}
+
+///HitPointsDto exposes the hit points left to a player of a duel
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HitPointsDto {
+    pub user: String,
+    pub hit_points: i32,
+}
+
+///GameDamageDto exposes the hit points lost by a player of a duel after a question, `dealt_at` is RFC 3339 formatted.
+///It is sent in `damage` events of the game progress stream too.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameDamageDto {
+    pub user: String,
+    pub from: String,
+    pub question_index: i8,
+    pub damage: i32,
+    pub hit_points: i32,
+    pub dealt_at: String,
+}
+
+///GameBuzzDto exposes the answer of a player to a question of a buzzer game, `buzzed_at` is RFC 3339 formatted.
+///It is sent in `buzz` events of the game progress stream too.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameBuzzDto {
+    pub user: String,
+    pub question_index: i8,
+    pub answer: i8,
+    pub correct: bool,
+    pub buzzed_at: String,
+}
+
+///GamePowerUpDto exposes the use of a power-up by a player, `used_at` is RFC 3339 formatted.
+///It is sent in `power_up` events of the game progress stream too.
+///The answers removed by a 50/50 are only given to the player who used it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GamePowerUpDto {
+    pub user: String,
+    pub power_up: PowerUp,
+    pub question_index: i8,
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_answers: Vec<i8>,
+    pub used_at: String,
+}
+
+///GameHintDto exposes a hint of a question revealed to a player, `revealed_at` is RFC 3339 formatted.
+///The text of the hint is only given to the player who asked for it, in the response and in `hint` events of their
+///game progress stream.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameHintDto {
+    pub user: String,
+    pub question_index: i8,
+    pub hint_index: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    pub penalty: i32,
+    pub revealed_at: String,
+}
+
+///GameTeamDto exposes a team of a team game and its players.
+///Only the name is read when creating a game, players join the teams in the lobby.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameTeamDto {
+    pub name: String,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+///TeamStandingDto exposes the standing of a team in a team game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamStandingDto {
+    pub team: String,
+    pub score: f64,
+    pub rank: i32,
+}
+
+///ScoreboardDto exposes the standings of the players of a game from the answers given so far,
+///and the standings of the teams in team games
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScoreboardDto {
+    pub game_id: String,
+    pub standings: Vec<GameStandingDto>,
+    pub team_standings: Vec<TeamStandingDto>,
 }
@@ -10,6 +10,8 @@ pub struct GameProgressDto {
     pub current_question: i8,
     pub question_number: i8,
     pub question_content: QuestionDto,
+    #[serde(default)]
+    pub overtime: u64,
 }
 
 ///QuestionDto is used to interact with the game frontend in the [GameResource](crate::resource::game_resource::GameResource)
@@ -24,8 +26,13 @@ pub struct QuestionDto {
     pub good_answer_number: i8,
     pub topic: String,
     pub remaining_time: u64,
+    #[serde(default, skip_serializing)]
+    pub hints: Vec<String>,
 }
 
+/// Topics having built-in questions.
+pub const BUILT_IN_TOPICS: [&str; 3] = ["Java", "Rust", "Kotlin"];
+
 pub fn questions_java() -> Vec<QuestionDto> {
     vec![
         QuestionDto {
@@ -37,6 +44,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, what statement is true ?".to_string(),
@@ -52,6 +60,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which of the below is valid way to instantiate an array in Java ?".to_string(),
@@ -62,6 +71,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, what is the return type of the hashCode() method in the Object class ?".to_string(),
@@ -72,6 +82,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, what does the expression float a = 35 / 0 return ?".to_string(),
@@ -82,6 +93,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Evaluate the following Java expression, if x=3, y=5, and z=10 : ++z + y - y + z + x++".to_string(),
@@ -92,6 +104,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 4,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which of the following tool is used to generate API documentation in HTML format from doc comments in source code ?".to_string(),
@@ -102,6 +115,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which of the following creates a List of 3 visible items and multiple selections abled ?".to_string(),
@@ -112,6 +126,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which of the following for loop declaration is NOT valid ?".to_string(),
@@ -122,6 +137,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which method of the Class.class is used to determine the name of a class represented by the class object as a String ?".to_string(),
@@ -132,6 +148,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, in which process, a local variable has the same name as one of the instance variables ?".to_string(),
@@ -142,6 +159,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 4,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which package contains the Random class ?".to_string(),
@@ -152,6 +170,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, what do you mean by nameless objects ?".to_string(),
@@ -162,6 +181,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 4,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, an interface with no fields or methods is known as a ______".to_string(),
@@ -172,6 +192,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which of the following is an immediate subclass of the Panel class?".to_string(),
@@ -182,6 +203,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which option is false about the final keyword ?".to_string(),
@@ -192,6 +214,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which of these classes are the direct subclasses of the Throwable class ?".to_string(),
@@ -202,6 +225,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What do you mean by chained exceptions in Java ?".to_string(),
@@ -212,6 +236,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, in which memory a String is stored, when we create a string using new operator ?".to_string(),
@@ -222,6 +247,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, what is the use of the intern() method ?".to_string(),
@@ -232,6 +258,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which of the following is a marker interface ?".to_string(),
@@ -242,6 +269,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which of the following is a reserved keyword in Java ?".to_string(),
@@ -252,6 +280,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which keyword is used for accessing the features of a package ?".to_string(),
@@ -262,6 +291,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In java, jar stands for_____".to_string(),
@@ -272,6 +302,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 4,
             topic: "Java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "in java, which of the following is false ?".to_string(),
@@ -282,6 +313,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, what is the use of \\w in regex ?".to_string(),
@@ -292,6 +324,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which of the given methods are of Object class ?".to_string(),
@@ -302,6 +335,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which of the following is a valid syntax to synchronize the HashMap ?".to_string(),
@@ -312,6 +346,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, what is meant by the classes and objects that dependents on each other ?".to_string(),
@@ -322,6 +357,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Javan how many threads can be executed at a time ?".to_string(),
@@ -332,6 +368,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, if three threads trying to share a single object at the same time, which condition will arise in this scenario ?".to_string(),
@@ -342,6 +379,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, if a thread goes to sleep".to_string(),
@@ -352,6 +390,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, which of the following modifiers can be used for a variable so that it can be accessed by any thread or a part of a program ?".to_string(),
@@ -362,6 +401,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, in character stream I/O, a single read/write operation performs _____".to_string(),
@@ -372,6 +412,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Java, what is the default encoding for an OutputStreamWriter ?".to_string(),
@@ -382,6 +423,7 @@ pub fn questions_java() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "java".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
     ]
 }
@@ -396,6 +438,7 @@ pub fn questions_rust() -> Vec<QuestionDto> {
         good_answer_number: 4,
         topic: "Rust".to_string(),
         remaining_time: QUESTION_SECONDS,
+        hints: vec![],
     }]
 }
 
@@ -410,6 +453,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which platform does Kotlin primarily target ?".to_string(),
@@ -420,6 +464,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Are semicolons (;) mandatory at the end of code statements in Kotlin ?".to_string(),
@@ -430,6 +475,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What paradigm(s) does the Kotlin programming language follow ?".to_string(),
@@ -440,6 +486,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 4,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How do you declare a variable in Kotlin?".to_string(),
@@ -450,6 +497,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 4,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How do you define a variable in Kotlin that cannot be reassigned ?".to_string(),
@@ -460,6 +508,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How do you declare a nullable variable in Kotlin ?".to_string(),
@@ -470,6 +519,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What is the difference between val and var in Kotlin ?".to_string(),
@@ -480,6 +530,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How do you define a function in Kotlin ?".to_string(),
@@ -490,6 +541,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Why is the when expression used in Kotlin ?".to_string(),
@@ -500,6 +552,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What does ?. operator do in Kotlin ?".to_string(),
@@ -510,6 +563,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What is the default visibility modifiers in Kotlin if no modifier is specified ?".to_string(),
@@ -520,6 +574,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which keyword is used to create a singleton in Kotlin ?".to_string(),
@@ -530,6 +585,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 4,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What is the main purpose of the let function in Kotlin ?".to_string(),
@@ -540,6 +596,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which feature in Kotlin helps to prevent NullPointerExceptions ?".to_string(),
@@ -550,6 +607,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What is the purpose of the open modifier in Kotlin ?".to_string(),
@@ -560,6 +618,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How do you create a single-line comment in Kotlin ?".to_string(),
@@ -570,6 +629,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How can we write a multi-line comment in Kotlin ?".to_string(),
@@ -580,6 +640,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What is the role of the init block in Kotlin ?".to_string(),
@@ -590,6 +651,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How do you call a function in Kotlin ?".to_string(),
@@ -600,6 +662,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which Kotlin construct allows a block of code to be executed a specific number of times ?".to_string(),
@@ -610,6 +673,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which keyword are used to handle conditional statements in Kotlin ?".to_string(),
@@ -620,6 +684,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What is the correct way to define a primary constructor in Kotlin ?".to_string(),
@@ -630,6 +695,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 4,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Kotlin, what is the main purpose of the return keyword ?".to_string(),
@@ -640,6 +706,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What is the primary use of the 'with' function in Kotlin ?".to_string(),
@@ -650,6 +717,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What does the 'also' function do in Kotlin ?".to_string(),
@@ -660,6 +728,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which of these is not a loop structure in Kotlin ?".to_string(),
@@ -670,6 +739,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How do we throw an exception in Kotlin ?".to_string(),
@@ -680,6 +750,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What methods can be used to achieve abstraction in Kotlin ?".to_string(),
@@ -690,6 +761,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How do you declare an array of integers in Kotlin ?".to_string(),
@@ -700,6 +772,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which of the following is not a basic type in Kotlin ?".to_string(),
@@ -710,6 +783,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How do you declare a String in Kotlin ?".to_string(),
@@ -720,6 +794,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Kotlin, how do you compare two Strings for equality ?".to_string(),
@@ -730,6 +805,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which property can be used to find the length of a string ?".to_string(),
@@ -740,6 +816,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "In Kotlin, which collection type has an order and can contain duplicate elements ?".to_string(),
@@ -750,6 +827,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which function is used to iterate over a collection in Kotlin ?".to_string(),
@@ -760,6 +838,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which of these functions can transform a list in Kotlin ?".to_string(),
@@ -770,6 +849,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "Which collection type ensures element uniqueness in Kotlin ?".to_string(),
@@ -780,6 +860,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 2,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "How do you create an empty list in Kotlin ?".to_string(),
@@ -790,6 +871,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 1,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
         QuestionDto {
             question_text: "What does the mapOf() function do in Kotlin ?".to_string(),
@@ -800,6 +882,7 @@ pub fn questions_kotlin() -> Vec<QuestionDto> {
             good_answer_number: 3,
             topic: "Kotlin".to_string(),
             remaining_time: QUESTION_SECONDS,
+            hints: vec![],
         },
 
     ]
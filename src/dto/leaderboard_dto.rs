@@ -0,0 +1,23 @@
+use rocket::serde::{Deserialize, Serialize};
+
+///LeaderboardDto is used to show a page of a leaderboard in the [LeaderboardResource](crate::resource::leaderboard_resource).
+///`me` is the entry of the authenticated player, whichever page they are on.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LeaderboardDto {
+    pub season: String,
+    pub topic: String,
+    pub page: u64,
+    pub size: i64,
+    pub entries: Vec<LeaderboardEntryDto>,
+    pub me: Option<LeaderboardEntryDto>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntryDto {
+    pub rank: u64,
+    pub user: String,
+    pub score: i32,
+    pub answers: i32,
+    pub games: i32,
+    pub wins: i32,
+}
@@ -0,0 +1,27 @@
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::model::matchmaking::TicketStatus;
+
+///MatchmakingRequestDto is used to join the queue in the [MatchmakingResource](crate::resource::matchmaking_resource)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct MatchmakingRequestDto {
+    pub topics: Vec<String>,
+}
+
+///TicketDto is used to follow a player in the queue of the [MatchmakingResource](crate::resource::matchmaking_resource).
+///It gives the constraints the group of the player must currently meet, they relax as the player waits.
+///`game_id` is the game to play once the player is matched.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TicketDto {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub user: String,
+    pub topics: Vec<String>,
+    pub rating: f64,
+    pub status: TicketStatus,
+    pub game_id: Option<String>,
+    pub waited_seconds: i64,
+    pub rating_gap: f64,
+    pub min_players: usize,
+    pub any_topic: bool,
+}
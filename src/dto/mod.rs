@@ -1,3 +1,12 @@
 pub mod answer;
+pub mod api_key_dto;
+pub mod daily_challenge_dto;
 pub mod game_dto;
 pub mod game_progress_dto;
+pub mod leaderboard_dto;
+pub mod matchmaking_dto;
+pub mod practice_dto;
+pub mod profile_dto;
+pub mod question_dto;
+pub mod rating_dto;
+pub mod user_dto;
@@ -0,0 +1,63 @@
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::dto::game_progress_dto::QuestionDto;
+use crate::dto::profile_dto::TopicStatsDto;
+
+///PracticeRequestDto is used to start a practice session in the [PracticeResource](crate::resource::practice_resource).
+///Sessions have 10 timed questions unless asked otherwise.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PracticeRequestDto {
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub question_number: Option<i8>,
+    #[serde(default)]
+    pub timed: Option<bool>,
+}
+
+///PracticeSessionDto is used to play a practice session in the [PracticeResource](crate::resource::practice_resource).
+///`question` is the question to answer, none once the session is finished.
+///`remaining_time` is only given in timed sessions.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PracticeSessionDto {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub topics: Vec<String>,
+    pub timed: bool,
+    pub question_number: i8,
+    pub current_question: i8,
+    pub question: Option<QuestionDto>,
+    pub remaining_time: Option<u64>,
+    pub finished: bool,
+}
+
+///PracticeFeedbackDto is used to tell the player how they answered, with the correct answer
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PracticeFeedbackDto {
+    pub answer: i8,
+    pub correct_answer: i8,
+    pub correct: bool,
+    pub timed_out: bool,
+    pub session: PracticeSessionDto,
+}
+
+///PracticeSummaryDto is used to show how a practice session went
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PracticeSummaryDto {
+    pub session: PracticeSessionDto,
+    pub answers: i32,
+    pub correct_answers: i32,
+    pub accuracy: f64,
+    pub average_answer_seconds: Option<f64>,
+    pub topics: Vec<TopicStatsDto>,
+    pub results: Vec<PracticeResultDto>,
+}
+
+///PracticeResultDto is used to go through the answers of a practice session, `answer` being 0 when the time ran out
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PracticeResultDto {
+    pub question_text: String,
+    pub topic: String,
+    pub answer: i8,
+    pub correct_answer: i8,
+    pub correct: bool,
+}
@@ -0,0 +1,53 @@
+use rocket::serde::{Deserialize, Serialize};
+
+///ProfileDto is used to show the lifetime statistics of a player in the [ProfileResource](crate::resource::profile_resource).
+///Dates are RFC 3339 formatted.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProfileDto {
+    pub user: String,
+    pub games_played: i32,
+    pub wins: i32,
+    pub answers: i32,
+    pub correct_answers: i32,
+    pub accuracy: f64,
+    pub average_answer_seconds: f64,
+    pub best_streak: i32,
+    pub topics: Vec<TopicStatsDto>,
+    pub recent_games: Vec<RecentGameDto>,
+    pub ratings: Vec<TopicRatingDto>,
+    pub survival_bests: Vec<SurvivalBestDto>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TopicStatsDto {
+    pub topic: String,
+    pub answers: i32,
+    pub correct_answers: i32,
+    pub accuracy: f64,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TopicRatingDto {
+    pub topic: String,
+    pub rating: f64,
+    pub games: i32,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RecentGameDto {
+    pub game_id: String,
+    pub topics: Vec<String>,
+    pub score: i32,
+    pub rank: i32,
+    pub players: i32,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SurvivalBestDto {
+    pub topic: String,
+    pub score: i32,
+    pub game_id: String,
+    pub achieved_at: Option<String>,
+}
@@ -0,0 +1,19 @@
+use rocket::serde::{Deserialize, Serialize};
+
+///AuthoredQuestionDto is used to manage the questions written by users in the [QuestionResource](crate::resource::question_resource)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AuthoredQuestionDto {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    pub question_text: String,
+    pub answer_1: String,
+    pub answer_2: String,
+    pub answer_3: String,
+    pub answer_4: String,
+    pub good_answer_number: i8,
+    pub topic: String,
+    #[serde(default)]
+    pub hints: Vec<String>,
+}
@@ -0,0 +1,23 @@
+use rocket::serde::{Deserialize, Serialize};
+
+///RatingDto is used to list the players of a topic leaderboard in the [RatingResource](crate::resource::rating_resource).
+///Dates are RFC 3339 formatted.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RatingDto {
+    pub rank: u64,
+    pub user: String,
+    pub topic: String,
+    pub rating: f64,
+    pub games: i32,
+    pub updated_at: String,
+}
+
+///RatingChangeDto is used to chart the rating of a player in the [RatingResource](crate::resource::rating_resource).
+///Dates are RFC 3339 formatted.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RatingChangeDto {
+    pub game_id: String,
+    pub rating_before: f64,
+    pub rating: f64,
+    pub changed_at: String,
+}
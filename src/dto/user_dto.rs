@@ -0,0 +1,34 @@
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::model::user::UserRole;
+
+///CredentialsDto is used to register and log in users in the [UserResource](crate::resource::user_resource)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CredentialsDto {
+    pub username: String,
+    pub password: String,
+}
+
+///GuestDto is used to play as a guest under a nickname in the [UserResource](crate::resource::user_resource)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct GuestDto {
+    pub nickname: String,
+}
+
+///UserDto is used to expose users in the [UserResource](crate::resource::user_resource)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct UserDto {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub username: String,
+    #[serde(default)]
+    pub roles: Vec<UserRole>,
+}
+
+///TokenDto holds the token to send as `Authorization: Bearer <token>` header, `expires_at` is RFC 3339 formatted
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TokenDto {
+    pub token: String,
+    pub token_type: String,
+    pub expires_at: String,
+}
@@ -0,0 +1,17 @@
+/// Error type for ApiKeyService.
+/// It is thrown anytime an error occurs in the service.
+#[derive(Debug)]
+pub struct ApiKeyServiceError {
+    pub message: String,
+    pub kind: ApiKeyServiceErrorKind,
+}
+
+/// Provides possible error kinds happening in the service.
+#[derive(Debug)]
+pub enum ApiKeyServiceErrorKind {
+    NotFound,
+    InvalidRequest,
+    Unauthorized,
+    Forbidden,
+    Internal,
+}
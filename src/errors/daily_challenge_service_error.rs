@@ -0,0 +1,16 @@
+/// Error type for DailyChallengeService.
+/// It is thrown anytime an error occurs in the service.
+#[derive(Debug)]
+pub struct DailyChallengeServiceError {
+    pub message: String,
+    pub kind: DailyChallengeServiceErrorKind,
+}
+
+/// Provides possible error kinds happening in the service.
+#[derive(Debug)]
+pub enum DailyChallengeServiceErrorKind {
+    NotFound,
+    InvalidRequest,
+    Conflict,
+    Internal,
+}
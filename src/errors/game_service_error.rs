@@ -10,5 +10,9 @@ pub struct GameServiceError {
 #[derive(Debug)]
 pub enum GameServiceErrorKind {
     NotFound,
+    InvalidTransition,
+    InvalidRequest,
+    Conflict,
+    Forbidden,
     Internal,
 }
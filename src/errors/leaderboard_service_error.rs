@@ -0,0 +1,15 @@
+/// Error type for LeaderboardService.
+/// It is thrown anytime an error occurs in the service.
+#[derive(Debug)]
+pub struct LeaderboardServiceError {
+    pub message: String,
+    pub kind: LeaderboardServiceErrorKind,
+}
+
+/// Provides possible error kinds happening in the service.
+#[derive(Debug)]
+pub enum LeaderboardServiceErrorKind {
+    NotFound,
+    InvalidRequest,
+    Internal,
+}
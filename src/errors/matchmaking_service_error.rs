@@ -0,0 +1,16 @@
+/// Error type for MatchmakingService.
+/// It is thrown anytime an error occurs in the service.
+#[derive(Debug)]
+pub struct MatchmakingServiceError {
+    pub message: String,
+    pub kind: MatchmakingServiceErrorKind,
+}
+
+/// Provides possible error kinds happening in the service.
+#[derive(Debug)]
+pub enum MatchmakingServiceErrorKind {
+    NotFound,
+    InvalidRequest,
+    Conflict,
+    Internal,
+}
@@ -1 +1,10 @@
+pub mod api_key_service_error;
+pub mod daily_challenge_service_error;
 pub mod game_service_error;
+pub mod leaderboard_service_error;
+pub mod matchmaking_service_error;
+pub mod practice_service_error;
+pub mod profile_service_error;
+pub mod question_service_error;
+pub mod rating_service_error;
+pub mod user_service_error;
@@ -0,0 +1,17 @@
+/// Error type for PracticeService.
+/// It is thrown anytime an error occurs in the service.
+#[derive(Debug)]
+pub struct PracticeServiceError {
+    pub message: String,
+    pub kind: PracticeServiceErrorKind,
+}
+
+/// Provides possible error kinds happening in the service.
+#[derive(Debug)]
+pub enum PracticeServiceErrorKind {
+    NotFound,
+    InvalidRequest,
+    Forbidden,
+    Conflict,
+    Internal,
+}
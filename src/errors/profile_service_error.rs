@@ -0,0 +1,14 @@
+/// Error type for ProfileService.
+/// It is thrown anytime an error occurs in the service.
+#[derive(Debug)]
+pub struct ProfileServiceError {
+    pub message: String,
+    pub kind: ProfileServiceErrorKind,
+}
+
+/// Provides possible error kinds happening in the service.
+#[derive(Debug)]
+pub enum ProfileServiceErrorKind {
+    NotFound,
+    Internal,
+}
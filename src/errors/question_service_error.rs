@@ -0,0 +1,16 @@
+/// Error type for QuestionService.
+/// It is thrown anytime an error occurs in the service.
+#[derive(Debug)]
+pub struct QuestionServiceError {
+    pub message: String,
+    pub kind: QuestionServiceErrorKind,
+}
+
+/// Provides possible error kinds happening in the service.
+#[derive(Debug)]
+pub enum QuestionServiceErrorKind {
+    NotFound,
+    InvalidRequest,
+    Forbidden,
+    Internal,
+}
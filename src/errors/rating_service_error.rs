@@ -0,0 +1,14 @@
+/// Error type for RatingService.
+/// It is thrown anytime an error occurs in the service.
+#[derive(Debug)]
+pub struct RatingServiceError {
+    pub message: String,
+    pub kind: RatingServiceErrorKind,
+}
+
+/// Provides possible error kinds happening in the service.
+#[derive(Debug)]
+pub enum RatingServiceErrorKind {
+    InvalidRequest,
+    Internal,
+}
@@ -0,0 +1,18 @@
+/// Error type for UserService.
+/// It is thrown anytime an error occurs in the service.
+#[derive(Debug)]
+pub struct UserServiceError {
+    pub message: String,
+    pub kind: UserServiceErrorKind,
+}
+
+/// Provides possible error kinds happening in the service.
+#[derive(Debug)]
+pub enum UserServiceErrorKind {
+    NotFound,
+    InvalidRequest,
+    Conflict,
+    Unauthorized,
+    Forbidden,
+    Internal,
+}
@@ -0,0 +1,49 @@
+use log::{error, info};
+use mongodb::bson::DateTime;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::tokio::{task, time};
+use rocket::{Orbit, Rocket};
+use std::time::Duration;
+
+use crate::resource::daily_challenge_resource::today_challenge;
+use crate::service::daily_challenge_service::DailyChallengeService;
+
+/// Seconds to wait before generating the challenge again when it failed.
+pub const DAILY_CHALLENGE_RETRY_SECONDS: u64 = 60;
+
+pub struct DailyChallengeScheduler;
+
+///Fairing to generate the daily challenges.
+///Once the server is up, it generates the challenge in progress, then the challenge of each day when it starts.
+#[rocket::async_trait]
+impl Fairing for DailyChallengeScheduler {
+    fn info(&self) -> Info {
+        Info {
+            name: "Generate the daily challenges",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, _rocket: &Rocket<Orbit>) {
+        task::spawn(async {
+            let daily_challenge_service = DailyChallengeService::init().await;
+            loop {
+                let now = DateTime::now();
+                let wait = match today_challenge(&daily_challenge_service, now).await {
+                    Ok(challenge) => {
+                        info!("Daily challenge of {} ready", challenge.day);
+                        let next_reset = daily_challenge_service.next_reset(now);
+                        Duration::from_millis(
+                            (next_reset.timestamp_millis() - now.timestamp_millis()).max(0) as u64,
+                        )
+                    }
+                    Err(err) => {
+                        error!("Failed to generate the daily challenge: {}", err.message);
+                        Duration::from_secs(DAILY_CHALLENGE_RETRY_SECONDS)
+                    }
+                };
+                time::sleep(wait).await;
+            }
+        });
+    }
+}
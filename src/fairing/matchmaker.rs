@@ -0,0 +1,45 @@
+use log::{error, info};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::tokio::{task, time};
+use rocket::{Orbit, Rocket};
+use std::time::Duration;
+
+use crate::resource::game_resource::start_new_game;
+use crate::service::matchmaking_service::MatchmakingService;
+
+/// Seconds between two attempts to match the players waiting in the queue.
+pub const MATCHMAKING_INTERVAL_SECONDS: u64 = 1;
+
+pub struct Matchmaker;
+
+///Fairing to match the players waiting in the matchmaking queue.
+///Once the server is up, it groups them regularly and runs the games created for the groups.
+#[rocket::async_trait]
+impl Fairing for Matchmaker {
+    fn info(&self) -> Info {
+        Info {
+            name: "Match players waiting in the queue",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, _rocket: &Rocket<Orbit>) {
+        task::spawn(async {
+            let matchmaking_service = MatchmakingService::init().await;
+            let mut interval = time::interval(Duration::from_secs(MATCHMAKING_INTERVAL_SECONDS));
+            loop {
+                interval.tick().await;
+                match matchmaking_service.match_players().await {
+                    Ok(games) => {
+                        for game in games {
+                            let id = game.id.unwrap().to_string();
+                            info!("Starting matched game {}", id);
+                            task::spawn(start_new_game(id));
+                        }
+                    }
+                    Err(err) => error!("Failed to match players: {}", err.message),
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,84 @@
+use log::{error, info};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Build, Rocket};
+use std::env;
+
+use crate::service::api_key_service::ApiKeyService;
+use crate::service::daily_challenge_service::DailyChallengeService;
+use crate::service::game_service::GameService;
+use crate::service::leaderboard_service::LeaderboardService;
+use crate::service::matchmaking_service::MatchmakingService;
+use crate::service::rating_service::RatingService;
+use crate::service::user_service::UserService;
+
+/// Comma separated usernames of the users to make admins at ignition.
+pub const ADMIN_USERNAMES: &str = "ADMIN_USERNAMES";
+
+pub struct Migration;
+
+///Fairing to migrate stored documents to the current data model.
+///It runs once at ignition, before the server accepts requests.
+#[rocket::async_trait]
+impl Fairing for Migration {
+    fn info(&self) -> Info {
+        Info {
+            name: "Migrate stored documents",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        let game_service = GameService::init().await;
+        match game_service.migrate_games().await {
+            Ok(migrated) => info!("{} games migrated to the status lifecycle", migrated),
+            Err(err) => error!("Failed to migrate games: {}", err.message),
+        }
+        let user_service = UserService::init().await;
+        match user_service.create_indexes().await {
+            Ok(_) => info!("User indexes created"),
+            Err(err) => error!("Failed to create user indexes: {}", err.message),
+        }
+        let api_key_service = ApiKeyService::init().await;
+        match api_key_service.create_indexes().await {
+            Ok(_) => info!("API key indexes created"),
+            Err(err) => error!("Failed to create API key indexes: {}", err.message),
+        }
+        let rating_service = RatingService::init().await;
+        match rating_service.create_indexes().await {
+            Ok(_) => info!("Rating indexes created"),
+            Err(err) => error!("Failed to create rating indexes: {}", err.message),
+        }
+        let leaderboard_service = LeaderboardService::init().await;
+        match leaderboard_service.create_indexes().await {
+            Ok(_) => info!("Leaderboard indexes created"),
+            Err(err) => error!("Failed to create leaderboard indexes: {}", err.message),
+        }
+        match leaderboard_service.backfill().await {
+            Ok(recorded) => info!("{} finished games recorded in the leaderboards", recorded),
+            Err(err) => error!("Failed to record finished games: {}", err.message),
+        }
+        let matchmaking_service = MatchmakingService::init().await;
+        match matchmaking_service.create_indexes().await {
+            Ok(_) => info!("Matchmaking indexes created"),
+            Err(err) => error!("Failed to create matchmaking indexes: {}", err.message),
+        }
+        let daily_challenge_service = DailyChallengeService::init().await;
+        match daily_challenge_service.create_indexes().await {
+            Ok(_) => info!("Daily challenge indexes created"),
+            Err(err) => error!("Failed to create daily challenge indexes: {}", err.message),
+        }
+        let admins: Vec<String> = env::var(ADMIN_USERNAMES)
+            .unwrap_or_default()
+            .split(',')
+            .map(|username| username.trim().to_string())
+            .filter(|username| !username.is_empty())
+            .collect();
+        if !admins.is_empty() {
+            match user_service.promote_admins(admins).await {
+                Ok(promoted) => info!("{} users promoted to admin", promoted),
+                Err(err) => error!("Failed to promote admins: {}", err.message),
+            }
+        }
+        Ok(rocket)
+    }
+}
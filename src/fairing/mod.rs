@@ -1,3 +1,6 @@
 pub mod cors;
+pub mod daily_challenge_scheduler;
 pub mod logging;
+pub mod matchmaker;
+pub mod migration;
 pub mod tracing;
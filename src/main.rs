@@ -5,13 +5,37 @@ mod mapper;
 mod model;
 mod repository;
 mod resource;
+mod security;
 mod service;
 
 use crate::fairing::cors::Cors;
+use crate::fairing::daily_challenge_scheduler::DailyChallengeScheduler;
 use crate::fairing::logging::{default_logging_layer, json_logging_layer, LogType};
+use crate::fairing::matchmaker::Matchmaker;
+use crate::fairing::migration::Migration;
 use crate::fairing::tracing::TracingFairing;
+use crate::resource::api_key_resource::{create_api_key, get_api_keys, revoke_api_key};
+use crate::resource::daily_challenge_resource::{
+    answer_daily_attempt, get_daily_attempt, get_daily_challenge, get_daily_leaderboard,
+    get_daily_streak, start_daily_attempt,
+};
 use crate::resource::game_resource::{
-    game_progress, game_register_user, get_game, get_games, patch_game,
+    abort_game, choose_team, game_progress, game_register_user, get_game, get_games,
+    get_scoreboard, join_game, patch_game, pause_game, remove_player, resume_game, reveal_hint,
+    revoke_join_code, rotate_join_code, skip_question, spectate_by_code, spectate_game,
+    stop_spectating, toggle_ready, transfer_host, use_power_up,
+};
+use crate::resource::leaderboard_resource::get_leaderboard;
+use crate::resource::matchmaking_resource::{cancel_ticket, enqueue, get_ticket, ticket_events};
+use crate::resource::practice_resource::{
+    answer_practice, get_practice, get_practice_summary, start_practice,
+};
+use crate::resource::profile_resource::get_profile;
+use crate::resource::question_resource::{create_question, delete_question, get_questions};
+use crate::resource::rating_resource::{get_rating_history, get_topic_leaderboard};
+use crate::resource::user_resource::{
+    create_guest, get_current_user, login, oidc_authorize, oidc_callback, register_user,
+    set_user_roles, upgrade_guest,
 };
 use resource::game_resource::{create_game, game_progress_answer, get_game_answers};
 use rocket::config::LogLevel;
@@ -83,10 +107,62 @@ fn build_rocket() -> Rocket<Build> {
         .mount("/", routes![get_games])
         .mount("/", routes![get_game])
         .mount("/", routes![patch_game])
+        .mount("/", routes![pause_game])
+        .mount("/", routes![resume_game])
+        .mount("/", routes![skip_question])
+        .mount("/", routes![abort_game])
+        .mount("/", routes![remove_player])
+        .mount("/", routes![toggle_ready])
+        .mount("/", routes![choose_team])
+        .mount("/", routes![join_game])
+        .mount("/", routes![spectate_game])
+        .mount("/", routes![spectate_by_code])
+        .mount("/", routes![stop_spectating])
+        .mount("/", routes![rotate_join_code])
+        .mount("/", routes![revoke_join_code])
+        .mount("/", routes![transfer_host])
         .mount("/", routes![game_progress])
         .mount("/", routes![game_progress_answer])
+        .mount("/", routes![use_power_up])
+        .mount("/", routes![reveal_hint])
         .mount("/", routes![game_register_user])
         .mount("/", routes![get_game_answers])
+        .mount("/", routes![get_scoreboard])
+        .mount("/", routes![register_user])
+        .mount("/", routes![login])
+        .mount("/", routes![get_current_user])
+        .mount("/", routes![create_guest])
+        .mount("/", routes![upgrade_guest])
+        .mount("/", routes![oidc_authorize])
+        .mount("/", routes![oidc_callback])
+        .mount("/", routes![set_user_roles])
+        .mount("/", routes![create_question])
+        .mount("/", routes![get_questions])
+        .mount("/", routes![delete_question])
+        .mount("/", routes![create_api_key])
+        .mount("/", routes![get_api_keys])
+        .mount("/", routes![revoke_api_key])
+        .mount("/", routes![get_profile])
+        .mount("/", routes![get_topic_leaderboard])
+        .mount("/", routes![get_rating_history])
+        .mount("/", routes![get_leaderboard])
+        .mount("/", routes![enqueue])
+        .mount("/", routes![get_ticket])
+        .mount("/", routes![ticket_events])
+        .mount("/", routes![cancel_ticket])
+        .mount("/", routes![start_practice])
+        .mount("/", routes![get_practice])
+        .mount("/", routes![answer_practice])
+        .mount("/", routes![get_practice_summary])
+        .mount("/", routes![get_daily_challenge])
+        .mount("/", routes![start_daily_attempt])
+        .mount("/", routes![get_daily_attempt])
+        .mount("/", routes![answer_daily_attempt])
+        .mount("/", routes![get_daily_leaderboard])
+        .mount("/", routes![get_daily_streak])
+        .attach(Migration)
+        .attach(Matchmaker)
+        .attach(DailyChallengeScheduler)
         .attach(Cors)
         .attach(TracingFairing)
 }
@@ -97,9 +173,12 @@ fn options_all() {}
 #[cfg(test)]
 mod tests {
     use crate::build_rocket;
+    use crate::model::game::GameStatus;
     use crate::resource::game_resource::rocket_uri_macro_get_games;
+    use crate::resource::user_resource::rocket_uri_macro_get_current_user;
+    use crate::service::user_service::UserService;
     use log::info;
-    use rocket::http::Status;
+    use rocket::http::{Header, Status};
     use rocket::local::asynchronous::Client;
     use rocket::{async_test, uri};
     use serial_test::serial;
@@ -121,8 +200,51 @@ mod tests {
         let client = Client::tracked(build_rocket())
             .await
             .expect("valid rocket instance");
-        let response = client.get(uri!(get_games)).dispatch().await;
+        let response = client
+            .get(uri!(get_games(status = Vec::<GameStatus>::new())))
+            .dispatch()
+            .await;
         assert_eq!(response.status(), Status::Ok);
         assert_eq!(response.into_string().await.unwrap(), "[]");
     }
+
+    #[async_test]
+    #[serial]
+    async fn guest_tokens_should_be_refused_once_the_guest_is_upgraded() {
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+
+        let client = Client::tracked(build_rocket())
+            .await
+            .expect("valid rocket instance");
+        let user_service = UserService::init().await;
+        let (token, claims) = user_service.create_guest("alice".to_string()).unwrap();
+        let authorization = Header::new("Authorization", format!("Bearer {}", token));
+        let response = client
+            .get(uri!(get_current_user))
+            .header(authorization.clone())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::NotFound);
+        user_service
+            .upgrade_guest(
+                claims.sub,
+                true,
+                "alice".to_string(),
+                "correct horse".to_string(),
+            )
+            .await
+            .unwrap();
+        let response = client
+            .get(uri!(get_current_user))
+            .header(authorization)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
 }
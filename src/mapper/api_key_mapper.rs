@@ -0,0 +1,29 @@
+use crate::dto::api_key_dto::ApiKeyDto;
+use crate::model::api_key::ApiKey;
+use mongodb::bson::DateTime;
+
+///this mapper is used to map between the [ApiKey](crate::model::api_key::ApiKey) and [ApiKeyDto](crate::dto::api_key_dto::ApiKeyDto) models
+pub fn to_dto(api_key: ApiKey) -> ApiKeyDto {
+    ApiKeyDto {
+        id: api_key.id.map(|id| id.to_string()),
+        name: api_key.name,
+        hint: api_key.hint,
+        scopes: api_key.scopes,
+        key: None,
+        created_at: to_rfc3339(api_key.created_at),
+        last_used_at: api_key.last_used_at.map(to_rfc3339),
+        revoked_at: api_key.revoked_at.map(to_rfc3339),
+    }
+}
+
+/// Maps a newly created [ApiKey], the key itself is given this time only.
+pub fn to_created_dto(key: String, api_key: ApiKey) -> ApiKeyDto {
+    ApiKeyDto {
+        key: Some(key),
+        ..to_dto(api_key)
+    }
+}
+
+fn to_rfc3339(date: DateTime) -> String {
+    date.try_to_rfc3339_string().unwrap_or_default()
+}
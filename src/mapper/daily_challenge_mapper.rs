@@ -0,0 +1,112 @@
+use crate::dto::daily_challenge_dto::{
+    DailyAttemptDto, DailyChallengeDto, DailyFeedbackDto, DailyLeaderboardDto, DailyRankDto,
+    DailyStreakDto,
+};
+use crate::mapper::question_mapper;
+use crate::model::daily_challenge::{
+    DailyAttempt, DailyChallenge, DailyFeedback, DailyLeaderboard, DailyStreak, RankedAttempt,
+};
+use crate::resource::game_resource::QUESTION_SECONDS;
+use mongodb::bson::DateTime;
+
+///this mapper is used to map between the [DailyChallenge](crate::model::daily_challenge::DailyChallenge) and [DailyChallengeDto](crate::dto::daily_challenge_dto::DailyChallengeDto) models
+pub fn to_dto(
+    challenge: &DailyChallenge,
+    attempt: Option<DailyAttemptDto>,
+    streak: Option<DailyStreak>,
+) -> DailyChallengeDto {
+    let mut topics: Vec<String> = challenge
+        .questions
+        .iter()
+        .map(|question| question.topic.clone())
+        .collect();
+    topics.sort();
+    topics.dedup();
+    DailyChallengeDto {
+        day: challenge.day.clone(),
+        topics,
+        question_number: challenge.questions.len() as i8,
+        starts_at: to_rfc3339(challenge.starts_at),
+        ends_at: to_rfc3339(challenge.ends_at),
+        attempt,
+        streak: streak.map(streak_to_dto),
+    }
+}
+
+///The question is given without its correct answer, with the time left to answer it
+pub fn attempt_to_dto(
+    challenge: &DailyChallenge,
+    attempt: DailyAttempt,
+    now: DateTime,
+) -> DailyAttemptDto {
+    let finished = attempt.finished_at.is_some();
+    let remaining_time = (!finished).then(|| attempt.remaining_time(now, QUESTION_SECONDS));
+    let question = challenge
+        .questions
+        .get(attempt.current_question as usize)
+        .filter(|_| !finished)
+        .cloned()
+        .map(|mut question| {
+            question.remaining_time = remaining_time.unwrap_or(0);
+            question_mapper::to_dto(question)
+        });
+    DailyAttemptDto {
+        day: attempt.day,
+        question_number: challenge.questions.len() as i8,
+        current_question: attempt.current_question,
+        question,
+        remaining_time,
+        score: attempt.score,
+        answer_seconds: attempt.answer_seconds,
+        finished,
+    }
+}
+
+pub fn feedback_to_dto(
+    challenge: &DailyChallenge,
+    feedback: DailyFeedback,
+    now: DateTime,
+) -> DailyFeedbackDto {
+    DailyFeedbackDto {
+        answer: feedback.answer,
+        correct: feedback.correct,
+        timed_out: feedback.timed_out,
+        attempt: attempt_to_dto(challenge, feedback.attempt, now),
+    }
+}
+
+pub fn leaderboard_to_dto(
+    leaderboard: DailyLeaderboard,
+    page: u64,
+    size: i64,
+) -> DailyLeaderboardDto {
+    DailyLeaderboardDto {
+        day: leaderboard.day,
+        page,
+        size,
+        attempts: leaderboard.attempts.into_iter().map(rank_to_dto).collect(),
+        me: leaderboard.me.map(rank_to_dto),
+    }
+}
+
+pub fn rank_to_dto(ranked: RankedAttempt) -> DailyRankDto {
+    DailyRankDto {
+        rank: ranked.rank,
+        user: ranked.attempt.user,
+        score: ranked.attempt.score,
+        answer_seconds: ranked.attempt.answer_seconds,
+        finished: ranked.attempt.finished_at.is_some(),
+    }
+}
+
+pub fn streak_to_dto(streak: DailyStreak) -> DailyStreakDto {
+    DailyStreakDto {
+        current: streak.current,
+        best: streak.best,
+        last_day: streak.last_day,
+    }
+}
+
+fn to_rfc3339(date: DateTime) -> String {
+    date.try_to_rfc3339_string().unwrap_or_default()
+}
@@ -1,10 +1,22 @@
 use crate::dto::answer::GameAnswerDto;
-use crate::dto::game_dto::GameDto;
+use crate::dto::game_dto::{
+    GameBuzzDto, GameDamageDto, GameDto, GameEliminationDto, GameHintDto, GameHostActionDto,
+    GamePowerUpDto, GameStandingDto, GameStatusChangeDto, GameTeamDto, HitPointsDto, LobbyDto,
+    LobbySettingsDto, ScoreboardDto, SpectatorsDto, TeamStandingDto,
+};
 use crate::dto::game_progress_dto::GameProgressDto;
 use crate::mapper::question_mapper;
-use crate::model::game::Game;
+use crate::model::buzzer::GameBuzz;
+use crate::model::duel::GameDamage;
+use crate::model::game::{
+    Game, GameElimination, GameHostAction, GameMode, GameScoreboard, GameStanding,
+    GameStatusChange, LobbySettings,
+};
 use crate::model::game_answer::GameAnswer;
 use crate::model::game_progress::GameProgress;
+use crate::model::hint::GameHint;
+use crate::model::power_up::GamePowerUp;
+use crate::model::team::{GameTeam, TeamStanding};
 use mongodb::bson::oid::ObjectId;
 use std::str::FromStr;
 
@@ -18,22 +30,269 @@ pub fn to_entity(game_dto: GameDto) -> Game {
         topics: game_dto.topics,
         question_number: game_dto.question_number,
         is_private: game_dto.is_private,
-        is_started: game_dto.is_started,
+        join_code: None,
+        join_password_hash: None,
+        spectate_code: None,
+        status: game_dto.status,
+        status_changed_at: None,
+        status_history: vec![],
+        mode: game_dto.mode,
         creator: game_dto.creator,
         users: game_dto.users,
+        lobby_settings: lobby_settings_to_entity(game_dto.lobby_settings),
+        ready_users: game_dto.ready_users,
+        kicked_users: vec![],
+        spectators: vec![],
+        host_actions: vec![],
+        standings: vec![],
+        eliminations: vec![],
+        damages: vec![],
+        buzzes: vec![],
+        miss_limit: game_dto.miss_limit,
+        power_ups: game_dto.power_ups,
+        used_power_ups: vec![],
+        hint_penalty: game_dto.hint_penalty,
+        revealed_hints: vec![],
+        teams: game_dto.teams.into_iter().map(team_to_entity).collect(),
+        team_scoring: game_dto.team_scoring,
+        team_standings: vec![],
     }
 }
 
 ///this mapper is used to map between the [Game](crate::model::game::Game) and [GameDto](crate::dto::game_dto::GameDto) models
 pub fn to_dto(game: Game) -> GameDto {
+    let hit_points = match game.mode {
+        GameMode::Duel => game
+            .users
+            .iter()
+            .map(|user| HitPointsDto {
+                user: user.clone(),
+                hit_points: game.hit_points(user),
+            })
+            .collect(),
+        _ => vec![],
+    };
+    let miss_limit = match game.mode {
+        GameMode::Survival => Some(game.miss_limit()),
+        _ => None,
+    };
+    let hint_penalty = Some(game.hint_penalty());
     GameDto {
         id: Some(game.id.expect("Failed to get game id").to_string()),
         topics: game.topics,
         question_number: game.question_number,
         is_private: game.is_private,
-        is_started: game.is_started,
+        join_code: game.join_code,
+        password: None,
+        spectate_code: game.spectate_code,
+        status: game.status,
+        status_history: game
+            .status_history
+            .into_iter()
+            .map(status_change_to_dto)
+            .collect(),
+        mode: game.mode,
         creator: game.creator,
         users: game.users,
+        lobby_settings: lobby_settings_to_dto(game.lobby_settings),
+        ready_users: game.ready_users,
+        spectator_count: game.spectators.len(),
+        host_actions: game
+            .host_actions
+            .into_iter()
+            .map(host_action_to_dto)
+            .collect(),
+        standings: game.standings.into_iter().map(standing_to_dto).collect(),
+        eliminations: game
+            .eliminations
+            .into_iter()
+            .map(elimination_to_dto)
+            .collect(),
+        hit_points,
+        damages: game.damages.into_iter().map(damage_to_dto).collect(),
+        buzzes: game.buzzes.into_iter().map(buzz_to_dto).collect(),
+        miss_limit,
+        power_ups: game.power_ups,
+        used_power_ups: game
+            .used_power_ups
+            .into_iter()
+            .map(power_up_to_dto)
+            .collect(),
+        hint_penalty,
+        revealed_hints: game.revealed_hints.into_iter().map(hint_to_dto).collect(),
+        teams: game.teams.into_iter().map(team_to_dto).collect(),
+        team_scoring: game.team_scoring,
+        team_standings: game
+            .team_standings
+            .into_iter()
+            .map(team_standing_to_dto)
+            .collect(),
+    }
+}
+
+pub fn lobby_settings_to_entity(lobby_settings_dto: LobbySettingsDto) -> LobbySettings {
+    LobbySettings {
+        max_players: lobby_settings_dto.max_players,
+        min_players: lobby_settings_dto.min_players,
+        ready_check: lobby_settings_dto.ready_check,
+    }
+}
+
+pub fn lobby_settings_to_dto(lobby_settings: LobbySettings) -> LobbySettingsDto {
+    LobbySettingsDto {
+        max_players: lobby_settings.max_players,
+        min_players: lobby_settings.min_players,
+        ready_check: lobby_settings.ready_check,
+    }
+}
+
+///this mapper is used to get the [LobbyDto](crate::dto::game_dto::LobbyDto) view of a [Game](crate::model::game::Game)
+pub fn to_lobby(game: &Game) -> LobbyDto {
+    LobbyDto {
+        game_id: game.id.expect("Failed to get game id").to_string(),
+        creator: game.creator.clone(),
+        users: game.users.clone(),
+        ready_users: game.ready_users.clone(),
+        max_players: game.lobby_settings.max_players,
+        min_players: game.lobby_settings.min_players,
+        ready_check: game.lobby_settings.ready_check,
+        can_start: game.can_start(),
+        teams: game.teams.iter().cloned().map(team_to_dto).collect(),
+        spectator_count: game.spectators.len(),
+    }
+}
+
+///this mapper is used to get the [SpectatorsDto](crate::dto::game_dto::SpectatorsDto) of a [Game](crate::model::game::Game)
+pub fn to_spectators(game: &Game) -> SpectatorsDto {
+    SpectatorsDto {
+        game_id: game.id.expect("Failed to get game id").to_string(),
+        spectator_count: game.spectators.len(),
+    }
+}
+
+///this mapper is used to get the [ScoreboardDto](crate::dto::game_dto::ScoreboardDto) of a [Game](crate::model::game::Game)
+pub fn scoreboard_to_dto(game_id: String, scoreboard: GameScoreboard) -> ScoreboardDto {
+    ScoreboardDto {
+        game_id,
+        standings: scoreboard
+            .standings
+            .into_iter()
+            .map(standing_to_dto)
+            .collect(),
+        team_standings: scoreboard
+            .team_standings
+            .into_iter()
+            .map(team_standing_to_dto)
+            .collect(),
+    }
+}
+
+pub fn status_change_to_dto(status_change: GameStatusChange) -> GameStatusChangeDto {
+    GameStatusChangeDto {
+        status: status_change.status,
+        changed_at: status_change
+            .changed_at
+            .try_to_rfc3339_string()
+            .unwrap_or_default(),
+    }
+}
+
+pub fn host_action_to_dto(host_action: GameHostAction) -> GameHostActionDto {
+    GameHostActionDto {
+        kind: host_action.kind,
+        host: host_action.host,
+        player: host_action.player,
+        question_index: host_action.question_index,
+        performed_at: host_action
+            .performed_at
+            .try_to_rfc3339_string()
+            .unwrap_or_default(),
+    }
+}
+
+pub fn standing_to_dto(standing: GameStanding) -> GameStandingDto {
+    GameStandingDto {
+        user: standing.user,
+        score: standing.score,
+        rank: standing.rank,
+    }
+}
+
+pub fn elimination_to_dto(elimination: GameElimination) -> GameEliminationDto {
+    GameEliminationDto {
+        user: elimination.user,
+        question_index: elimination.question_index,
+        eliminated_at: elimination
+            .eliminated_at
+            .try_to_rfc3339_string()
+            .unwrap_or_default(),
+    }
+}
+
+pub fn damage_to_dto(damage: GameDamage) -> GameDamageDto {
+    GameDamageDto {
+        user: damage.user,
+        from: damage.from,
+        question_index: damage.question_index,
+        damage: damage.damage,
+        hit_points: damage.hit_points,
+        dealt_at: damage.dealt_at.try_to_rfc3339_string().unwrap_or_default(),
+    }
+}
+
+/// Maps the use of a power-up, the answers removed by a 50/50 being left out for the other players.
+pub fn power_up_to_dto(power_up: GamePowerUp) -> GamePowerUpDto {
+    GamePowerUpDto {
+        user: power_up.user,
+        power_up: power_up.power_up,
+        question_index: power_up.question_index,
+        target: power_up.target,
+        removed_answers: vec![],
+        used_at: power_up.used_at.try_to_rfc3339_string().unwrap_or_default(),
+    }
+}
+
+/// Maps a hint revealed to a player, its text being left out for the other players.
+pub fn hint_to_dto(hint: GameHint) -> GameHintDto {
+    GameHintDto {
+        user: hint.user,
+        question_index: hint.question_index,
+        hint_index: hint.hint_index,
+        hint: None,
+        penalty: hint.penalty,
+        revealed_at: hint.revealed_at.try_to_rfc3339_string().unwrap_or_default(),
+    }
+}
+
+pub fn buzz_to_dto(buzz: GameBuzz) -> GameBuzzDto {
+    GameBuzzDto {
+        user: buzz.user,
+        question_index: buzz.question_index,
+        answer: buzz.answer,
+        correct: buzz.correct,
+        buzzed_at: buzz.buzzed_at.try_to_rfc3339_string().unwrap_or_default(),
+    }
+}
+
+pub fn team_to_entity(team_dto: GameTeamDto) -> GameTeam {
+    GameTeam {
+        name: team_dto.name,
+        users: team_dto.users,
+    }
+}
+
+pub fn team_to_dto(team: GameTeam) -> GameTeamDto {
+    GameTeamDto {
+        name: team.name,
+        users: team.users,
+    }
+}
+
+pub fn team_standing_to_dto(team_standing: TeamStanding) -> TeamStandingDto {
+    TeamStandingDto {
+        team: team_standing.team,
+        score: team_standing.score,
+        rank: team_standing.rank,
     }
 }
 
@@ -44,6 +303,7 @@ pub fn progress_to_entity(game_progress_dto: GameProgressDto) -> GameProgress {
         current_question: game_progress_dto.current_question,
         question_number: game_progress_dto.question_number,
         question_content: question_mapper::to_entity(question),
+        overtime: game_progress_dto.overtime,
     }
 }
 
@@ -54,6 +314,7 @@ pub fn entity_to_progress(game_progress: GameProgress) -> GameProgressDto {
         current_question: game_progress.current_question,
         question_number: game_progress.question_number,
         question_content: question_mapper::to_dto(question),
+        overtime: game_progress.overtime,
     }
 }
 
@@ -65,6 +326,11 @@ pub fn answer_to_entity(game_answer_dto: GameAnswerDto) -> GameAnswer {
         question_index: game_answer_dto.question_index,
         correct_answer: game_answer_dto.correct_answer,
         question: question_mapper::to_entity(game_answer_dto.question),
+        practice: false,
+        daily: false,
+        wager: game_answer_dto.wager,
+        power_up: game_answer_dto.power_up,
+        answer_seconds: game_answer_dto.answer_seconds,
     }
 }
 
@@ -76,5 +342,8 @@ pub fn entity_to_answer(game_answer: GameAnswer) -> GameAnswerDto {
         question_index: game_answer.question_index,
         correct_answer: game_answer.correct_answer,
         question: question_mapper::to_dto(game_answer.question),
+        wager: game_answer.wager,
+        power_up: game_answer.power_up,
+        answer_seconds: game_answer.answer_seconds,
     }
 }
@@ -0,0 +1,25 @@
+use crate::dto::leaderboard_dto::{LeaderboardDto, LeaderboardEntryDto};
+use crate::model::leaderboard::{Leaderboard, RankedEntry};
+
+///this mapper is used to map between the [Leaderboard](crate::model::leaderboard::Leaderboard) and [LeaderboardDto](crate::dto::leaderboard_dto::LeaderboardDto) models
+pub fn to_dto(leaderboard: Leaderboard, page: u64, size: i64) -> LeaderboardDto {
+    LeaderboardDto {
+        season: leaderboard.season,
+        topic: leaderboard.topic,
+        page,
+        size,
+        entries: leaderboard.entries.into_iter().map(entry_to_dto).collect(),
+        me: leaderboard.me.map(entry_to_dto),
+    }
+}
+
+pub fn entry_to_dto(ranked: RankedEntry) -> LeaderboardEntryDto {
+    LeaderboardEntryDto {
+        rank: ranked.rank,
+        user: ranked.entry.user,
+        score: ranked.entry.score,
+        answers: ranked.entry.answers,
+        games: ranked.entry.games,
+        wins: ranked.entry.wins,
+    }
+}
@@ -0,0 +1,27 @@
+use crate::dto::matchmaking_dto::TicketDto;
+use crate::model::matchmaking::{MatchRules, MatchmakingTicket, TicketStatus};
+use mongodb::bson::DateTime;
+
+///this mapper is used to map between the [MatchmakingTicket](crate::model::matchmaking::MatchmakingTicket) and [TicketDto](crate::dto::matchmaking_dto::TicketDto) models.
+///The waiting time stops when the ticket leaves the queue.
+pub fn to_dto(ticket: MatchmakingTicket, now: DateTime) -> TicketDto {
+    let waited_until = match ticket.status {
+        TicketStatus::Waiting => now,
+        _ => ticket.status_changed_at,
+    };
+    let waited_seconds =
+        (waited_until.timestamp_millis() - ticket.created_at.timestamp_millis()) / 1000;
+    let rules = MatchRules::after(waited_seconds);
+    TicketDto {
+        id: ticket.id.map(|id| id.to_string()),
+        user: ticket.user,
+        topics: ticket.topics,
+        rating: ticket.rating,
+        status: ticket.status,
+        game_id: ticket.game_id,
+        waited_seconds,
+        rating_gap: rules.rating_gap,
+        min_players: rules.min_players,
+        any_topic: rules.any_topic,
+    }
+}
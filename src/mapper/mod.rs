@@ -1,2 +1,10 @@
+pub mod api_key_mapper;
+pub mod daily_challenge_mapper;
 pub mod game_mapper;
+pub mod leaderboard_mapper;
+pub mod matchmaking_mapper;
+pub mod practice_mapper;
+pub mod profile_mapper;
 pub mod question_mapper;
+pub mod rating_mapper;
+pub mod user_mapper;
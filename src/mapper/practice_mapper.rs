@@ -0,0 +1,69 @@
+use crate::dto::practice_dto::{
+    PracticeFeedbackDto, PracticeResultDto, PracticeSessionDto, PracticeSummaryDto,
+};
+use crate::mapper::{profile_mapper, question_mapper};
+use crate::model::practice::{PracticeFeedback, PracticeResult, PracticeSession, PracticeSummary};
+use crate::resource::game_resource::QUESTION_SECONDS;
+use mongodb::bson::DateTime;
+
+///this mapper is used to map between the [PracticeSession](crate::model::practice::PracticeSession) and [PracticeSessionDto](crate::dto::practice_dto::PracticeSessionDto) models.
+///The question is given without its correct answer, with the time left to answer it.
+pub fn to_dto(session: PracticeSession, now: DateTime) -> PracticeSessionDto {
+    let remaining_time = session
+        .current()
+        .and(session.remaining_time(now, QUESTION_SECONDS));
+    let question = session.current().cloned().map(|mut question| {
+        question.remaining_time = remaining_time.unwrap_or(0);
+        question_mapper::to_dto(question)
+    });
+    PracticeSessionDto {
+        id: session.id.map(|id| id.to_string()),
+        topics: session.topics,
+        timed: session.timed,
+        question_number: session.questions.len() as i8,
+        current_question: session.current_question,
+        question,
+        remaining_time,
+        finished: session.finished_at.is_some(),
+    }
+}
+
+pub fn feedback_to_dto(feedback: PracticeFeedback, now: DateTime) -> PracticeFeedbackDto {
+    PracticeFeedbackDto {
+        answer: feedback.answer,
+        correct_answer: feedback.correct_answer,
+        correct: feedback.correct,
+        timed_out: feedback.timed_out,
+        session: to_dto(feedback.session, now),
+    }
+}
+
+pub fn summary_to_dto(
+    session: PracticeSession,
+    summary: PracticeSummary,
+    now: DateTime,
+) -> PracticeSummaryDto {
+    PracticeSummaryDto {
+        session: to_dto(session, now),
+        answers: summary.answers,
+        correct_answers: summary.correct_answers,
+        accuracy: summary.accuracy,
+        average_answer_seconds: summary.average_answer_seconds,
+        topics: summary
+            .topics
+            .into_iter()
+            .map(profile_mapper::topic_to_dto)
+            .collect(),
+        results: summary.results.into_iter().map(result_to_dto).collect(),
+    }
+}
+
+pub fn result_to_dto(result: PracticeResult) -> PracticeResultDto {
+    PracticeResultDto {
+        question_text: result.question.question_text,
+        topic: result.question.topic,
+        answer: result.answer,
+        correct_answer: result.question.good_answer_number,
+        correct: result.correct,
+    }
+}
@@ -0,0 +1,73 @@
+use crate::dto::profile_dto::{
+    ProfileDto, RecentGameDto, SurvivalBestDto, TopicRatingDto, TopicStatsDto,
+};
+use crate::model::profile::{PlayerProfile, RecentGame, SurvivalBest, TopicRating, TopicStats};
+use mongodb::bson::DateTime;
+
+///this mapper is used to map between the [PlayerProfile](crate::model::profile::PlayerProfile) and [ProfileDto](crate::dto::profile_dto::ProfileDto) models
+pub fn to_dto(profile: PlayerProfile) -> ProfileDto {
+    ProfileDto {
+        user: profile.user,
+        games_played: profile.games_played,
+        wins: profile.wins,
+        answers: profile.answers,
+        correct_answers: profile.correct_answers,
+        accuracy: profile.accuracy,
+        average_answer_seconds: profile.average_answer_seconds,
+        best_streak: profile.best_streak,
+        topics: profile.topics.into_iter().map(topic_to_dto).collect(),
+        recent_games: profile
+            .recent_games
+            .into_iter()
+            .map(recent_game_to_dto)
+            .collect(),
+        ratings: profile.ratings.into_iter().map(rating_to_dto).collect(),
+        survival_bests: profile
+            .survival_bests
+            .into_iter()
+            .map(survival_best_to_dto)
+            .collect(),
+        updated_at: to_rfc3339(profile.updated_at),
+    }
+}
+
+pub fn topic_to_dto(topic: TopicStats) -> TopicStatsDto {
+    TopicStatsDto {
+        topic: topic.topic,
+        answers: topic.answers,
+        correct_answers: topic.correct_answers,
+        accuracy: topic.accuracy,
+    }
+}
+
+pub fn rating_to_dto(rating: TopicRating) -> TopicRatingDto {
+    TopicRatingDto {
+        topic: rating.topic,
+        rating: rating.rating,
+        games: rating.games,
+    }
+}
+
+pub fn recent_game_to_dto(game: RecentGame) -> RecentGameDto {
+    RecentGameDto {
+        game_id: game.game_id,
+        topics: game.topics,
+        score: game.score,
+        rank: game.rank,
+        players: game.players,
+        finished_at: game.finished_at.map(to_rfc3339),
+    }
+}
+
+pub fn survival_best_to_dto(best: SurvivalBest) -> SurvivalBestDto {
+    SurvivalBestDto {
+        topic: best.topic,
+        score: best.score,
+        game_id: best.game_id,
+        achieved_at: best.achieved_at.map(to_rfc3339),
+    }
+}
+
+fn to_rfc3339(date: DateTime) -> String {
+    date.try_to_rfc3339_string().unwrap_or_default()
+}
@@ -1,4 +1,8 @@
+use crate::dto::question_dto::AuthoredQuestionDto;
+use crate::model::question::AuthoredQuestion;
+use crate::resource::game_resource::QUESTION_SECONDS;
 use crate::{dto::game_progress_dto::QuestionDto, model::game_progress::Question};
+use mongodb::bson::DateTime;
 
 pub fn to_dto(question: Question) -> QuestionDto {
     QuestionDto {
@@ -10,6 +14,7 @@ pub fn to_dto(question: Question) -> QuestionDto {
         good_answer_number: question.good_answer_number,
         topic: question.topic,
         remaining_time: question.remaining_time,
+        hints: question.hints,
     }
 }
 
@@ -23,5 +28,52 @@ pub fn to_entity(question: QuestionDto) -> Question {
         good_answer_number: question.good_answer_number,
         topic: question.topic,
         remaining_time: question.remaining_time,
+        hints: question.hints,
+    }
+}
+
+pub fn authored_to_dto(question: AuthoredQuestion) -> AuthoredQuestionDto {
+    AuthoredQuestionDto {
+        id: question.id.map(|id| id.to_string()),
+        author: Some(question.author),
+        question_text: question.question_text,
+        answer_1: question.answer_1,
+        answer_2: question.answer_2,
+        answer_3: question.answer_3,
+        answer_4: question.answer_4,
+        good_answer_number: question.good_answer_number,
+        topic: question.topic,
+        hints: question.hints,
+    }
+}
+
+pub fn authored_to_entity(question: AuthoredQuestionDto, author: String) -> AuthoredQuestion {
+    AuthoredQuestion {
+        id: None,
+        author,
+        question_text: question.question_text,
+        answer_1: question.answer_1,
+        answer_2: question.answer_2,
+        answer_3: question.answer_3,
+        answer_4: question.answer_4,
+        good_answer_number: question.good_answer_number,
+        topic: question.topic,
+        hints: question.hints,
+        created_at: DateTime::now(),
+    }
+}
+
+/// Maps an [AuthoredQuestion] to a question asked in a game, with the full answer time.
+pub fn authored_to_game_question(question: AuthoredQuestion) -> QuestionDto {
+    QuestionDto {
+        question_text: question.question_text,
+        answer_1: question.answer_1,
+        answer_2: question.answer_2,
+        answer_3: question.answer_3,
+        answer_4: question.answer_4,
+        good_answer_number: question.good_answer_number,
+        topic: question.topic,
+        remaining_time: QUESTION_SECONDS,
+        hints: question.hints,
     }
 }
@@ -0,0 +1,28 @@
+use crate::dto::rating_dto::{RatingChangeDto, RatingDto};
+use crate::model::rating::{PlayerRating, RatingChange};
+use mongodb::bson::DateTime;
+
+///this mapper is used to map between the [PlayerRating](crate::model::rating::PlayerRating) and [RatingDto](crate::dto::rating_dto::RatingDto) models
+pub fn to_dto(rank: u64, rating: PlayerRating) -> RatingDto {
+    RatingDto {
+        rank,
+        user: rating.user,
+        topic: rating.topic,
+        rating: rating.rating,
+        games: rating.games,
+        updated_at: to_rfc3339(rating.updated_at),
+    }
+}
+
+pub fn change_to_dto(change: RatingChange) -> RatingChangeDto {
+    RatingChangeDto {
+        game_id: change.game_id,
+        rating_before: change.rating_before,
+        rating: change.rating,
+        changed_at: to_rfc3339(change.changed_at),
+    }
+}
+
+fn to_rfc3339(date: DateTime) -> String {
+    date.try_to_rfc3339_string().unwrap_or_default()
+}
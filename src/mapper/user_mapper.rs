@@ -0,0 +1,23 @@
+use crate::dto::user_dto::{TokenDto, UserDto};
+use crate::model::user::User;
+use crate::security::token::Claims;
+use mongodb::bson::DateTime;
+
+///this mapper is used to map between the [User](crate::model::user::User) and [UserDto](crate::dto::user_dto::UserDto) models
+pub fn to_dto(user: User) -> UserDto {
+    UserDto {
+        id: user.id.map(|id| id.to_string()),
+        username: user.username,
+        roles: user.roles,
+    }
+}
+
+pub fn to_token(token: String, claims: Claims) -> TokenDto {
+    TokenDto {
+        token,
+        token_type: "Bearer".to_string(),
+        expires_at: DateTime::from_millis(claims.exp as i64 * 1000)
+            .try_to_rfc3339_string()
+            .unwrap_or_default(),
+    }
+}
@@ -0,0 +1,32 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+///API key of a [User](crate::model::user::User), to be stored in the database.
+///Only a hash of the key is kept, the key itself is shown once to its owner when created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub owner: String,
+    pub name: String,
+    pub hint: String,
+    pub key_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub created_at: DateTime,
+    #[serde(default)]
+    pub last_used_at: Option<DateTime>,
+    #[serde(default)]
+    pub revoked_at: Option<DateTime>,
+}
+
+///Scope of an [ApiKey], limiting what the key may be used for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ApiKeyScope {
+    #[serde(rename = "games:create")]
+    GamesCreate,
+    #[serde(rename = "games:control")]
+    GamesControl,
+    #[serde(rename = "questions:write")]
+    QuestionsWrite,
+}
@@ -0,0 +1,76 @@
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+///Answer of a player to a question of a [Game](crate::model::game::Game) played in
+///[GameMode::Buzzer](crate::model::game::GameMode::Buzzer), stored with the game in the order the answers were received.
+///A player buzzes once per question, and nobody buzzes anymore once a player answered correctly.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameBuzz {
+    pub user: String,
+    pub question_index: i8,
+    pub answer: i8,
+    pub correct: bool,
+    pub buzzed_at: DateTime,
+}
+
+/// Gives the player who won a question, the first one who answered it correctly.
+pub fn question_winner(question_index: i8, buzzes: &[GameBuzz]) -> Option<&str> {
+    buzzes
+        .iter()
+        .find(|buzz| buzz.question_index == question_index && buzz.correct)
+        .map(|buzz| buzz.user.as_str())
+}
+
+/// Computes the score of each player of a buzzer game, the number of questions they won.
+/// Players who won no question are left out.
+pub fn buzzer_scores(buzzes: &[GameBuzz]) -> Vec<(String, i32)> {
+    let mut scores: Vec<(String, i32)> = vec![];
+    for buzz in buzzes.iter().filter(|buzz| buzz.correct) {
+        match scores.iter_mut().find(|(user, _)| *user == buzz.user) {
+            Some((_, score)) => *score += 1,
+            None => scores.push((buzz.user.clone(), 1)),
+        }
+    }
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::buzzer::{buzzer_scores, question_winner, GameBuzz};
+    use mongodb::bson::DateTime;
+
+    fn buzz(user: &str, question_index: i8, correct: bool) -> GameBuzz {
+        GameBuzz {
+            user: user.to_string(),
+            question_index,
+            answer: if correct { 2 } else { 1 },
+            correct,
+            buzzed_at: DateTime::now(),
+        }
+    }
+
+    #[test]
+    fn question_winner_should_be_the_first_correct_buzz() {
+        let buzzes = vec![
+            buzz("alice", 0, false),
+            buzz("bob", 0, true),
+            buzz("carol", 1, false),
+        ];
+        assert_eq!(question_winner(0, &buzzes), Some("bob"));
+        assert_eq!(question_winner(1, &buzzes), None);
+    }
+
+    #[test]
+    fn buzzer_scores_should_count_the_questions_won() {
+        let buzzes = vec![
+            buzz("alice", 0, false),
+            buzz("bob", 0, true),
+            buzz("alice", 1, true),
+            buzz("bob", 2, true),
+        ];
+        assert_eq!(
+            buzzer_scores(&buzzes),
+            vec![("bob".to_string(), 2), ("alice".to_string(), 1)]
+        );
+    }
+}
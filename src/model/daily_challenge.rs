@@ -0,0 +1,336 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rocket::serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::model::game_progress::Question;
+
+/// Number of questions of a daily challenge.
+pub const DAILY_CHALLENGE_QUESTIONS: usize = 10;
+/// Topics the questions of a daily challenge are picked from.
+pub const DAILY_CHALLENGE_TOPICS: [&str; 3] = ["Java", "Kotlin", "Rust"];
+const MILLIS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+///Questions of the challenge of a day, to be stored in the database.
+///Every player answers the same questions, the day being identified as `YYYY-MM-DD`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyChallenge {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub day: String,
+    pub questions: Vec<Question>,
+    pub starts_at: DateTime,
+    pub ends_at: DateTime,
+}
+
+///Attempt of a player at the challenge of a day, to be stored in the database.
+///A player attempts a challenge only once, answering its questions one after the other against the clock.
+///`answer_seconds` is the time spent on the answered questions, telling apart players with the same score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyAttempt {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub day: String,
+    pub user: String,
+    pub current_question: i8,
+    pub question_started_at: DateTime,
+    pub score: i32,
+    pub answer_seconds: i64,
+    pub started_at: DateTime,
+    pub finished_at: Option<DateTime>,
+}
+
+impl DailyAttempt {
+    /// Gives the seconds left to answer the current question out of `question_seconds`.
+    pub fn remaining_time(&self, now: DateTime, question_seconds: u64) -> u64 {
+        let elapsed = now.timestamp_millis() - self.question_started_at.timestamp_millis();
+        question_seconds.saturating_sub((elapsed.max(0) / 1000) as u64)
+    }
+}
+
+///Result of an answer to the daily challenge.
+///The correct answer is not given, so it does not spread to the players who did not attempt the challenge yet.
+#[derive(Debug, Clone)]
+pub struct DailyFeedback {
+    pub answer: i8,
+    pub correct: bool,
+    pub timed_out: bool,
+    pub attempt: DailyAttempt,
+}
+
+///[DailyAttempt] with the rank of the player on the daily leaderboard.
+///Players with the same score and answer time share the same rank.
+#[derive(Debug, Clone)]
+pub struct RankedAttempt {
+    pub rank: u64,
+    pub attempt: DailyAttempt,
+}
+
+///Page of the leaderboard of a day, with the attempt of the player asking for it when they have one
+#[derive(Debug, Clone)]
+pub struct DailyLeaderboard {
+    pub day: String,
+    pub attempts: Vec<RankedAttempt>,
+    pub me: Option<RankedAttempt>,
+}
+
+///Days in a row a player attempted the daily challenge.
+///The current streak is kept until the end of the day after the last attempt.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DailyStreak {
+    pub current: u32,
+    pub best: u32,
+    pub last_day: Option<String>,
+}
+
+///Time of the day, in UTC, when the challenge of the next day starts
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct DailyReset {
+    minutes: i64,
+}
+
+impl DailyReset {
+    /// Reads a reset time formatted as `HH:MM`.
+    /// Returns midnight if no time is given.
+    /// Returns an error if the time is not valid.
+    pub fn parse(time: &str) -> Result<Self, String> {
+        let time = time.trim();
+        if time.is_empty() {
+            return Ok(DailyReset::default());
+        }
+        let invalid = || format!("Daily challenge time {} is not a HH:MM time", time);
+        let (hours, minutes) = time.split_once(':').ok_or_else(invalid)?;
+        let hours: i64 = hours.parse().map_err(|_| invalid())?;
+        let minutes: i64 = minutes.parse().map_err(|_| invalid())?;
+        if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+            return Err(invalid());
+        }
+        Ok(DailyReset {
+            minutes: hours * 60 + minutes,
+        })
+    }
+
+    /// Gives the day of the challenge in progress at a date.
+    pub fn day_of(&self, date: DateTime) -> String {
+        let shifted = DateTime::from_millis(date.timestamp_millis() - self.offset_millis());
+        shifted
+            .try_to_rfc3339_string()
+            .map(|date| date[..10].to_string())
+            .unwrap_or_default()
+    }
+
+    /// Gives the start and the end of the challenge of a day.
+    /// Returns none if the day is not a `YYYY-MM-DD` date.
+    pub fn bounds(&self, day: &str) -> Option<(DateTime, DateTime)> {
+        let midnight = DateTime::parse_rfc3339_str(format!("{}T00:00:00Z", day)).ok()?;
+        let starts_at = midnight.timestamp_millis() + self.offset_millis();
+        Some((
+            DateTime::from_millis(starts_at),
+            DateTime::from_millis(starts_at + MILLIS_PER_DAY),
+        ))
+    }
+
+    /// Gives the day before a day.
+    pub fn previous_day(day: &str) -> Option<String> {
+        let midnight = DateTime::parse_rfc3339_str(format!("{}T00:00:00Z", day)).ok()?;
+        DateTime::from_millis(midnight.timestamp_millis() - MILLIS_PER_DAY)
+            .try_to_rfc3339_string()
+            .ok()
+            .map(|date| date[..10].to_string())
+    }
+
+    fn offset_millis(&self) -> i64 {
+        self.minutes * 60 * 1000
+    }
+}
+
+/// Picks the questions of the challenge of a day, always the same ones for a given day and pool.
+/// Topics take turns so the challenge spreads across them, then the questions are shuffled.
+pub fn pick_questions(pool: Vec<Question>, count: usize, day: &str) -> Vec<Question> {
+    let seed = day
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .fold(0u64, |seed, digit| seed * 10 + (digit - b'0') as u64);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut topics: BTreeMap<String, Vec<Question>> = BTreeMap::new();
+    for question in pool {
+        topics
+            .entry(question.topic.clone())
+            .or_default()
+            .push(question);
+    }
+    let mut topics: Vec<Vec<Question>> = topics
+        .into_values()
+        .map(|mut questions| {
+            questions.sort_by(|a, b| a.question_text.cmp(&b.question_text));
+            questions.shuffle(&mut rng);
+            questions.reverse();
+            questions
+        })
+        .collect();
+    let mut questions = vec![];
+    while questions.len() < count && topics.iter().any(|topic| !topic.is_empty()) {
+        for topic in &mut topics {
+            if questions.len() < count {
+                questions.extend(topic.pop());
+            }
+        }
+    }
+    questions.shuffle(&mut rng);
+    questions
+}
+
+/// Ranks a page of attempts sorted by decreasing score, then by increasing answer time.
+/// `first_rank` is the rank of the first attempt, following attempts share it as long as they tie.
+pub fn rank_attempts(
+    first_rank: u64,
+    skip: u64,
+    attempts: Vec<DailyAttempt>,
+) -> Vec<RankedAttempt> {
+    let mut ranked: Vec<RankedAttempt> = Vec::with_capacity(attempts.len());
+    for (index, attempt) in attempts.into_iter().enumerate() {
+        let rank = match ranked.last() {
+            None => first_rank,
+            Some(previous)
+                if previous.attempt.score == attempt.score
+                    && previous.attempt.answer_seconds == attempt.answer_seconds =>
+            {
+                previous.rank
+            }
+            Some(_) => skip + index as u64 + 1,
+        };
+        ranked.push(RankedAttempt { rank, attempt });
+    }
+    ranked
+}
+
+/// Computes the streaks of a player from the days they attempted the challenge, sorted from the oldest,
+/// and the day of the challenge in progress.
+pub fn streaks(days: &[String], today: &str) -> DailyStreak {
+    let mut best = 0;
+    let mut run = 0;
+    let mut previous: Option<&String> = None;
+    for day in days {
+        run = match previous {
+            Some(previous) if DailyReset::previous_day(day).as_ref() == Some(previous) => run + 1,
+            Some(previous) if previous == day => run,
+            _ => 1,
+        };
+        best = best.max(run);
+        previous = Some(day);
+    }
+    let ongoing = previous.is_some_and(|last| {
+        last == today || DailyReset::previous_day(today).as_ref() == Some(last)
+    });
+    DailyStreak {
+        current: if ongoing { run } else { 0 },
+        best,
+        last_day: previous.cloned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::daily_challenge::{
+        pick_questions, rank_attempts, streaks, DailyAttempt, DailyReset,
+    };
+    use crate::model::game_progress::Question;
+    use mongodb::bson::DateTime;
+
+    fn date(date: &str) -> DateTime {
+        DateTime::parse_rfc3339_str(date).unwrap()
+    }
+
+    fn question(topic: &str, index: usize) -> Question {
+        Question {
+            question_text: format!("{} question {}", topic, index),
+            topic: topic.to_string(),
+            good_answer_number: 1,
+            ..Default::default()
+        }
+    }
+
+    fn attempt(user: &str, score: i32, answer_seconds: i64) -> DailyAttempt {
+        DailyAttempt {
+            id: None,
+            day: "2026-10-19".to_string(),
+            user: user.to_string(),
+            current_question: 10,
+            question_started_at: DateTime::now(),
+            score,
+            answer_seconds,
+            started_at: DateTime::now(),
+            finished_at: None,
+        }
+    }
+
+    fn days(days: &[&str]) -> Vec<String> {
+        days.iter().map(|day| day.to_string()).collect()
+    }
+
+    #[test]
+    fn daily_reset_should_shift_the_day_of_the_challenge() {
+        let reset = DailyReset::parse("06:30").unwrap();
+        assert_eq!(reset.day_of(date("2026-10-19T06:29:59Z")), "2026-10-18");
+        assert_eq!(reset.day_of(date("2026-10-19T06:30:00Z")), "2026-10-19");
+        assert_eq!(
+            reset.bounds("2026-10-19"),
+            Some((date("2026-10-19T06:30:00Z"), date("2026-10-20T06:30:00Z")))
+        );
+        assert_eq!(DailyReset::parse("").unwrap(), DailyReset::default());
+        assert!(DailyReset::parse("24:00").is_err());
+        assert!(DailyReset::parse("noon").is_err());
+    }
+
+    #[test]
+    fn pick_questions_should_give_the_same_questions_for_a_day_across_topics() {
+        let pool: Vec<Question> = (0..6)
+            .map(|index| question("Rust", index))
+            .chain((0..2).map(|index| question("Java", index)))
+            .collect();
+        let mut reversed = pool.clone();
+        reversed.reverse();
+        let questions = pick_questions(pool.clone(), 4, "2026-10-19");
+        assert_eq!(questions, pick_questions(reversed, 4, "2026-10-19"));
+        assert_eq!(questions.len(), 4);
+        let java = questions.iter().filter(|q| q.topic == "Java").count();
+        assert_eq!(java, 2);
+        assert_eq!(pick_questions(pool, 20, "2026-10-20").len(), 8);
+    }
+
+    #[test]
+    fn rank_attempts_should_break_ties_with_answer_time() {
+        let ranked = rank_attempts(
+            1,
+            0,
+            vec![
+                attempt("alice", 8, 40),
+                attempt("bob", 8, 55),
+                attempt("carol", 8, 55),
+                attempt("dave", 6, 20),
+            ],
+        );
+        let ranks: Vec<u64> = ranked.iter().map(|ranked| ranked.rank).collect();
+        assert_eq!(ranks, vec![1, 2, 2, 4]);
+    }
+
+    #[test]
+    fn streaks_should_count_days_in_a_row() {
+        let played = days(&[
+            "2026-09-30",
+            "2026-10-01",
+            "2026-10-02",
+            "2026-10-17",
+            "2026-10-18",
+        ]);
+        let streak = streaks(&played, "2026-10-19");
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.best, 3);
+        assert_eq!(streak.last_day, Some("2026-10-18".to_string()));
+        assert_eq!(streaks(&played, "2026-10-20").current, 0);
+        assert_eq!(streaks(&[], "2026-10-20").best, 0);
+    }
+}
@@ -0,0 +1,183 @@
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::model::game::GameStanding;
+use crate::model::game_answer::GameAnswer;
+
+/// Number of players of a duel.
+pub const DUEL_PLAYERS: i8 = 2;
+/// Hit points of the players when a duel starts.
+pub const DUEL_HIT_POINTS: i32 = 100;
+/// Damage dealt to the opponent by a correct answer, however slow.
+pub const BASE_DAMAGE: i32 = 10;
+/// Extra damage dealt by a correct answer given right away, it shrinks as the time to answer runs out.
+pub const SPEED_DAMAGE: i32 = 15;
+/// Damage a player deals to themselves with a wrong answer.
+pub const SELF_DAMAGE: i32 = 5;
+
+///Hit points lost by a player of a duel after a question, to be stored with the [Game](crate::model::game::Game).
+///`from` is the player who dealt the damage, the player themselves for a wrong answer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameDamage {
+    pub user: String,
+    pub from: String,
+    pub question_index: i8,
+    pub damage: i32,
+    pub hit_points: i32,
+    pub dealt_at: DateTime,
+}
+
+/// Gives the hit points left to a player of a duel after the given damages.
+pub fn hit_points(user: &str, damages: &[GameDamage]) -> i32 {
+    DUEL_HIT_POINTS
+        - damages
+            .iter()
+            .filter(|damage| damage.user == user)
+            .map(|damage| damage.damage)
+            .sum::<i32>()
+}
+
+/// Gives the damage dealt by a correct answer given with `remaining_time` seconds left out of `question_seconds`.
+pub fn answer_damage(remaining_time: u64, question_seconds: u64) -> i32 {
+    let speed = remaining_time.min(question_seconds) as f64 / question_seconds.max(1) as f64;
+    BASE_DAMAGE + (SPEED_DAMAGE as f64 * speed).round() as i32
+}
+
+/// Computes the damages dealt by the answers of the players of a duel to a question.
+/// A correct answer hurts every opponent, a wrong answer hurts the player who gave it, not answering hurts nobody.
+/// `previous` are the damages of the previous questions, so the hit points left are given with each damage.
+pub fn duel_damages(
+    players: &[String],
+    answers: &[GameAnswer],
+    previous: &[GameDamage],
+    question_seconds: u64,
+    now: DateTime,
+) -> Vec<GameDamage> {
+    let mut damages: Vec<GameDamage> = vec![];
+    for answer in answers
+        .iter()
+        .filter(|answer| players.contains(&answer.user))
+    {
+        let hits: Vec<(String, i32)> = if answer.answer == answer.correct_answer {
+            let damage = answer_damage(answer.question.remaining_time, question_seconds);
+            players
+                .iter()
+                .filter(|player| **player != answer.user)
+                .map(|player| (player.clone(), damage))
+                .collect()
+        } else {
+            vec![(answer.user.clone(), SELF_DAMAGE)]
+        };
+        for (user, damage) in hits {
+            let dealt: Vec<GameDamage> = previous.iter().chain(&damages).cloned().collect();
+            damages.push(GameDamage {
+                hit_points: hit_points(&user, &dealt) - damage,
+                user,
+                from: answer.user.clone(),
+                question_index: answer.question_index,
+                damage,
+                dealt_at: now,
+            });
+        }
+    }
+    damages
+}
+
+/// Ranks the players of a duel: a player knocked out loses, otherwise the player with the most hit points left wins.
+/// Players with as many hit points left and the same score share the same rank.
+pub fn rank_duelists(mut scores: Vec<(String, i32)>, damages: &[GameDamage]) -> Vec<GameStanding> {
+    let left = |user: &str| hit_points(user, damages).max(0);
+    scores.sort_by_key(|(user, score)| std::cmp::Reverse((left(user), *score)));
+    let mut standings: Vec<GameStanding> = Vec::with_capacity(scores.len());
+    for (index, (user, score)) in scores.into_iter().enumerate() {
+        let rank = match standings.last() {
+            Some(previous) if previous.score == score && left(&previous.user) == left(&user) => {
+                previous.rank
+            }
+            _ => index as i32 + 1,
+        };
+        standings.push(GameStanding { user, score, rank });
+    }
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::duel::{
+        answer_damage, duel_damages, hit_points, rank_duelists, GameDamage, DUEL_HIT_POINTS,
+        SELF_DAMAGE,
+    };
+    use crate::model::game_answer::GameAnswer;
+    use crate::model::game_progress::Question;
+    use mongodb::bson::DateTime;
+
+    fn answer(user: &str, answer: i8, remaining_time: u64) -> GameAnswer {
+        GameAnswer {
+            game_id: "duel".to_string(),
+            user: user.to_string(),
+            answer,
+            question_index: 3,
+            correct_answer: 2,
+            question: Question {
+                good_answer_number: 2,
+                remaining_time,
+                ..Default::default()
+            },
+            practice: false,
+            daily: false,
+            wager: None,
+            power_up: None,
+            answer_seconds: None,
+        }
+    }
+
+    fn players() -> Vec<String> {
+        vec!["alice".to_string(), "bob".to_string()]
+    }
+
+    #[test]
+    fn answer_damage_should_grow_with_speed() {
+        assert_eq!(answer_damage(20, 20), 25);
+        assert_eq!(answer_damage(10, 20), 18);
+        assert_eq!(answer_damage(0, 20), 10);
+    }
+
+    #[test]
+    fn duel_damages_should_hurt_the_opponent_or_the_player_who_missed() {
+        let previous = vec![GameDamage {
+            user: "bob".to_string(),
+            from: "alice".to_string(),
+            question_index: 2,
+            damage: 90,
+            hit_points: 10,
+            dealt_at: DateTime::now(),
+        }];
+        let damages = duel_damages(
+            &players(),
+            &[answer("alice", 2, 20), answer("bob", 1, 15)],
+            &previous,
+            20,
+            DateTime::now(),
+        );
+        assert_eq!(damages.len(), 2);
+        assert_eq!(damages[0].user, "bob");
+        assert_eq!(damages[0].damage, 25);
+        assert_eq!(damages[0].hit_points, -15);
+        assert_eq!(damages[1].user, "bob");
+        assert_eq!(damages[1].from, "bob");
+        assert_eq!(damages[1].damage, SELF_DAMAGE);
+        assert_eq!(damages[1].hit_points, -20);
+        assert_eq!(hit_points("alice", &damages), DUEL_HIT_POINTS);
+    }
+
+    #[test]
+    fn rank_duelists_should_rank_by_hit_points_left() {
+        let damages = duel_damages(&players(), &[answer("bob", 2, 0)], &[], 20, DateTime::now());
+        let standings = rank_duelists(
+            vec![("alice".to_string(), 5), ("bob".to_string(), 1)],
+            &damages,
+        );
+        assert_eq!(standings[0].user, "bob");
+        assert_eq!(standings[1].rank, 2);
+    }
+}
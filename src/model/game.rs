@@ -1,5 +1,17 @@
 use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
 use rocket::serde::{Deserialize, Serialize};
+use rocket::{FromFormField, UriDisplayQuery};
+
+use crate::model::buzzer::{question_winner, GameBuzz};
+use crate::model::duel::{hit_points, GameDamage};
+use crate::model::hint::{GameHint, DEFAULT_HINT_PENALTY};
+use crate::model::power_up::{time_left, GamePowerUp, PowerUp};
+use crate::model::survival::DEFAULT_MISS_LIMIT;
+use crate::model::team::{GameTeam, TeamScoring, TeamStanding};
+
+/// Number of players needed to start a game when not set by its creator.
+pub const DEFAULT_MIN_PLAYERS: i8 = 2;
 
 ///Game entity to be stored in the database
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -9,7 +21,672 @@ pub struct Game {
     pub topics: Vec<String>,
     pub question_number: i8,
     pub is_private: bool,
-    pub is_started: bool,
+    #[serde(default)]
+    pub join_code: Option<String>,
+    #[serde(default)]
+    pub join_password_hash: Option<String>,
+    #[serde(default)]
+    pub spectate_code: Option<String>,
+    #[serde(default)]
+    pub status: GameStatus,
+    #[serde(default)]
+    pub status_changed_at: Option<DateTime>,
+    #[serde(default)]
+    pub status_history: Vec<GameStatusChange>,
+    #[serde(default)]
+    pub mode: GameMode,
     pub creator: Option<String>,
     pub users: Vec<String>,
+    #[serde(default)]
+    pub lobby_settings: LobbySettings,
+    #[serde(default)]
+    pub ready_users: Vec<String>,
+    #[serde(default)]
+    pub kicked_users: Vec<String>,
+    #[serde(default)]
+    pub spectators: Vec<String>,
+    #[serde(default)]
+    pub host_actions: Vec<GameHostAction>,
+    #[serde(default)]
+    pub standings: Vec<GameStanding>,
+    #[serde(default)]
+    pub eliminations: Vec<GameElimination>,
+    #[serde(default)]
+    pub damages: Vec<GameDamage>,
+    #[serde(default)]
+    pub buzzes: Vec<GameBuzz>,
+    #[serde(default)]
+    pub miss_limit: Option<i8>,
+    #[serde(default)]
+    pub power_ups: bool,
+    #[serde(default)]
+    pub used_power_ups: Vec<GamePowerUp>,
+    #[serde(default)]
+    pub hint_penalty: Option<i32>,
+    #[serde(default)]
+    pub revealed_hints: Vec<GameHint>,
+    #[serde(default)]
+    pub teams: Vec<GameTeam>,
+    #[serde(default)]
+    pub team_scoring: TeamScoring,
+    #[serde(default)]
+    pub team_standings: Vec<TeamStanding>,
+}
+
+impl Game {
+    /// Tells if the given user is the host of the game.
+    pub fn is_hosted_by(&self, user: &str) -> bool {
+        self.creator.as_deref() == Some(user)
+    }
+
+    /// Tells if enough players are in the lobby, and ready when a ready check is required, to start the game.
+    /// In [GameMode::Team], every team needs a player too.
+    pub fn can_start(&self) -> bool {
+        let enough_players = self.users.len() >= self.lobby_settings.min_players as usize;
+        let all_ready = !self.lobby_settings.ready_check
+            || self
+                .users
+                .iter()
+                .all(|user| self.ready_users.contains(user));
+        let teams_filled =
+            self.mode != GameMode::Team || self.teams.iter().all(|team| !team.users.is_empty());
+        enough_players && all_ready && teams_filled
+    }
+
+    /// Gives the team of a player of a team game.
+    pub fn team_of(&self, user: &str) -> Option<&GameTeam> {
+        self.teams
+            .iter()
+            .find(|team| team.users.iter().any(|player| player == user))
+    }
+
+    /// Tells if the given player was knocked out of the game.
+    pub fn is_eliminated(&self, user: &str) -> bool {
+        self.eliminations
+            .iter()
+            .any(|elimination| elimination.user == user)
+    }
+
+    /// Gives the players still in the game, the ones who were not knocked out.
+    pub fn survivors(&self) -> Vec<String> {
+        self.users
+            .iter()
+            .filter(|user| !self.is_eliminated(user))
+            .cloned()
+            .collect()
+    }
+
+    /// Gives the misses allowed to a player of a survival game, the player is out on the last one.
+    pub fn miss_limit(&self) -> i8 {
+        self.miss_limit.unwrap_or(DEFAULT_MISS_LIMIT)
+    }
+
+    /// Gives the points a revealed hint costs to a player.
+    pub fn hint_penalty(&self) -> i32 {
+        self.hint_penalty.unwrap_or(DEFAULT_HINT_PENALTY)
+    }
+
+    /// Counts the hints of the question at the given index revealed to the given player.
+    pub fn hints_revealed_to(&self, user: &str, question_index: i8) -> usize {
+        self.revealed_hints
+            .iter()
+            .filter(|hint| hint.user == user && hint.question_index == question_index)
+            .count()
+    }
+
+    /// Gives the hit points left to a player of a duel.
+    pub fn hit_points(&self, user: &str) -> i32 {
+        hit_points(user, &self.damages)
+    }
+
+    /// Gives the power-up the given player used on the question at the given index.
+    pub fn power_up_of(&self, user: &str, question_index: i8) -> Option<&GamePowerUp> {
+        self.used_power_ups
+            .iter()
+            .find(|used| used.user == user && used.question_index == question_index)
+    }
+
+    /// Gives the power-ups the given player used so far.
+    pub fn power_ups_used_by(&self, user: &str) -> usize {
+        self.used_power_ups
+            .iter()
+            .filter(|used| used.user == user)
+            .count()
+    }
+
+    /// Tells if a player took extra time on the question at the given index, the question then goes on
+    /// for them once the time ran out.
+    pub fn has_overtime(&self, question_index: i8) -> bool {
+        self.used_power_ups.iter().any(|used| {
+            used.question_index == question_index && used.power_up == PowerUp::ExtraTime
+        })
+    }
+
+    /// Gives the seconds left to the given player to answer the question at the given index,
+    /// from the time left to every player and the power-ups played on the question.
+    pub fn time_left(
+        &self,
+        user: &str,
+        question_index: i8,
+        remaining_time: u64,
+        overtime: u64,
+    ) -> u64 {
+        let extended = self
+            .power_up_of(user, question_index)
+            .is_some_and(|used| used.power_up == PowerUp::ExtraTime);
+        let frozen = self.used_power_ups.iter().any(|used| {
+            used.question_index == question_index
+                && used.power_up == PowerUp::Freeze
+                && used.target.as_deref() == Some(user)
+        });
+        time_left(remaining_time, overtime, extended, frozen)
+    }
+
+    /// Tells if the game has a winner before its last question:
+    /// in [GameMode::Elimination], when a single player of several is left,
+    /// in [GameMode::Duel], when a player is knocked out,
+    /// and in [GameMode::Survival], when every player reached the miss limit.
+    pub fn is_decided(&self) -> bool {
+        match self.mode {
+            GameMode::Survival => !self.users.is_empty() && self.survivors().is_empty(),
+            GameMode::Classic | GameMode::Team | GameMode::Buzzer | GameMode::Wager => false,
+            GameMode::Elimination => self.users.len() > 1 && self.survivors().len() <= 1,
+            GameMode::Duel => self.users.iter().any(|user| self.hit_points(user) <= 0),
+        }
+    }
+
+    /// Tells if the host asked to skip the question at the given index.
+    pub fn is_question_skipped(&self, question_index: i8) -> bool {
+        self.host_actions.iter().any(|action| {
+            action.kind == GameHostActionKind::Skip && action.question_index == Some(question_index)
+        })
+    }
+
+    /// Tells if the question at the given index ends before its time runs out:
+    /// when the host skipped it, or in [GameMode::Buzzer] when a player answered it correctly.
+    pub fn is_question_over(&self, question_index: i8) -> bool {
+        self.is_question_skipped(question_index)
+            || (self.mode == GameMode::Buzzer
+                && question_winner(question_index, &self.buzzes).is_some())
+    }
+}
+
+///Lobby settings of a [Game], chosen by its creator
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LobbySettings {
+    pub max_players: Option<i8>,
+    pub min_players: i8,
+    pub ready_check: bool,
+}
+
+impl Default for LobbySettings {
+    fn default() -> Self {
+        LobbySettings {
+            max_players: None,
+            min_players: DEFAULT_MIN_PLAYERS,
+            ready_check: false,
+        }
+    }
+}
+
+impl LobbySettings {
+    /// Tells if the settings can be applied to a game.
+    pub fn is_valid(&self) -> bool {
+        self.min_players >= 1
+            && self
+                .max_players
+                .is_none_or(|max_players| max_players >= self.min_players)
+    }
+}
+
+///Rules a [Game] is played with, chosen by its creator
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    /// Every player answers every question.
+    #[default]
+    Classic,
+    /// Players who answer wrong or do not answer in time are knocked out, until one remains.
+    Elimination,
+    /// Two players fight with hit points, correct answers dealing damage to the opponent, until one is knocked out.
+    Duel,
+    /// Players are split into named teams, the scores of the players of a team making the score of the team.
+    Team,
+    /// Only the first player to answer a question correctly scores, a wrong answer locks the player out of the question.
+    Buzzer,
+    /// Players stake some of their points on each answer, winning the stake when right and losing it when wrong.
+    Wager,
+    /// Questions keep coming, harder and faster, until every player reached the miss limit.
+    Survival,
+}
+
+///Lifecycle status of a [Game].
+///Allowed moves between statuses are described by [GameStatus::can_transition_to].
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    FromFormField,
+    UriDisplayQuery,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GameStatus {
+    #[default]
+    Lobby,
+    Countdown,
+    Running,
+    Paused,
+    Finished,
+    Aborted,
+    Expired,
+}
+
+impl GameStatus {
+    /// Tells if a game in this status may move to the `next` status.
+    pub fn can_transition_to(&self, next: GameStatus) -> bool {
+        use GameStatus::*;
+        matches!(
+            (self, next),
+            (Lobby, Countdown)
+                | (Lobby, Aborted)
+                | (Lobby, Expired)
+                | (Countdown, Running)
+                | (Countdown, Aborted)
+                | (Running, Paused)
+                | (Running, Finished)
+                | (Running, Aborted)
+                | (Paused, Running)
+                | (Paused, Aborted)
+                | (Paused, Expired)
+        )
+    }
+
+    /// Tells if the game is over, no transition can happen anymore from this status.
+    pub fn is_over(&self) -> bool {
+        matches!(
+            self,
+            GameStatus::Finished | GameStatus::Aborted | GameStatus::Expired
+        )
+    }
+
+    /// Name of the status as stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameStatus::Lobby => "lobby",
+            GameStatus::Countdown => "countdown",
+            GameStatus::Running => "running",
+            GameStatus::Paused => "paused",
+            GameStatus::Finished => "finished",
+            GameStatus::Aborted => "aborted",
+            GameStatus::Expired => "expired",
+        }
+    }
+}
+
+///Timestamped entry of the [Game] status history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameStatusChange {
+    pub status: GameStatus,
+    pub changed_at: DateTime,
+}
+
+///Action performed by the host of a [Game], kept as history of the game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameHostAction {
+    pub kind: GameHostActionKind,
+    pub host: String,
+    pub player: Option<String>,
+    pub question_index: Option<i8>,
+    pub performed_at: DateTime,
+}
+
+///Final standing of a player in a finished [Game], the score is the number of correct answers.
+///Players with the same score share the same rank.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameStanding {
+    pub user: String,
+    pub score: i32,
+    pub rank: i32,
+}
+
+///Standings of a [Game] from the answers given so far, with the standings of the teams in [GameMode::Team]
+#[derive(Debug, Clone)]
+pub struct GameScoreboard {
+    pub standings: Vec<GameStanding>,
+    pub team_standings: Vec<TeamStanding>,
+}
+
+///Knock out of a player from a [Game] played in [GameMode::Elimination]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameElimination {
+    pub user: String,
+    pub question_index: i8,
+    pub eliminated_at: DateTime,
+}
+
+/// Gives the survivors knocked out by a question, the ones who did not answer it correctly.
+/// Nobody is knocked out when no survivor answered correctly, so a game always keeps a player.
+pub fn knock_out(survivors: &[String], correct_players: &[String]) -> Vec<String> {
+    let knocked_out: Vec<String> = survivors
+        .iter()
+        .filter(|user| !correct_players.contains(user))
+        .cloned()
+        .collect();
+    if knocked_out.len() == survivors.len() {
+        return vec![];
+    }
+    knocked_out
+}
+
+/// Ranks the players of a game played in [GameMode::Elimination]: the survivors first, then the others
+/// from the last knocked out, players knocked out by the same question being ranked by their scores.
+/// Players surviving as long with the same score share the same rank.
+pub fn rank_survivors(
+    mut scores: Vec<(String, i32)>,
+    eliminations: &[GameElimination],
+) -> Vec<GameStanding> {
+    let survived = |user: &str| {
+        eliminations
+            .iter()
+            .find(|elimination| elimination.user == user)
+            .map_or(i8::MAX, |elimination| elimination.question_index)
+    };
+    scores.sort_by_key(|(user, score)| std::cmp::Reverse((survived(user), *score)));
+    let mut standings: Vec<GameStanding> = Vec::with_capacity(scores.len());
+    for (index, (user, score)) in scores.into_iter().enumerate() {
+        let rank = match standings.last() {
+            Some(previous)
+                if previous.score == score && survived(&previous.user) == survived(&user) =>
+            {
+                previous.rank
+            }
+            _ => index as i32 + 1,
+        };
+        standings.push(GameStanding { user, score, rank });
+    }
+    standings
+}
+
+/// Ranks the players from their scores, best first, players with the same score sharing the same rank.
+pub fn rank_players(mut scores: Vec<(String, i32)>) -> Vec<GameStanding> {
+    scores.sort_by(|(_, a), (_, b)| b.cmp(a));
+    let mut standings: Vec<GameStanding> = Vec::with_capacity(scores.len());
+    for (index, (user, score)) in scores.into_iter().enumerate() {
+        let rank = match standings.last() {
+            Some(previous) if previous.score == score => previous.rank,
+            _ => index as i32 + 1,
+        };
+        standings.push(GameStanding { user, score, rank });
+    }
+    standings
+}
+
+///Kinds of [GameHostAction]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GameHostActionKind {
+    Start,
+    Pause,
+    Resume,
+    Skip,
+    Abort,
+    Kick,
+    TransferHost,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::buzzer::GameBuzz;
+    use crate::model::duel::{GameDamage, DUEL_HIT_POINTS};
+    use crate::model::game::{
+        knock_out, rank_players, rank_survivors, Game, GameElimination, GameMode, GameStatus,
+        LobbySettings,
+    };
+    use crate::model::power_up::{GamePowerUp, PowerUp, EXTRA_TIME_SECONDS, FREEZE_SECONDS};
+    use crate::model::team::GameTeam;
+    use mongodb::bson::DateTime;
+
+    #[test]
+    fn game_status_should_allow_the_lifecycle_transitions() {
+        assert!(GameStatus::Lobby.can_transition_to(GameStatus::Countdown));
+        assert!(GameStatus::Countdown.can_transition_to(GameStatus::Running));
+        assert!(GameStatus::Running.can_transition_to(GameStatus::Paused));
+        assert!(GameStatus::Paused.can_transition_to(GameStatus::Running));
+        assert!(GameStatus::Running.can_transition_to(GameStatus::Finished));
+        assert!(GameStatus::Lobby.can_transition_to(GameStatus::Expired));
+    }
+
+    #[test]
+    fn game_status_should_reject_invalid_transitions() {
+        assert!(!GameStatus::Lobby.can_transition_to(GameStatus::Running));
+        assert!(!GameStatus::Running.can_transition_to(GameStatus::Lobby));
+        assert!(!GameStatus::Finished.can_transition_to(GameStatus::Running));
+        assert!(!GameStatus::Aborted.can_transition_to(GameStatus::Lobby));
+        assert!(!GameStatus::Expired.can_transition_to(GameStatus::Paused));
+    }
+
+    #[test]
+    fn game_should_start_only_with_enough_ready_players() {
+        let mut game = Game {
+            users: vec!["bob".to_string()],
+            lobby_settings: LobbySettings {
+                max_players: Some(2),
+                min_players: 2,
+                ready_check: true,
+            },
+            ..Default::default()
+        };
+        assert!(!game.can_start());
+        game.users.push("alice".to_string());
+        assert!(!game.can_start());
+        game.ready_users = vec!["alice".to_string(), "bob".to_string()];
+        assert!(game.can_start());
+    }
+
+    #[test]
+    fn team_game_should_start_only_with_a_player_in_every_team() {
+        let mut game = Game {
+            mode: GameMode::Team,
+            users: vec!["alice".to_string(), "bob".to_string()],
+            teams: vec![
+                GameTeam {
+                    name: "Sales".to_string(),
+                    users: vec!["alice".to_string(), "bob".to_string()],
+                },
+                GameTeam {
+                    name: "IT".to_string(),
+                    users: vec![],
+                },
+            ],
+            ..Default::default()
+        };
+        assert!(!game.can_start());
+        game.teams[0].users.pop();
+        game.teams[1].users.push("bob".to_string());
+        assert!(game.can_start());
+        assert_eq!(game.team_of("bob").unwrap().name, "IT");
+        assert!(game.team_of("carol").is_none());
+    }
+
+    #[test]
+    fn lobby_settings_should_reject_less_max_players_than_min_players() {
+        let settings = LobbySettings {
+            max_players: Some(2),
+            min_players: 3,
+            ready_check: false,
+        };
+        assert!(!settings.is_valid());
+        assert!(LobbySettings::default().is_valid());
+    }
+
+    #[test]
+    fn rank_players_should_share_ranks_between_equal_scores() {
+        let standings = rank_players(vec![
+            ("alice".to_string(), 3),
+            ("bob".to_string(), 5),
+            ("carol".to_string(), 3),
+            ("dave".to_string(), 1),
+        ]);
+        let ranks: Vec<(&str, i32)> = standings
+            .iter()
+            .map(|standing| (standing.user.as_str(), standing.rank))
+            .collect();
+        assert_eq!(
+            ranks,
+            vec![("bob", 1), ("alice", 2), ("carol", 2), ("dave", 4)]
+        );
+    }
+
+    fn eliminated(user: &str, question_index: i8) -> GameElimination {
+        GameElimination {
+            user: user.to_string(),
+            question_index,
+            eliminated_at: DateTime::now(),
+        }
+    }
+
+    #[test]
+    fn knock_out_should_keep_everyone_when_nobody_answered_correctly() {
+        let survivors = vec!["alice".to_string(), "bob".to_string()];
+        assert_eq!(knock_out(&survivors, &["bob".to_string()]), vec!["alice"]);
+        assert!(knock_out(&survivors, &[]).is_empty());
+    }
+
+    #[test]
+    fn elimination_game_should_be_decided_with_a_single_survivor() {
+        let mut game = Game {
+            mode: GameMode::Elimination,
+            users: vec!["alice".to_string(), "bob".to_string()],
+            ..Default::default()
+        };
+        assert!(!game.is_decided());
+        game.eliminations.push(eliminated("alice", 2));
+        assert!(game.is_decided());
+        assert_eq!(game.survivors(), vec!["bob"]);
+        game.mode = GameMode::Classic;
+        assert!(!game.is_decided());
+    }
+
+    #[test]
+    fn duel_should_be_decided_by_a_knock_out() {
+        let mut game = Game {
+            mode: GameMode::Duel,
+            users: vec!["alice".to_string(), "bob".to_string()],
+            ..Default::default()
+        };
+        assert!(!game.is_decided());
+        game.damages.push(GameDamage {
+            user: "bob".to_string(),
+            from: "alice".to_string(),
+            question_index: 0,
+            damage: DUEL_HIT_POINTS,
+            hit_points: 0,
+            dealt_at: DateTime::now(),
+        });
+        assert_eq!(game.hit_points("bob"), 0);
+        assert!(game.is_decided());
+    }
+
+    #[test]
+    fn buzzer_question_should_be_over_once_answered_correctly() {
+        let buzz = |user: &str, correct: bool| GameBuzz {
+            user: user.to_string(),
+            question_index: 0,
+            answer: 1,
+            correct,
+            buzzed_at: DateTime::now(),
+        };
+        let mut game = Game {
+            mode: GameMode::Buzzer,
+            buzzes: vec![buzz("alice", false)],
+            ..Default::default()
+        };
+        assert!(!game.is_question_over(0));
+        game.buzzes.push(buzz("bob", true));
+        assert!(game.is_question_over(0));
+        assert!(!game.is_question_over(1));
+        game.mode = GameMode::Classic;
+        assert!(!game.is_question_over(0));
+    }
+
+    #[test]
+    fn survival_game_should_be_decided_once_everyone_is_out() {
+        let mut game = Game {
+            mode: GameMode::Survival,
+            users: vec!["alice".to_string()],
+            ..Default::default()
+        };
+        assert!(!game.is_decided());
+        game.eliminations.push(eliminated("alice", 7));
+        assert!(game.is_decided());
+    }
+
+    #[test]
+    fn time_left_should_follow_the_power_ups_played_on_the_question() {
+        let used = |user: &str, power_up: PowerUp, target: Option<&str>| GamePowerUp {
+            user: user.to_string(),
+            power_up,
+            question_index: 2,
+            target: target.map(|target| target.to_string()),
+            removed_answers: vec![],
+            used_at: DateTime::now(),
+        };
+        let game = Game {
+            power_ups: true,
+            used_power_ups: vec![
+                used("alice", PowerUp::ExtraTime, None),
+                used("bob", PowerUp::Freeze, Some("carol")),
+            ],
+            ..Default::default()
+        };
+        assert!(game.has_overtime(2));
+        assert!(!game.has_overtime(3));
+        assert_eq!(game.time_left("alice", 2, 10, 0), 10 + EXTRA_TIME_SECONDS);
+        assert_eq!(game.time_left("alice", 2, 0, 4), 4);
+        assert_eq!(game.time_left("bob", 2, 0, 4), 0);
+        assert_eq!(game.time_left("carol", 2, 10, 0), 10 - FREEZE_SECONDS);
+        assert_eq!(game.time_left("carol", 3, 10, 0), 10);
+        assert_eq!(game.power_ups_used_by("bob"), 1);
+        assert_eq!(
+            game.power_up_of("bob", 2).map(|used| used.power_up),
+            Some(PowerUp::Freeze)
+        );
+    }
+
+    #[test]
+    fn rank_survivors_should_rank_by_survival_then_score() {
+        let scores = vec![
+            ("alice".to_string(), 1),
+            ("bob".to_string(), 4),
+            ("carol".to_string(), 3),
+            ("dave".to_string(), 3),
+            ("erin".to_string(), 2),
+        ];
+        let eliminations = vec![
+            eliminated("alice", 1),
+            eliminated("carol", 4),
+            eliminated("dave", 4),
+            eliminated("erin", 4),
+        ];
+        let standings = rank_survivors(scores, &eliminations);
+        let ranks: Vec<(&str, i32)> = standings
+            .iter()
+            .map(|standing| (standing.user.as_str(), standing.rank))
+            .collect();
+        assert_eq!(
+            ranks,
+            vec![
+                ("bob", 1),
+                ("carol", 2),
+                ("dave", 2),
+                ("erin", 4),
+                ("alice", 5)
+            ]
+        );
+    }
 }
@@ -1,8 +1,14 @@
 use rocket::serde::{Deserialize, Serialize};
 
 use crate::model::game_progress::Question;
+use crate::model::power_up::PowerUp;
 
-///GameAnswer entity to be stored in the database
+///GameAnswer entity to be stored in the database.
+///Answers given in practice sessions are stored too, `game_id` being the id of the session.
+///So are the answers of daily challenge attempts, `game_id` being the id of the attempt.
+///`wager` is the stake of the player in games played in [GameMode::Wager](crate::model::game::GameMode::Wager).
+///`power_up` is the power-up the player used on the question before answering it.
+///`answer_seconds` is the time the player took to answer, none in untimed practice sessions and for older answers.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct GameAnswer {
     pub game_id: String,
@@ -11,4 +17,14 @@ pub struct GameAnswer {
     pub question_index: i8,
     pub correct_answer: i8,
     pub(crate) question: Question,
+    #[serde(default)]
+    pub practice: bool,
+    #[serde(default)]
+    pub daily: bool,
+    #[serde(default)]
+    pub wager: Option<i32>,
+    #[serde(default)]
+    pub power_up: Option<PowerUp>,
+    #[serde(default)]
+    pub answer_seconds: Option<u64>,
 }
@@ -1,6 +1,7 @@
 use rocket::serde::{Deserialize, Serialize};
 
-///GameProgress entity to be stored in the database
+///GameProgress entity to be stored in the database.
+///`overtime` is the time left to the players who took extra time, once the time to answer ran out for the others.
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct GameProgress {
     #[serde(rename = "_id")]
@@ -8,6 +9,8 @@ pub struct GameProgress {
     pub current_question: i8,
     pub question_number: i8,
     pub question_content: Question,
+    #[serde(default)]
+    pub overtime: u64,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -20,4 +23,6 @@ pub struct Question {
     pub good_answer_number: i8,
     pub topic: String,
     pub remaining_time: u64,
+    #[serde(default)]
+    pub hints: Vec<String>,
 }
@@ -0,0 +1,78 @@
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+/// Most hints an authored question may have.
+pub const MAX_HINTS: usize = 3;
+/// Points a revealed hint costs to a player when not set by the creator of the game.
+pub const DEFAULT_HINT_PENALTY: i32 = 1;
+/// Most points a creator may charge for a hint.
+pub const MAX_HINT_PENALTY: i32 = 10;
+
+///Hint of a question of a [Game](crate::model::game::Game) revealed to a player, stored with the game in the order of reveal.
+///Hints of a question are revealed one after the other, `hint_index` being the position of the hint in the question.
+///`penalty` is the points the hint costs to the player, taken from their score.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameHint {
+    pub user: String,
+    pub question_index: i8,
+    pub hint_index: i32,
+    pub hint: String,
+    pub penalty: i32,
+    pub revealed_at: DateTime,
+}
+
+/// Tells if the hints of an authored question are valid: at most [MAX_HINTS], none of them blank.
+pub fn are_hints_valid(hints: &[String]) -> bool {
+    hints.len() <= MAX_HINTS && hints.iter().all(|hint| !hint.trim().is_empty())
+}
+
+/// Sums the points the hints revealed to a player cost.
+pub fn hint_penalties(user: &str, hints: &[GameHint]) -> i32 {
+    hints
+        .iter()
+        .filter(|hint| hint.user == user)
+        .map(|hint| hint.penalty)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::hint::{are_hints_valid, hint_penalties, GameHint};
+    use mongodb::bson::DateTime;
+
+    fn hint(user: &str, question_index: i8, penalty: i32) -> GameHint {
+        GameHint {
+            user: user.to_string(),
+            question_index,
+            hint_index: 0,
+            hint: "Look at the keywords".to_string(),
+            penalty,
+            revealed_at: DateTime::now(),
+        }
+    }
+
+    #[test]
+    fn hint_penalties_should_add_the_penalties_of_the_player() {
+        let hints = vec![hint("alice", 0, 1), hint("bob", 0, 2), hint("alice", 3, 2)];
+        assert_eq!(hint_penalties("alice", &hints), 3);
+        assert_eq!(hint_penalties("bob", &hints), 2);
+        assert_eq!(hint_penalties("carol", &hints), 0);
+    }
+
+    #[test]
+    fn are_hints_valid_should_reject_blank_and_extra_hints() {
+        let hint = |text: &str| text.to_string();
+        assert!(are_hints_valid(&[]));
+        assert!(are_hints_valid(&[
+            hint("It is immutable"),
+            hint("It is a class")
+        ]));
+        assert!(!are_hints_valid(&[hint(" ")]));
+        assert!(!are_hints_valid(&[
+            hint("a"),
+            hint("b"),
+            hint("c"),
+            hint("d")
+        ]));
+    }
+}
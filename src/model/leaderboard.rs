@@ -0,0 +1,167 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+/// Season of the leaderboards counting every game ever played.
+pub const ALL_TIME: &str = "all-time";
+/// Topic of the leaderboards counting the answers of every topic.
+pub const ALL_TOPICS: &str = "all";
+
+///Results of a player for a season and a topic, to be stored in the database.
+///Entries are incremented each time a game of the player finishes, so leaderboards never scan the answers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub season: String,
+    pub topic: String,
+    pub user: String,
+    pub score: i32,
+    pub answers: i32,
+    pub games: i32,
+    pub wins: i32,
+    pub updated_at: DateTime,
+}
+
+///[LeaderboardEntry] with the rank of the player, players with the same score sharing the same rank
+#[derive(Debug, Clone)]
+pub struct RankedEntry {
+    pub rank: u64,
+    pub entry: LeaderboardEntry,
+}
+
+///Page of a leaderboard, with the entry of the player asking for it when they have one
+#[derive(Debug, Clone)]
+pub struct Leaderboard {
+    pub season: String,
+    pub topic: String,
+    pub entries: Vec<RankedEntry>,
+    pub me: Option<RankedEntry>,
+}
+
+///Boundaries of the leaderboard seasons.
+///Seasons are calendar months by default, identified as `YYYY-MM`.
+///They can instead start at given dates, each season lasting until the next one starts and being identified by its start date.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Seasons {
+    Monthly,
+    Starting(Vec<(String, DateTime)>),
+}
+
+impl Seasons {
+    /// Reads season start dates formatted as `YYYY-MM-DD` and separated by commas, in any order.
+    /// Returns monthly seasons if no date is given.
+    /// Returns an error if a date is not valid.
+    pub fn parse(starts: &str) -> Result<Self, String> {
+        let mut starts = starts
+            .split(',')
+            .map(str::trim)
+            .filter(|start| !start.is_empty())
+            .map(|start| {
+                DateTime::parse_rfc3339_str(format!("{}T00:00:00Z", start))
+                    .map(|date| (start.to_string(), date))
+                    .map_err(|_| format!("Season start {} is not a YYYY-MM-DD date", start))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if starts.is_empty() {
+            return Ok(Seasons::Monthly);
+        }
+        starts.sort_by_key(|(_, date)| *date);
+        Ok(Seasons::Starting(starts))
+    }
+
+    /// Gives the season a date belongs to.
+    /// Returns none if the date is before the first configured season.
+    pub fn season_of(&self, date: DateTime) -> Option<String> {
+        match self {
+            Seasons::Monthly => date
+                .try_to_rfc3339_string()
+                .ok()
+                .map(|date| date[..7].to_string()),
+            Seasons::Starting(starts) => starts
+                .iter()
+                .rev()
+                .find(|(_, start)| *start <= date)
+                .map(|(season, _)| season.clone()),
+        }
+    }
+}
+
+/// Ranks a page of entries sorted by decreasing score.
+/// `first_rank` is the rank of the first entry, following entries share it as long as their score is the same.
+pub fn rank_entries(
+    first_rank: u64,
+    skip: u64,
+    entries: Vec<LeaderboardEntry>,
+) -> Vec<RankedEntry> {
+    let mut ranked: Vec<RankedEntry> = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.into_iter().enumerate() {
+        let rank = match ranked.last() {
+            None => first_rank,
+            Some(previous) if previous.entry.score == entry.score => previous.rank,
+            Some(_) => skip + index as u64 + 1,
+        };
+        ranked.push(RankedEntry { rank, entry });
+    }
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::leaderboard::{rank_entries, LeaderboardEntry, Seasons};
+    use mongodb::bson::DateTime;
+
+    fn date(date: &str) -> DateTime {
+        DateTime::parse_rfc3339_str(date).unwrap()
+    }
+
+    fn entry(user: &str, score: i32) -> LeaderboardEntry {
+        LeaderboardEntry {
+            id: None,
+            season: "2026-10".to_string(),
+            topic: "Rust".to_string(),
+            user: user.to_string(),
+            score,
+            answers: 10,
+            games: 1,
+            wins: 0,
+            updated_at: DateTime::now(),
+        }
+    }
+
+    #[test]
+    fn monthly_seasons_should_follow_calendar_months() {
+        let seasons = Seasons::parse("").unwrap();
+        assert_eq!(seasons, Seasons::Monthly);
+        assert_eq!(
+            seasons.season_of(date("2026-10-19T08:30:00Z")),
+            Some("2026-10".to_string())
+        );
+    }
+
+    #[test]
+    fn configured_seasons_should_last_until_the_next_start() {
+        let seasons = Seasons::parse("2026-09-01, 2026-06-15").unwrap();
+        assert_eq!(seasons.season_of(date("2026-06-14T23:59:59Z")), None);
+        assert_eq!(
+            seasons.season_of(date("2026-06-15T00:00:00Z")),
+            Some("2026-06-15".to_string())
+        );
+        assert_eq!(
+            seasons.season_of(date("2027-01-01T00:00:00Z")),
+            Some("2026-09-01".to_string())
+        );
+        assert!(Seasons::parse("2026-13-01").is_err());
+    }
+
+    #[test]
+    fn rank_entries_should_share_ranks_between_equal_scores() {
+        let ranked = rank_entries(
+            3,
+            20,
+            vec![entry("alice", 7), entry("bob", 7), entry("carol", 5)],
+        );
+        let ranks: Vec<u64> = ranked.iter().map(|ranked| ranked.rank).collect();
+        assert_eq!(ranks, vec![3, 3, 23]);
+    }
+}
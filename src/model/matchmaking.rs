@@ -0,0 +1,256 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+/// Number of players of a matched game when the queue is busy enough.
+pub const MATCH_PLAYERS: usize = 4;
+/// Smallest number of players of a matched game, accepted once a player waited [FEWER_PLAYERS_AFTER_SECONDS].
+pub const MIN_MATCH_PLAYERS: usize = 2;
+pub const FEWER_PLAYERS_AFTER_SECONDS: i64 = 20;
+/// Largest rating difference with a player who just joined the queue.
+pub const BASE_RATING_GAP: f64 = 100.0;
+/// The rating difference grows by this much every [RATING_GAP_STEP_SECONDS] of waiting.
+pub const RATING_GAP_STEP: f64 = 50.0;
+pub const RATING_GAP_STEP_SECONDS: i64 = 10;
+/// Players sharing no topic are matched once a player waited this long.
+pub const ANY_TOPIC_AFTER_SECONDS: i64 = 40;
+/// Players not matched after this long leave the queue.
+pub const MATCHMAKING_TIMEOUT_SECONDS: i64 = 120;
+
+///Request of a player to be matched with other players, to be stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchmakingTicket {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user: String,
+    pub topics: Vec<String>,
+    pub rating: f64,
+    pub status: TicketStatus,
+    pub game_id: Option<String>,
+    pub created_at: DateTime,
+    pub status_changed_at: DateTime,
+}
+
+///Status of a [MatchmakingTicket].
+///Tickets are [TicketStatus::Matched] as soon as their group is formed, and get their game once it is created.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketStatus {
+    #[default]
+    Waiting,
+    Matched,
+    Cancelled,
+    Expired,
+}
+
+impl TicketStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketStatus::Waiting => "waiting",
+            TicketStatus::Matched => "matched",
+            TicketStatus::Cancelled => "cancelled",
+            TicketStatus::Expired => "expired",
+        }
+    }
+}
+
+///Constraints a group must meet to be matched, they relax as the oldest player of the group waits
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchRules {
+    pub rating_gap: f64,
+    pub min_players: usize,
+    pub any_topic: bool,
+}
+
+impl MatchRules {
+    /// Gives the constraints applying to a player who waited the given number of seconds.
+    pub fn after(waited_seconds: i64) -> Self {
+        let waited_seconds = waited_seconds.max(0);
+        MatchRules {
+            rating_gap: BASE_RATING_GAP
+                + RATING_GAP_STEP * (waited_seconds / RATING_GAP_STEP_SECONDS) as f64,
+            min_players: if waited_seconds < FEWER_PLAYERS_AFTER_SECONDS {
+                MATCH_PLAYERS
+            } else {
+                MIN_MATCH_PLAYERS
+            },
+            any_topic: waited_seconds >= ANY_TOPIC_AFTER_SECONDS,
+        }
+    }
+}
+
+///Group of waiting players to put in the same game, with the topics of the game
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchGroup {
+    pub tickets: Vec<ObjectId>,
+    pub users: Vec<String>,
+    pub topics: Vec<String>,
+}
+
+/// Groups waiting tickets, the oldest tickets being matched first with the closest ratings.
+/// A ticket is grouped with others sharing one of its topics and rated close enough,
+/// according to the [MatchRules] of how long it waited. Tickets must be sorted from the oldest.
+pub fn find_groups(tickets: &[MatchmakingTicket], now: DateTime) -> Vec<MatchGroup> {
+    let mut grouped = vec![false; tickets.len()];
+    let mut groups = vec![];
+    for (anchor_index, anchor) in tickets.iter().enumerate() {
+        if grouped[anchor_index] {
+            continue;
+        }
+        let waited_seconds = (now.timestamp_millis() - anchor.created_at.timestamp_millis()) / 1000;
+        let rules = MatchRules::after(waited_seconds);
+        let mut best: Option<(Vec<usize>, Vec<String>)> = None;
+        let topic_choices: Vec<Option<&String>> = if rules.any_topic {
+            vec![None]
+        } else {
+            anchor.topics.iter().map(Some).collect()
+        };
+        for topic in topic_choices {
+            let mut candidates: Vec<usize> = tickets
+                .iter()
+                .enumerate()
+                .filter(|(index, ticket)| {
+                    *index != anchor_index
+                        && !grouped[*index]
+                        && ticket.user != anchor.user
+                        && (ticket.rating - anchor.rating).abs() <= rules.rating_gap
+                        && topic.is_none_or(|topic| ticket.topics.contains(topic))
+                })
+                .map(|(index, _)| index)
+                .collect();
+            candidates.sort_by(|a, b| {
+                let distance = |index: &usize| (tickets[*index].rating - anchor.rating).abs();
+                distance(a).total_cmp(&distance(b))
+            });
+            candidates.truncate(MATCH_PLAYERS - 1);
+            let enough = candidates.len() + 1 >= rules.min_players;
+            let better = best
+                .as_ref()
+                .is_none_or(|(best, _)| candidates.len() > best.len());
+            if enough && better {
+                let topics = match topic {
+                    Some(topic) => vec![topic.clone()],
+                    None => shared_topics(anchor, candidates.iter().map(|index| &tickets[*index])),
+                };
+                best = Some((candidates, topics));
+            }
+        }
+        if let Some((members, topics)) = best {
+            grouped[anchor_index] = true;
+            let mut group = MatchGroup {
+                tickets: anchor.id.into_iter().collect(),
+                users: vec![anchor.user.clone()],
+                topics,
+            };
+            for index in members {
+                grouped[index] = true;
+                group.tickets.extend(tickets[index].id);
+                group.users.push(tickets[index].user.clone());
+            }
+            groups.push(group);
+        }
+    }
+    groups
+}
+
+/// Gives the topics of the anchor wanted by every other member, or all the topics of the anchor if there is none.
+fn shared_topics<'a>(
+    anchor: &MatchmakingTicket,
+    members: impl Iterator<Item = &'a MatchmakingTicket> + Clone,
+) -> Vec<String> {
+    let shared: Vec<String> = anchor
+        .topics
+        .iter()
+        .filter(|topic| members.clone().all(|member| member.topics.contains(topic)))
+        .cloned()
+        .collect();
+    if shared.is_empty() {
+        anchor.topics.clone()
+    } else {
+        shared
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::matchmaking::{
+        find_groups, MatchRules, MatchmakingTicket, TicketStatus, MATCH_PLAYERS, MIN_MATCH_PLAYERS,
+    };
+    use mongodb::bson::oid::ObjectId;
+    use mongodb::bson::DateTime;
+
+    fn ticket(user: &str, topics: &[&str], rating: f64, waited_seconds: i64) -> MatchmakingTicket {
+        let created_at = DateTime::from_millis(1_000_000 - waited_seconds * 1000);
+        MatchmakingTicket {
+            id: Some(ObjectId::new()),
+            user: user.to_string(),
+            topics: topics.iter().map(|topic| topic.to_string()).collect(),
+            rating,
+            status: TicketStatus::Waiting,
+            game_id: None,
+            created_at,
+            status_changed_at: created_at,
+        }
+    }
+
+    fn now() -> DateTime {
+        DateTime::from_millis(1_000_000)
+    }
+
+    #[test]
+    fn match_rules_should_relax_while_waiting() {
+        let rules = MatchRules::after(0);
+        assert_eq!(rules.rating_gap, 100.0);
+        assert_eq!(rules.min_players, MATCH_PLAYERS);
+        assert!(!rules.any_topic);
+        let rules = MatchRules::after(45);
+        assert_eq!(rules.rating_gap, 300.0);
+        assert_eq!(rules.min_players, MIN_MATCH_PLAYERS);
+        assert!(rules.any_topic);
+    }
+
+    #[test]
+    fn find_groups_should_wait_for_a_full_group_at_first() {
+        let tickets = vec![
+            ticket("alice", &["Rust"], 1500.0, 5),
+            ticket("bob", &["Rust"], 1520.0, 2),
+        ];
+        assert!(find_groups(&tickets, now()).is_empty());
+        let tickets = vec![
+            ticket("alice", &["Rust"], 1500.0, 25),
+            ticket("bob", &["Rust"], 1520.0, 2),
+        ];
+        let groups = find_groups(&tickets, now());
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].users, vec!["alice", "bob"]);
+        assert_eq!(groups[0].topics, vec!["Rust"]);
+    }
+
+    #[test]
+    fn find_groups_should_group_players_by_topic_and_skill() {
+        let tickets = vec![
+            ticket("alice", &["Java", "Rust"], 1500.0, 5),
+            ticket("bob", &["Rust"], 1550.0, 4),
+            ticket("carol", &["Java"], 1450.0, 3),
+            ticket("dave", &["Rust"], 1480.0, 2),
+            ticket("erin", &["Rust"], 1900.0, 1),
+            ticket("frank", &["Rust"], 1510.0, 1),
+        ];
+        let groups = find_groups(&tickets, now());
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].users, vec!["alice", "frank", "dave", "bob"]);
+        assert_eq!(groups[0].topics, vec!["Rust"]);
+    }
+
+    #[test]
+    fn find_groups_should_ignore_topics_after_a_long_wait() {
+        let tickets = vec![
+            ticket("alice", &["Java"], 1500.0, 50),
+            ticket("bob", &["Rust"], 1550.0, 4),
+        ];
+        let groups = find_groups(&tickets, now());
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].users, vec!["alice", "bob"]);
+        assert_eq!(groups[0].topics, vec!["Java"]);
+    }
+}
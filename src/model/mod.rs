@@ -1,3 +1,20 @@
+pub mod api_key;
+pub mod buzzer;
+pub mod daily_challenge;
+pub mod duel;
 pub mod game;
 pub mod game_answer;
 pub mod game_progress;
+pub mod hint;
+pub mod leaderboard;
+pub mod matchmaking;
+pub mod oidc_login;
+pub mod power_up;
+pub mod practice;
+pub mod profile;
+pub mod question;
+pub mod rating;
+pub mod survival;
+pub mod team;
+pub mod user;
+pub mod wager;
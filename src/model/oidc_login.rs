@@ -0,0 +1,12 @@
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+///Pending OpenID Connect login, stored between the redirection to the provider and its callback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcLogin {
+    pub state: String,
+    pub provider: String,
+    pub code_verifier: String,
+    pub nonce: String,
+    pub created_at: DateTime,
+}
@@ -0,0 +1,179 @@
+use mongodb::bson::DateTime;
+use rand::seq::SliceRandom;
+use rocket::serde::{Deserialize, Serialize};
+use rocket::FromFormField;
+
+use crate::model::game_answer::GameAnswer;
+
+/// Power-ups every player of a game played with power-ups starts with.
+pub const STARTING_POWER_UPS: i32 = 1;
+/// Correct answers in a row earning a player another power-up.
+pub const POWER_UP_STREAK: i32 = 3;
+/// Seconds added to the time to answer of a player using [PowerUp::ExtraTime].
+pub const EXTRA_TIME_SECONDS: u64 = 10;
+/// Seconds taken from the time to answer of a player hit by [PowerUp::Freeze].
+pub const FREEZE_SECONDS: u64 = 5;
+/// Wrong answers hidden from a player using [PowerUp::FiftyFifty].
+pub const FIFTY_FIFTY_REMOVED: usize = 2;
+/// Number of answers of a question.
+const ANSWER_COUNT: i8 = 4;
+
+///Power-up a player spends during a question of a [Game](crate::model::game::Game) played with power-ups
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, FromFormField)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerUp {
+    /// Two wrong answers are removed, for the player only.
+    #[field(value = "fifty_fifty")]
+    FiftyFifty,
+    /// The player gets more time to answer, the question goes on for them once the time ran out for the others.
+    #[field(value = "extra_time")]
+    ExtraTime,
+    /// A correct answer of the player scores twice.
+    #[field(value = "double_points")]
+    DoublePoints,
+    /// The time to answer of an opponent is shortened.
+    #[field(value = "freeze")]
+    Freeze,
+}
+
+///Use of a [PowerUp] by a player of a [Game](crate::model::game::Game), stored with the game in the order of use.
+///A player uses a single power-up per question, before answering it.
+///`target` is the opponent hit by a [PowerUp::Freeze], and `removed_answers` the answers hidden by a [PowerUp::FiftyFifty].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GamePowerUp {
+    pub user: String,
+    pub power_up: PowerUp,
+    pub question_index: i8,
+    #[serde(default)]
+    pub target: Option<String>,
+    #[serde(default)]
+    pub removed_answers: Vec<i8>,
+    pub used_at: DateTime,
+}
+
+/// Counts the power-ups a player earned before the question of the given index:
+/// the [STARTING_POWER_UPS], and one more for each [POWER_UP_STREAK] correct answers in a row.
+/// A wrong or missing answer breaks the streak, questions skipped by the host do not.
+pub fn earned_power_ups(
+    user: &str,
+    answers: &[GameAnswer],
+    question_index: i8,
+    skipped: impl Fn(i8) -> bool,
+) -> i32 {
+    let mut earned = STARTING_POWER_UPS;
+    let mut streak = 0;
+    for index in (0..question_index).filter(|index| !skipped(*index)) {
+        let correct = answers.iter().any(|answer| {
+            answer.user == user
+                && answer.question_index == index
+                && answer.answer == answer.correct_answer
+        });
+        streak = if correct { streak + 1 } else { 0 };
+        if streak == POWER_UP_STREAK {
+            earned += 1;
+            streak = 0;
+        }
+    }
+    earned
+}
+
+/// Picks at random the wrong answers a [PowerUp::FiftyFifty] removes from a question.
+pub fn fifty_fifty(correct_answer: i8) -> Vec<i8> {
+    let mut wrong_answers: Vec<i8> = (1..=ANSWER_COUNT)
+        .filter(|answer| *answer != correct_answer)
+        .collect();
+    wrong_answers.shuffle(&mut rand::thread_rng());
+    wrong_answers.truncate(FIFTY_FIFTY_REMOVED);
+    wrong_answers.sort();
+    wrong_answers
+}
+
+/// Gives the seconds left to a player to answer a question.
+/// `remaining_time` is the time left to every player and `overtime` the time left to the players who took extra time
+/// once it ran out. Extra time is added to the time of the player, a freeze is taken from it.
+pub fn time_left(remaining_time: u64, overtime: u64, extended: bool, frozen: bool) -> u64 {
+    let extension = match (extended, remaining_time) {
+        (false, _) => 0,
+        (true, 0) => overtime,
+        (true, _) => EXTRA_TIME_SECONDS,
+    };
+    let penalty = if frozen { FREEZE_SECONDS } else { 0 };
+    (remaining_time + extension).saturating_sub(penalty)
+}
+
+/// Gives the seconds a player took to answer a question lasting `question_seconds`, from the time left to every player
+/// and the overtime left to the players who took extra time. A freeze shortens the time to answer, not the time taken.
+pub fn answer_seconds(question_seconds: u64, remaining_time: u64, overtime: u64) -> u64 {
+    let elapsed = question_seconds.saturating_sub(remaining_time);
+    match remaining_time {
+        0 if overtime > 0 => elapsed + EXTRA_TIME_SECONDS.saturating_sub(overtime),
+        _ => elapsed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::game_answer::GameAnswer;
+    use crate::model::power_up::{
+        answer_seconds, earned_power_ups, fifty_fifty, time_left, EXTRA_TIME_SECONDS,
+        STARTING_POWER_UPS,
+    };
+
+    fn answer(user: &str, question_index: i8, correct: bool) -> GameAnswer {
+        GameAnswer {
+            user: user.to_string(),
+            question_index,
+            answer: if correct { 2 } else { 1 },
+            correct_answer: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn earned_power_ups_should_reward_streaks() {
+        let answers: Vec<GameAnswer> = (0..7)
+            .map(|index| answer("alice", index, index != 3))
+            .collect();
+        assert_eq!(earned_power_ups("alice", &answers, 3, |_| false), 2);
+        assert_eq!(earned_power_ups("alice", &answers, 6, |_| false), 2);
+        assert_eq!(earned_power_ups("alice", &answers, 7, |_| false), 3);
+        assert_eq!(
+            earned_power_ups("alice", &answers, 5, |index| index == 3),
+            2
+        );
+        assert_eq!(
+            earned_power_ups("bob", &answers, 7, |_| false),
+            STARTING_POWER_UPS
+        );
+    }
+
+    #[test]
+    fn fifty_fifty_should_only_remove_wrong_answers() {
+        for correct_answer in 1..=4 {
+            let removed = fifty_fifty(correct_answer);
+            assert_eq!(removed.len(), 2);
+            assert!(!removed.contains(&correct_answer));
+            assert!(removed.iter().all(|answer| (1..=4).contains(answer)));
+            assert_ne!(removed[0], removed[1]);
+        }
+    }
+
+    #[test]
+    fn time_left_should_add_extra_time_and_remove_freezes() {
+        assert_eq!(time_left(12, 0, false, false), 12);
+        assert_eq!(time_left(12, 0, true, false), 12 + EXTRA_TIME_SECONDS);
+        assert_eq!(time_left(12, 0, false, true), 7);
+        assert_eq!(time_left(3, 0, false, true), 0);
+        assert_eq!(time_left(0, 4, true, false), 4);
+        assert_eq!(time_left(0, 4, false, false), 0);
+        assert_eq!(time_left(0, 8, true, true), 3);
+    }
+
+    #[test]
+    fn answer_seconds_should_count_the_overtime_taken() {
+        assert_eq!(answer_seconds(20, 12, 0), 8);
+        assert_eq!(answer_seconds(11, 4, 0), 7);
+        assert_eq!(answer_seconds(20, 0, EXTRA_TIME_SECONDS), 20);
+        assert_eq!(answer_seconds(11, 0, 4), 11 + EXTRA_TIME_SECONDS - 4);
+    }
+}
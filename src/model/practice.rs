@@ -0,0 +1,212 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::model::game_answer::GameAnswer;
+use crate::model::game_progress::Question;
+use crate::model::profile::{accuracy, TopicStats};
+
+/// Number of questions of a practice session when not chosen by the player.
+pub const DEFAULT_PRACTICE_QUESTIONS: i8 = 10;
+pub const MAX_PRACTICE_QUESTIONS: i8 = 50;
+
+///Practice session of a single player, to be stored in the database.
+///Its questions are picked when it starts, and the next one is asked as soon as the current one is answered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PracticeSession {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user: String,
+    pub topics: Vec<String>,
+    pub timed: bool,
+    pub questions: Vec<Question>,
+    pub current_question: i8,
+    pub question_started_at: DateTime,
+    pub started_at: DateTime,
+    pub finished_at: Option<DateTime>,
+}
+
+impl PracticeSession {
+    /// Gives the question to answer, none once every question was answered.
+    pub fn current(&self) -> Option<&Question> {
+        match self.finished_at {
+            Some(_) => None,
+            None => self.questions.get(self.current_question as usize),
+        }
+    }
+
+    /// Gives the whole seconds spent on the current question.
+    pub fn elapsed_seconds(&self, now: DateTime) -> u64 {
+        let elapsed = now.timestamp_millis() - self.question_started_at.timestamp_millis();
+        (elapsed.max(0) / 1000) as u64
+    }
+
+    /// Gives the seconds left to answer the current question out of `question_seconds`, none if the session is untimed.
+    pub fn remaining_time(&self, now: DateTime, question_seconds: u64) -> Option<u64> {
+        self.timed
+            .then(|| question_seconds.saturating_sub(self.elapsed_seconds(now)))
+    }
+}
+
+///Result of an answer in a practice session, telling the player the correct answer right away
+#[derive(Debug, Clone)]
+pub struct PracticeFeedback {
+    pub answer: i8,
+    pub correct_answer: i8,
+    pub correct: bool,
+    pub timed_out: bool,
+    pub session: PracticeSession,
+}
+
+///Summary of a practice session, computed from its answers
+#[derive(Debug, Clone, PartialEq)]
+pub struct PracticeSummary {
+    pub answers: i32,
+    pub correct_answers: i32,
+    pub accuracy: f64,
+    pub average_answer_seconds: Option<f64>,
+    pub topics: Vec<TopicStats>,
+    pub results: Vec<PracticeResult>,
+}
+
+///Answer of the player to a question of a practice session, `answer` being 0 when the time ran out
+#[derive(Debug, Clone, PartialEq)]
+pub struct PracticeResult {
+    pub question: Question,
+    pub answer: i8,
+    pub correct: bool,
+}
+
+/// Summarizes the answers of a practice session, in the order of its questions.
+/// Answer times are only known for timed sessions, out of `question_seconds` per question.
+pub fn summarize(
+    session: &PracticeSession,
+    answers: &[GameAnswer],
+    question_seconds: u64,
+) -> PracticeSummary {
+    let mut answers: Vec<&GameAnswer> = answers.iter().collect();
+    answers.sort_by_key(|answer| answer.question_index);
+    let results: Vec<PracticeResult> = answers
+        .iter()
+        .map(|answer| PracticeResult {
+            question: answer.question.clone(),
+            answer: answer.answer,
+            correct: answer.answer == answer.correct_answer,
+        })
+        .collect();
+    let mut topics: Vec<TopicStats> = vec![];
+    for result in &results {
+        let index = match topics
+            .iter()
+            .position(|topic| topic.topic == result.question.topic)
+        {
+            Some(index) => index,
+            None => {
+                topics.push(TopicStats {
+                    topic: result.question.topic.clone(),
+                    answers: 0,
+                    correct_answers: 0,
+                    accuracy: 0.0,
+                });
+                topics.len() - 1
+            }
+        };
+        topics[index].answers += 1;
+        topics[index].correct_answers += result.correct as i32;
+    }
+    for topic in &mut topics {
+        topic.accuracy = accuracy(topic.correct_answers, topic.answers);
+    }
+    let answer_count = results.len() as i32;
+    let correct_answers = results.iter().filter(|result| result.correct).count() as i32;
+    let average_answer_seconds = (session.timed && !results.is_empty()).then(|| {
+        results
+            .iter()
+            .map(|result| question_seconds.saturating_sub(result.question.remaining_time) as f64)
+            .sum::<f64>()
+            / results.len() as f64
+    });
+    PracticeSummary {
+        answers: answer_count,
+        correct_answers,
+        accuracy: accuracy(correct_answers, answer_count),
+        average_answer_seconds,
+        topics,
+        results,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::game_answer::GameAnswer;
+    use crate::model::game_progress::Question;
+    use crate::model::practice::{summarize, PracticeSession};
+    use mongodb::bson::DateTime;
+
+    fn session(timed: bool) -> PracticeSession {
+        PracticeSession {
+            id: None,
+            user: "alice".to_string(),
+            topics: vec!["Rust".to_string(), "Java".to_string()],
+            timed,
+            questions: vec![],
+            current_question: 0,
+            question_started_at: DateTime::from_millis(10_000),
+            started_at: DateTime::from_millis(10_000),
+            finished_at: None,
+        }
+    }
+
+    fn answer(question_index: i8, topic: &str, answer: i8, remaining_time: u64) -> GameAnswer {
+        GameAnswer {
+            game_id: "practice".to_string(),
+            user: "alice".to_string(),
+            answer,
+            question_index,
+            correct_answer: 2,
+            question: Question {
+                topic: topic.to_string(),
+                good_answer_number: 2,
+                remaining_time,
+                ..Default::default()
+            },
+            practice: true,
+            daily: false,
+            wager: None,
+            power_up: None,
+            answer_seconds: None,
+        }
+    }
+
+    #[test]
+    fn remaining_time_should_only_count_down_in_timed_sessions() {
+        let now = DateTime::from_millis(17_500);
+        assert_eq!(session(true).remaining_time(now, 20), Some(13));
+        assert_eq!(
+            session(true).remaining_time(DateTime::from_millis(90_000), 20),
+            Some(0)
+        );
+        assert_eq!(session(false).remaining_time(now, 20), None);
+    }
+
+    #[test]
+    fn summarize_should_give_accuracy_per_topic_and_answer_times() {
+        let answers = vec![
+            answer(2, "Rust", 0, 0),
+            answer(0, "Rust", 2, 14),
+            answer(1, "Java", 2, 16),
+        ];
+        let summary = summarize(&session(true), &answers, 20);
+        assert_eq!(summary.answers, 3);
+        assert_eq!(summary.correct_answers, 2);
+        assert_eq!(summary.average_answer_seconds, Some(10.0));
+        assert_eq!(summary.topics[0].topic, "Rust");
+        assert_eq!(summary.topics[0].accuracy, 0.5);
+        assert_eq!(summary.topics[1].accuracy, 1.0);
+        let answered: Vec<i8> = summary.results.iter().map(|result| result.answer).collect();
+        assert_eq!(answered, vec![2, 2, 0]);
+
+        let summary = summarize(&session(false), &answers, 20);
+        assert_eq!(summary.average_answer_seconds, None);
+    }
+}
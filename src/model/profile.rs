@@ -0,0 +1,99 @@
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+///Lifetime statistics of a player, to be stored in the database.
+///It is computed from the answers and the finished games of the player, and refreshed when their games finish.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerProfile {
+    #[serde(rename = "_id")]
+    pub user: String,
+    pub games_played: i32,
+    pub wins: i32,
+    pub answers: i32,
+    pub correct_answers: i32,
+    pub accuracy: f64,
+    pub average_answer_seconds: f64,
+    pub best_streak: i32,
+    pub topics: Vec<TopicStats>,
+    pub recent_games: Vec<RecentGame>,
+    #[serde(default)]
+    pub ratings: Vec<TopicRating>,
+    #[serde(default)]
+    pub survival_bests: Vec<SurvivalBest>,
+    pub updated_at: DateTime,
+}
+
+///Answer statistics of a [PlayerProfile] for a topic
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TopicStats {
+    pub topic: String,
+    pub answers: i32,
+    pub correct_answers: i32,
+    pub accuracy: f64,
+}
+
+///Skill rating of a [PlayerProfile] on a topic, copied from its [PlayerRating](crate::model::rating::PlayerRating)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TopicRating {
+    pub topic: String,
+    pub rating: f64,
+    pub games: i32,
+}
+
+///Finished game of a [PlayerProfile], with the standing of the player
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentGame {
+    pub game_id: String,
+    pub topics: Vec<String>,
+    pub score: i32,
+    pub rank: i32,
+    pub players: i32,
+    pub finished_at: Option<DateTime>,
+}
+
+///Personal best of a [PlayerProfile] in the survival games of a topic, the most questions answered correctly
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SurvivalBest {
+    pub topic: String,
+    pub score: i32,
+    pub game_id: String,
+    pub achieved_at: Option<DateTime>,
+}
+
+/// Gives the longest run of correct answers among outcomes in answering order.
+pub fn best_streak(outcomes: &[bool]) -> i32 {
+    outcomes
+        .split(|correct| !correct)
+        .map(|streak| streak.len() as i32)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Gives the share of correct answers, zero when nothing was answered.
+pub fn accuracy(correct_answers: i32, answers: i32) -> f64 {
+    match answers {
+        0 => 0.0,
+        _ => correct_answers as f64 / answers as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::profile::{accuracy, best_streak};
+
+    #[test]
+    fn best_streak_should_give_the_longest_run_of_correct_answers() {
+        assert_eq!(best_streak(&[]), 0);
+        assert_eq!(best_streak(&[false, false]), 0);
+        assert_eq!(
+            best_streak(&[true, true, false, true, true, true, false]),
+            3
+        );
+    }
+
+    #[test]
+    fn accuracy_should_be_zero_without_answers() {
+        assert_eq!(accuracy(0, 0), 0.0);
+        assert_eq!(accuracy(3, 4), 0.75);
+    }
+}
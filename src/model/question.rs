@@ -0,0 +1,23 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+///Question written by a user, to be stored in the database.
+///It is asked in the games of its topic along with the built-in questions.
+///Its optional `hints` can be revealed to the players during a game, at the cost of some points.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthoredQuestion {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub author: String,
+    pub question_text: String,
+    pub answer_1: String,
+    pub answer_2: String,
+    pub answer_3: String,
+    pub answer_4: String,
+    pub good_answer_number: i8,
+    pub topic: String,
+    #[serde(default)]
+    pub hints: Vec<String>,
+    pub created_at: DateTime,
+}
@@ -0,0 +1,153 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+/// Rating of a player before their first game on a topic.
+pub const INITIAL_RATING: f64 = 1500.0;
+/// Largest rating change a player can get from a single game.
+pub const K_FACTOR: f64 = 32.0;
+
+///Skill rating of a player on a topic, to be stored in the database.
+///There is one rating per player and topic, updated each time a game on the topic finishes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerRating {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user: String,
+    pub topic: String,
+    pub rating: f64,
+    pub games: i32,
+    pub updated_at: DateTime,
+}
+
+///Change of a [PlayerRating] after a game, to be stored in the database to chart the rating over time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingChange {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user: String,
+    pub topic: String,
+    pub game_id: String,
+    pub rating_before: f64,
+    pub rating: f64,
+    pub changed_at: DateTime,
+}
+
+///Result of a player in a finished game, used to compute their new rating on a topic.
+///`outcomes` tells which questions of the topic the player answered correctly, in the order they were asked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatedPlayer {
+    pub rating: f64,
+    pub rank: i32,
+    pub outcomes: Vec<bool>,
+}
+
+/// Gives the probability, according to the Elo model, that a player beats an opponent.
+pub fn expected_score(rating: f64, opponent_rating: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent_rating - rating) / 400.0))
+}
+
+/// Computes the new ratings of the players of a finished game, in the same order.
+/// Each player is compared to every other one, the result of a pair being the average of
+/// their final standings and of their head to head record on the questions of the topic.
+/// The [K_FACTOR] is shared between the opponents, so a game moves a rating by at most [K_FACTOR].
+pub fn rate_players(players: &[RatedPlayer]) -> Vec<f64> {
+    if players.len() < 2 {
+        return players.iter().map(|player| player.rating).collect();
+    }
+    let k_factor = K_FACTOR / (players.len() - 1) as f64;
+    players
+        .iter()
+        .enumerate()
+        .map(|(index, player)| {
+            let change: f64 = players
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(_, opponent)| {
+                    let standing = standing_result(player.rank, opponent.rank);
+                    let questions =
+                        question_result(&player.outcomes, &opponent.outcomes).unwrap_or(standing);
+                    let result = (standing + questions) / 2.0;
+                    result - expected_score(player.rating, opponent.rating)
+                })
+                .sum();
+            player.rating + k_factor * change
+        })
+        .collect()
+}
+
+fn standing_result(rank: i32, opponent_rank: i32) -> f64 {
+    match rank.cmp(&opponent_rank) {
+        std::cmp::Ordering::Less => 1.0,
+        std::cmp::Ordering::Equal => 0.5,
+        std::cmp::Ordering::Greater => 0.0,
+    }
+}
+
+/// Gives the share of the questions won against an opponent, a question answered by both or by none being a draw.
+/// Returns none if no question was asked.
+fn question_result(outcomes: &[bool], opponent_outcomes: &[bool]) -> Option<f64> {
+    if outcomes.is_empty() {
+        return None;
+    }
+    let points: f64 = outcomes
+        .iter()
+        .zip(opponent_outcomes.iter().chain(std::iter::repeat(&false)))
+        .map(
+            |(correct, opponent_correct)| match (correct, opponent_correct) {
+                (true, false) => 1.0,
+                (false, true) => 0.0,
+                _ => 0.5,
+            },
+        )
+        .sum();
+    Some(points / outcomes.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::rating::{expected_score, rate_players, RatedPlayer, INITIAL_RATING};
+
+    fn player(rating: f64, rank: i32, outcomes: Vec<bool>) -> RatedPlayer {
+        RatedPlayer {
+            rating,
+            rank,
+            outcomes,
+        }
+    }
+
+    #[test]
+    fn expected_score_should_favour_the_higher_rating() {
+        assert_eq!(expected_score(1500.0, 1500.0), 0.5);
+        assert!((expected_score(1900.0, 1500.0) - 0.909).abs() < 0.001);
+        assert!((expected_score(1500.0, 1900.0) - 0.091).abs() < 0.001);
+    }
+
+    #[test]
+    fn rate_players_should_move_ratings_from_losers_to_winners() {
+        let ratings = rate_players(&[
+            player(INITIAL_RATING, 1, vec![true, true]),
+            player(INITIAL_RATING, 2, vec![false, false]),
+        ]);
+        assert_eq!(ratings, vec![1516.0, 1484.0]);
+    }
+
+    #[test]
+    fn rate_players_should_weigh_question_outcomes_with_standings() {
+        let ratings = rate_players(&[
+            player(INITIAL_RATING, 1, vec![true, false]),
+            player(INITIAL_RATING, 2, vec![false, true]),
+            player(INITIAL_RATING, 2, vec![false, false]),
+        ]);
+        // The two last players share their rank, the questions tell them apart
+        assert_eq!(ratings, vec![1510.0, 1498.0, 1492.0]);
+        assert_eq!(ratings.iter().sum::<f64>(), 3.0 * INITIAL_RATING);
+    }
+
+    #[test]
+    fn rate_players_should_not_rate_a_lone_player() {
+        let ratings = rate_players(&[player(1600.0, 1, vec![true])]);
+        assert_eq!(ratings, vec![1600.0]);
+    }
+}
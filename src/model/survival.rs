@@ -0,0 +1,158 @@
+use rand::seq::SliceRandom;
+use std::collections::BTreeMap;
+
+use crate::model::game_answer::GameAnswer;
+use crate::model::game_progress::Question;
+
+/// Misses allowed to a player of a survival game when not set by its creator, the player is out on the last one.
+pub const DEFAULT_MISS_LIMIT: i8 = 3;
+/// Most misses a creator may allow to the players of a survival game.
+pub const MAX_MISS_LIMIT: i8 = 10;
+/// Survival games have no question count, they stop at the most questions a game can index.
+pub const SURVIVAL_MAX_QUESTIONS: i8 = i8::MAX;
+/// Questions asked at each level of a survival game before it gets harder.
+pub const SURVIVAL_QUESTIONS_PER_LEVEL: i8 = 5;
+/// Seconds taken from the time to answer at each level of a survival game.
+pub const SURVIVAL_SECONDS_STEP: u64 = 3;
+/// Shortest time to answer a question of a survival game.
+pub const SURVIVAL_MIN_SECONDS: u64 = 5;
+/// Hardest difficulty tier of the questions.
+pub const MAX_DIFFICULTY_TIER: u8 = 3;
+
+/// Gives the level of a survival game at the question of the given index, starting at zero.
+pub fn survival_level(question_index: i8) -> u8 {
+    (question_index.max(0) / SURVIVAL_QUESTIONS_PER_LEVEL) as u8
+}
+
+/// Gives the time to answer the question of the given index in a survival game, shorter at each level.
+pub fn survival_seconds(question_index: i8, question_seconds: u64) -> u64 {
+    question_seconds
+        .saturating_sub(survival_level(question_index) as u64 * SURVIVAL_SECONDS_STEP)
+        .max(SURVIVAL_MIN_SECONDS)
+}
+
+/// Gives the difficulty tier of the question of the given index in a survival game, from 1 up to [MAX_DIFFICULTY_TIER].
+pub fn survival_tier(question_index: i8) -> u8 {
+    (survival_level(question_index) + 1).min(MAX_DIFFICULTY_TIER)
+}
+
+/// Gives the difficulty tier of a question from the share of correct answers it got, from 1 for the easiest.
+/// Questions never answered are in the easiest tier.
+pub fn difficulty_tier(correct_rate: Option<f64>) -> u8 {
+    match correct_rate {
+        Some(rate) if rate < 1.0 / 3.0 => 3,
+        Some(rate) if rate < 2.0 / 3.0 => 2,
+        _ => 1,
+    }
+}
+
+/// Picks at random the question of the given index of a survival game, among the questions of its tier.
+/// `tiers` gives the tier of the questions by question text, missing questions being in the easiest tier.
+/// When no question is in the tier, the question is picked in the closest tier below, then above.
+pub fn pick_survival_question(
+    pool: &[Question],
+    tiers: &BTreeMap<String, u8>,
+    question_index: i8,
+) -> Option<Question> {
+    let tier_of = |question: &Question| tiers.get(&question.question_text).copied().unwrap_or(1);
+    let wanted = survival_tier(question_index);
+    let candidates = (1..=wanted)
+        .rev()
+        .chain(wanted + 1..=MAX_DIFFICULTY_TIER)
+        .map(|tier| {
+            pool.iter()
+                .filter(|question| tier_of(question) == tier)
+                .collect::<Vec<_>>()
+        })
+        .find(|questions| !questions.is_empty())?;
+    candidates
+        .choose(&mut rand::thread_rng())
+        .map(|question| (*question).clone())
+}
+
+/// Counts the questions up to the given index a player of a survival game missed, answering wrong or not at all.
+/// `skipped` tells the questions skipped by the host, they are not counted.
+pub fn count_misses(
+    user: &str,
+    answers: &[GameAnswer],
+    question_index: i8,
+    skipped: impl Fn(i8) -> bool,
+) -> i8 {
+    (0..=question_index)
+        .filter(|index| !skipped(*index))
+        .filter(|index| {
+            !answers.iter().any(|answer| {
+                answer.user == user
+                    && answer.question_index == *index
+                    && answer.answer == answer.correct_answer
+            })
+        })
+        .count()
+        .min(i8::MAX as usize) as i8
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::game_answer::GameAnswer;
+    use crate::model::game_progress::Question;
+    use crate::model::survival::{
+        count_misses, difficulty_tier, pick_survival_question, survival_seconds, survival_tier,
+        SURVIVAL_MIN_SECONDS,
+    };
+    use std::collections::BTreeMap;
+
+    fn question(text: &str) -> Question {
+        Question {
+            question_text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn answer(user: &str, question_index: i8, correct: bool) -> GameAnswer {
+        GameAnswer {
+            user: user.to_string(),
+            question_index,
+            answer: if correct { 2 } else { 1 },
+            correct_answer: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn survival_should_get_harder_at_each_level() {
+        assert_eq!(survival_seconds(0, 20), 20);
+        assert_eq!(survival_seconds(5, 20), 17);
+        assert_eq!(survival_seconds(100, 20), SURVIVAL_MIN_SECONDS);
+        assert_eq!(survival_tier(4), 1);
+        assert_eq!(survival_tier(5), 2);
+        assert_eq!(survival_tier(60), 3);
+    }
+
+    #[test]
+    fn pick_survival_question_should_fall_back_to_the_closest_tier() {
+        assert_eq!(difficulty_tier(None), 1);
+        assert_eq!(difficulty_tier(Some(0.5)), 2);
+        assert_eq!(difficulty_tier(Some(0.1)), 3);
+        let pool = vec![question("easy"), question("hard")];
+        let tiers = BTreeMap::from([("hard".to_string(), 3)]);
+        let first = pick_survival_question(&pool, &tiers, 0).unwrap();
+        assert_eq!(first.question_text, "easy");
+        let second_level = pick_survival_question(&pool, &tiers, 5).unwrap();
+        assert_eq!(second_level.question_text, "easy");
+        let last_level = pick_survival_question(&pool, &tiers, 10).unwrap();
+        assert_eq!(last_level.question_text, "hard");
+        assert!(pick_survival_question(&[], &tiers, 0).is_none());
+    }
+
+    #[test]
+    fn count_misses_should_count_wrong_and_missing_answers() {
+        let answers = vec![
+            answer("alice", 0, true),
+            answer("alice", 1, false),
+            answer("bob", 1, true),
+        ];
+        assert_eq!(count_misses("alice", &answers, 3, |_| false), 3);
+        assert_eq!(count_misses("alice", &answers, 3, |index| index == 2), 2);
+        assert_eq!(count_misses("bob", &answers, 1, |_| false), 1);
+    }
+}
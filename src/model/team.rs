@@ -0,0 +1,162 @@
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::model::game::GameStanding;
+
+/// Number of teams a team game needs at least.
+pub const MIN_TEAMS: usize = 2;
+
+///Team of a [Game](crate::model::game::Game) played in [GameMode::Team](crate::model::game::GameMode::Team).
+///Teams are named by the creator of the game, players join one of them in the lobby.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameTeam {
+    pub name: String,
+    pub users: Vec<String>,
+}
+
+///Rule aggregating the scores of the players of a team into the score of the team
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TeamScoring {
+    /// The scores of the players add up, bigger teams have an edge.
+    #[default]
+    Sum,
+    /// The average score of the players, teams of different sizes play on equal terms.
+    Average,
+    /// The score of the best player of the team.
+    Best,
+}
+
+impl TeamScoring {
+    /// Aggregates the scores of the players of a team, a team without players scores zero.
+    pub fn score(&self, scores: &[i32]) -> f64 {
+        if scores.is_empty() {
+            return 0.0;
+        }
+        match self {
+            TeamScoring::Sum => scores.iter().sum::<i32>() as f64,
+            TeamScoring::Average => scores.iter().sum::<i32>() as f64 / scores.len() as f64,
+            TeamScoring::Best => scores.iter().copied().max().unwrap_or_default() as f64,
+        }
+    }
+}
+
+///Standing of a team in a [Game], its score is aggregated from the scores of its players with the [TeamScoring] of the game.
+///Teams with the same score share the same rank.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamStanding {
+    pub team: String,
+    pub score: f64,
+    pub rank: i32,
+}
+
+/// Tells if teams can be played with: enough teams, with distinct and non blank names.
+pub fn are_teams_valid(teams: &[GameTeam]) -> bool {
+    teams.len() >= MIN_TEAMS
+        && teams.iter().enumerate().all(|(index, team)| {
+            !team.name.trim().is_empty()
+                && teams[..index]
+                    .iter()
+                    .all(|previous| previous.name != team.name)
+        })
+}
+
+/// Gives the team a new player is balanced into, the one with the fewest players, the first one on a tie.
+pub fn smallest_team(teams: &[GameTeam]) -> Option<&GameTeam> {
+    teams
+        .iter()
+        .enumerate()
+        .min_by_key(|(index, team)| (team.users.len(), *index))
+        .map(|(_, team)| team)
+}
+
+/// Ranks the teams from the standings of their players, best first.
+/// Players without a standing score zero.
+pub fn rank_teams(
+    teams: &[GameTeam],
+    standings: &[GameStanding],
+    scoring: TeamScoring,
+) -> Vec<TeamStanding> {
+    let mut scores: Vec<(String, f64)> = teams
+        .iter()
+        .map(|team| {
+            let scores: Vec<i32> = team
+                .users
+                .iter()
+                .map(|user| {
+                    standings
+                        .iter()
+                        .find(|standing| &standing.user == user)
+                        .map_or(0, |standing| standing.score)
+                })
+                .collect();
+            (team.name.clone(), scoring.score(&scores))
+        })
+        .collect();
+    scores.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    let mut team_standings: Vec<TeamStanding> = Vec::with_capacity(scores.len());
+    for (index, (team, score)) in scores.into_iter().enumerate() {
+        let rank = match team_standings.last() {
+            Some(previous) if previous.score == score => previous.rank,
+            _ => index as i32 + 1,
+        };
+        team_standings.push(TeamStanding { team, score, rank });
+    }
+    team_standings
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::game::rank_players;
+    use crate::model::team::{are_teams_valid, rank_teams, smallest_team, GameTeam, TeamScoring};
+
+    fn team(name: &str, users: &[&str]) -> GameTeam {
+        GameTeam {
+            name: name.to_string(),
+            users: users.iter().map(|user| user.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn are_teams_valid_should_need_distinct_named_teams() {
+        assert!(are_teams_valid(&[team("Sales", &[]), team("IT", &[])]));
+        assert!(!are_teams_valid(&[team("Sales", &[])]));
+        assert!(!are_teams_valid(&[team("Sales", &[]), team("Sales", &[])]));
+        assert!(!are_teams_valid(&[team("Sales", &[]), team(" ", &[])]));
+    }
+
+    #[test]
+    fn smallest_team_should_balance_players() {
+        let teams = vec![
+            team("Sales", &["alice", "bob"]),
+            team("IT", &["carol"]),
+            team("HR", &["dave"]),
+        ];
+        assert_eq!(smallest_team(&teams).unwrap().name, "IT");
+        assert_eq!(smallest_team(&[]), None);
+    }
+
+    #[test]
+    fn rank_teams_should_aggregate_with_the_scoring_rule() {
+        let teams = vec![
+            team("Sales", &["alice", "bob", "erin"]),
+            team("IT", &["carol", "dave"]),
+        ];
+        let standings = rank_players(vec![
+            ("alice".to_string(), 2),
+            ("bob".to_string(), 2),
+            ("carol".to_string(), 5),
+            ("dave".to_string(), 1),
+        ]);
+        let sum = rank_teams(&teams, &standings, TeamScoring::Sum);
+        assert_eq!((sum[0].team.as_str(), sum[0].score), ("IT", 6.0));
+        assert_eq!((sum[1].team.as_str(), sum[1].rank), ("Sales", 2));
+        let average = rank_teams(&teams, &standings, TeamScoring::Average);
+        assert_eq!(average[0].score, 3.0);
+        assert_eq!(average[1].score, 4.0 / 3.0);
+        let best = rank_teams(&teams, &standings, TeamScoring::Best);
+        assert_eq!(best[0].score, 5.0);
+        assert_eq!(best[1].score, 2.0);
+        let tie = rank_teams(&teams, &rank_players(vec![]), TeamScoring::Sum);
+        assert_eq!(tie[1].rank, 1);
+    }
+}
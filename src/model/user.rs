@@ -0,0 +1,73 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+///User entity to be stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub username: String,
+    #[serde(default)]
+    pub password_hash: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub identities: Vec<UserIdentity>,
+    #[serde(default = "default_roles")]
+    pub roles: Vec<UserRole>,
+    /// Guest the user was registered from, the upgrade of the guest may be resumed with the credentials of the user.
+    #[serde(default)]
+    pub upgraded_from: Option<String>,
+    /// When the whole history of the guest was moved to the user, the tokens of the guest are refused from then on.
+    #[serde(default)]
+    pub upgraded_at: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+///Role granting privileges to a [User], what each role allows is decided by the [policy](crate::service::policy)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UserRole {
+    Player,
+    Host,
+    QuestionAuthor,
+    Moderator,
+    Admin,
+}
+
+/// Roles given to new users and guests: everybody may play and host games.
+pub fn default_roles() -> Vec<UserRole> {
+    vec![UserRole::Player, UserRole::Host]
+}
+
+///Account of a [User] at an OpenID Connect provider, identified by the issuer and the subject of its ID tokens
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserIdentity {
+    pub issuer: String,
+    pub subject: String,
+}
+
+/// Separates the nickname of a guest from the tag making their player name unique, as in `alice#1f2e3d4c`.
+/// Usernames of registered users cannot contain it.
+pub const GUEST_TAG_SEPARATOR: char = '#';
+
+/// Gives the nickname shown for a player, the tag of guests is left out.
+pub fn nickname_of(player: &str) -> &str {
+    player
+        .split_once(GUEST_TAG_SEPARATOR)
+        .map_or(player, |(nickname, _)| nickname)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::user::nickname_of;
+
+    #[test]
+    fn nickname_of_should_leave_out_the_guest_tag() {
+        assert_eq!(nickname_of("alice#1f2e3d4c"), "alice");
+        assert_eq!(nickname_of("bob"), "bob");
+    }
+}
@@ -0,0 +1,86 @@
+use crate::model::game_answer::GameAnswer;
+
+/// Points every player of a wager game starts with.
+pub const WAGER_STARTING_POINTS: i32 = 10;
+/// Smallest stake of a wager, a player out of points can still stake it.
+pub const MIN_WAGER: i32 = 1;
+
+/// Gives the points won or lost by an answer of a wager game: the stake when the answer is correct,
+/// minus the stake otherwise.
+pub fn wager_points(answer: &GameAnswer) -> i32 {
+    let wager = answer.wager.unwrap_or_default();
+    if answer.answer == answer.correct_answer {
+        wager
+    } else {
+        -wager
+    }
+}
+
+/// Gives the points of a player of a wager game after the given answers.
+pub fn wager_balance(user: &str, answers: &[GameAnswer]) -> i32 {
+    WAGER_STARTING_POINTS
+        + answers
+            .iter()
+            .filter(|answer| answer.user == user)
+            .map(wager_points)
+            .sum::<i32>()
+}
+
+/// Tells if a player with the given points may stake a wager: at least [MIN_WAGER], at most their points.
+pub fn is_wager_valid(wager: i32, balance: i32) -> bool {
+    (MIN_WAGER..=balance.max(MIN_WAGER)).contains(&wager)
+}
+
+/// Computes the score of each player of a wager game, the points they have after the given answers.
+pub fn wager_scores(users: &[String], answers: &[GameAnswer]) -> Vec<(String, i32)> {
+    users
+        .iter()
+        .map(|user| (user.clone(), wager_balance(user, answers)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::game_answer::GameAnswer;
+    use crate::model::wager::{
+        is_wager_valid, wager_balance, wager_scores, MIN_WAGER, WAGER_STARTING_POINTS,
+    };
+
+    fn answer(user: &str, question_index: i8, correct: bool, wager: i32) -> GameAnswer {
+        GameAnswer {
+            game_id: "wager".to_string(),
+            user: user.to_string(),
+            answer: if correct { 2 } else { 1 },
+            question_index,
+            correct_answer: 2,
+            wager: Some(wager),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn wager_balance_should_add_won_stakes_and_remove_lost_ones() {
+        let answers = vec![
+            answer("alice", 0, true, 5),
+            answer("alice", 1, false, 8),
+            answer("bob", 0, false, 10),
+        ];
+        assert_eq!(wager_balance("alice", &answers), WAGER_STARTING_POINTS - 3);
+        assert_eq!(wager_balance("bob", &answers), 0);
+        assert_eq!(wager_balance("carol", &answers), WAGER_STARTING_POINTS);
+        let users = vec!["alice".to_string(), "carol".to_string()];
+        assert_eq!(
+            wager_scores(&users, &answers),
+            vec![("alice".to_string(), 7), ("carol".to_string(), 10)]
+        );
+    }
+
+    #[test]
+    fn is_wager_valid_should_stay_within_the_points_of_the_player() {
+        assert!(is_wager_valid(10, 10));
+        assert!(!is_wager_valid(11, 10));
+        assert!(!is_wager_valid(0, 10));
+        assert!(is_wager_valid(MIN_WAGER, -4));
+        assert!(!is_wager_valid(2, 0));
+    }
+}
@@ -0,0 +1,97 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, DateTime};
+use mongodb::options::{ClientOptions, FindOneAndUpdateOptions, IndexOptions, ReturnDocument};
+use mongodb::results::{CreateIndexResult, InsertOneResult, UpdateResult};
+use mongodb::{Client, Collection, Cursor, IndexModel};
+use std::env;
+extern crate dotenv;
+use crate::model::api_key::ApiKey;
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const API_KEY: &str = "ApiKey";
+
+/// Repository for [ApiKey] object to interact with the database
+pub struct ApiKeyRepo {
+    col: Collection<ApiKey>,
+}
+
+impl ApiKeyRepo {
+    /// Creates a new instance of [ApiKeyRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<ApiKey> = db.collection(API_KEY);
+        debug!("DB client created");
+        ApiKeyRepo { col }
+    }
+
+    /// Creates the unique index on key hashes, used to look keys up.
+    pub async fn create_indexes(&self) -> mongodb::error::Result<CreateIndexResult> {
+        debug!("Creating API key indexes in DB");
+        let index = IndexModel::builder()
+            .keys(doc! {"key_hash": 1})
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        let created = self.col.create_index(index, None).await;
+        info!("API key indexes created in DB");
+        created
+    }
+
+    /// Creates a new [ApiKey] in the database.
+    pub async fn create_api_key(&self, api_key: ApiKey) -> mongodb::error::Result<InsertOneResult> {
+        debug!("Creating API key in DB");
+        let api_key_created = self.col.insert_one(api_key, None).await;
+        info!("API key created in DB");
+        api_key_created
+    }
+
+    /// Gets all the [ApiKey]s of a user from the database, revoked ones included.
+    pub async fn get_api_keys(&self, owner: String) -> mongodb::error::Result<Cursor<ApiKey>> {
+        debug!("Getting API keys from DB");
+        let api_keys = self.col.find(doc! {"owner": owner}, None).await;
+        info!("API keys retrieved from DB");
+        api_keys
+    }
+
+    /// Gets the not revoked [ApiKey] having the given hash and records that it was just used.
+    pub async fn use_api_key(&self, key_hash: String) -> mongodb::error::Result<Option<ApiKey>> {
+        debug!("Using API key in DB");
+        let filter = doc! {"key_hash": key_hash, "revoked_at": null};
+        let update = doc! { "$set": doc! {"last_used_at": DateTime::now()} };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let api_key = self.col.find_one_and_update(filter, update, options).await;
+        info!("API key used in DB");
+        api_key
+    }
+
+    /// Revokes an [ApiKey] of a user, it cannot be used anymore.
+    /// Nothing is updated if the key does not belong to the user or is already revoked.
+    pub async fn revoke_api_key(
+        &self,
+        id: ObjectId,
+        owner: String,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Revoking API key in DB");
+        let filter = doc! {"_id": id, "owner": owner, "revoked_at": null};
+        let update = doc! { "$set": doc! {"revoked_at": DateTime::now()} };
+        let api_key = self.col.update_one(filter, update, None).await;
+        info!("API key revoked in DB");
+        api_key
+    }
+}
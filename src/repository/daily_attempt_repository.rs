@@ -0,0 +1,184 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, DateTime};
+use mongodb::options::{
+    ClientOptions, FindOneAndUpdateOptions, FindOptions, IndexOptions, ReturnDocument,
+};
+use mongodb::results::{CreateIndexesResult, InsertOneResult, UpdateResult};
+use mongodb::{Client, Collection, Cursor, IndexModel};
+use std::env;
+extern crate dotenv;
+use crate::model::daily_challenge::DailyAttempt;
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const DAILY_ATTEMPT: &str = "DailyAttempt";
+
+/// Repository for [DailyAttempt] object to interact with the database
+pub struct DailyAttemptRepo {
+    col: Collection<DailyAttempt>,
+}
+
+impl DailyAttemptRepo {
+    /// Creates a new instance of [DailyAttemptRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<DailyAttempt> = db.collection(DAILY_ATTEMPT);
+        debug!("DB client created");
+        DailyAttemptRepo { col }
+    }
+
+    /// Creates the unique index on the days and players, so a player attempts a challenge only once,
+    /// and the index used to rank the attempts of a day.
+    pub async fn create_indexes(&self) -> mongodb::error::Result<CreateIndexesResult> {
+        debug!("Creating daily attempt indexes in DB");
+        let player_day = IndexModel::builder()
+            .keys(doc! {"user": 1, "day": 1})
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        let ranking = IndexModel::builder()
+            .keys(doc! {"day": 1, "score": -1, "answer_seconds": 1})
+            .build();
+        let created = self
+            .col
+            .create_indexes(vec![player_day, ranking], None)
+            .await;
+        info!("Daily attempt indexes created in DB");
+        created
+    }
+
+    /// Creates a new [DailyAttempt] in the database.
+    /// Returns an error if the player already attempted the challenge of the day.
+    pub async fn create_attempt(
+        &self,
+        attempt: DailyAttempt,
+    ) -> mongodb::error::Result<InsertOneResult> {
+        debug!("Creating daily attempt in DB");
+        let attempt_created = self.col.insert_one(attempt, None).await;
+        info!("Daily attempt created in DB");
+        attempt_created
+    }
+
+    /// Gets the [DailyAttempt] of a player at the challenge of a day from the database.
+    pub async fn get_attempt(
+        &self,
+        day: String,
+        user: String,
+    ) -> mongodb::error::Result<Option<DailyAttempt>> {
+        debug!("Getting daily attempt from DB");
+        let attempt = self
+            .col
+            .find_one(doc! {"day": day, "user": user}, None)
+            .await;
+        info!("Daily attempt retrieved from DB");
+        attempt
+    }
+
+    /// Moves a [DailyAttempt] from the given question to the next one, adding the points and the answer time
+    /// of the answer, finishing it after its last question, and returns the updated attempt.
+    /// Returns none if the attempt is not on the given question anymore, so a question is answered only once.
+    pub async fn next_question(
+        &self,
+        id: ObjectId,
+        current_question: i8,
+        points: i32,
+        answer_seconds: i64,
+        finished: bool,
+        now: DateTime,
+    ) -> mongodb::error::Result<Option<DailyAttempt>> {
+        debug!("Moving daily attempt to next question in DB");
+        let filter =
+            doc! {"_id": id, "current_question": current_question as i32, "finished_at": null};
+        let update = doc! {
+            "$set": doc! {
+                "current_question": current_question as i32 + 1,
+                "question_started_at": now,
+                "finished_at": if finished { Some(now) } else { None },
+            },
+            "$inc": doc! {"score": points, "answer_seconds": answer_seconds},
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let attempt = self.col.find_one_and_update(filter, update, options).await;
+        info!("Daily attempt moved to next question in DB");
+        attempt
+    }
+
+    /// Gets a page of the [DailyAttempt]s of a day from the database, best first.
+    pub async fn get_attempts(
+        &self,
+        day: String,
+        skip: u64,
+        limit: i64,
+    ) -> mongodb::error::Result<Cursor<DailyAttempt>> {
+        debug!("Getting daily attempts from DB");
+        let options = FindOptions::builder()
+            .sort(doc! {"score": -1, "answer_seconds": 1, "user": 1})
+            .skip(skip)
+            .limit(limit)
+            .build();
+        let attempts = self.col.find(doc! {"day": day}, options).await;
+        info!("Daily attempts retrieved from DB");
+        attempts
+    }
+
+    /// Counts the [DailyAttempt]s of a day ranked before the given score and answer time.
+    pub async fn count_ahead(
+        &self,
+        day: String,
+        score: i32,
+        answer_seconds: i64,
+    ) -> mongodb::error::Result<u64> {
+        debug!("Counting daily attempts ahead in DB");
+        let filter = doc! {"day": day, "$or": [
+            doc! {"score": doc! {"$gt": score}},
+            doc! {"score": score, "answer_seconds": doc! {"$lt": answer_seconds}},
+        ]};
+        let count = self.col.count_documents(filter, None).await;
+        info!("Daily attempts ahead counted in DB");
+        count
+    }
+
+    /// Gets the days a player attempted the challenge from the database, oldest first.
+    pub async fn get_attempt_days(&self, user: String) -> mongodb::error::Result<Vec<String>> {
+        debug!("Getting daily attempt days from DB");
+        let days = self.col.distinct("day", doc! {"user": user}, None).await?;
+        let mut days: Vec<String> = days
+            .into_iter()
+            .filter_map(|day| day.as_str().map(str::to_string))
+            .collect();
+        days.sort();
+        info!("Daily attempt days retrieved from DB");
+        Ok(days)
+    }
+
+    /// Moves all the [DailyAttempt]s of a player to another player name.
+    pub async fn rename_user(
+        &self,
+        from: String,
+        to: String,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Renaming user of daily attempts in DB");
+        let update = doc! { "$set": doc! {"user": to} };
+        let attempts = self
+            .col
+            .update_many(doc! {"user": from}, update, None)
+            .await;
+        info!("User of daily attempts renamed in DB");
+        attempts
+    }
+}
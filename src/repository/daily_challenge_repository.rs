@@ -0,0 +1,75 @@
+use mongodb::bson::doc;
+use mongodb::options::{ClientOptions, IndexOptions};
+use mongodb::results::{CreateIndexResult, InsertOneResult};
+use mongodb::{Client, Collection, IndexModel};
+use std::env;
+extern crate dotenv;
+use crate::model::daily_challenge::DailyChallenge;
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const DAILY_CHALLENGE: &str = "DailyChallenge";
+
+/// Repository for [DailyChallenge] object to interact with the database
+pub struct DailyChallengeRepo {
+    col: Collection<DailyChallenge>,
+}
+
+impl DailyChallengeRepo {
+    /// Creates a new instance of [DailyChallengeRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<DailyChallenge> = db.collection(DAILY_CHALLENGE);
+        debug!("DB client created");
+        DailyChallengeRepo { col }
+    }
+
+    /// Creates the unique index on the days, so there is only one challenge a day.
+    pub async fn create_indexes(&self) -> mongodb::error::Result<CreateIndexResult> {
+        debug!("Creating daily challenge indexes in DB");
+        let day = IndexModel::builder()
+            .keys(doc! {"day": 1})
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        let created = self.col.create_index(day, None).await;
+        info!("Daily challenge indexes created in DB");
+        created
+    }
+
+    /// Creates a new [DailyChallenge] in the database.
+    /// Returns an error if the challenge of the day already exists.
+    pub async fn create_challenge(
+        &self,
+        challenge: DailyChallenge,
+    ) -> mongodb::error::Result<InsertOneResult> {
+        debug!("Creating daily challenge in DB");
+        let challenge_created = self.col.insert_one(challenge, None).await;
+        info!("Daily challenge created in DB");
+        challenge_created
+    }
+
+    /// Gets the [DailyChallenge] of a day from the database.
+    pub async fn get_challenge(
+        &self,
+        day: String,
+    ) -> mongodb::error::Result<Option<DailyChallenge>> {
+        debug!("Getting daily challenge from DB");
+        let challenge = self.col.find_one(doc! {"day": day}, None).await;
+        info!("Daily challenge retrieved from DB");
+        challenge
+    }
+}
@@ -1,10 +1,14 @@
 extern crate dotenv;
 use dotenv::dotenv;
 use log::debug;
+use mongodb::bson::Document;
 use mongodb::{bson::doc, options::ClientOptions, Client, Collection, Cursor};
+use rocket::futures::TryStreamExt;
 use std::env;
 
+use crate::model::game::GameStatus;
 use crate::model::game_answer::GameAnswer;
+use crate::repository::game_repository::GAME;
 
 pub const MONGO_URI: &str = "MONGO_URI";
 pub const MONGO_DATABASE: &str = "CodingFighters";
@@ -62,4 +66,129 @@ impl GameAnswerRepo {
         debug!("Games retrieved from DB");
         game
     }
+
+    /// Moves all the [GameAnswer]s of a player to another player name.
+    pub async fn rename_user(
+        &self,
+        from: String,
+        to: String,
+    ) -> mongodb::error::Result<mongodb::results::UpdateResult> {
+        debug!("Renaming user of answers in DB");
+        let update = doc! { "$set": doc! {"user": to} };
+        let answers = self.col.update_many(doc!("user": from), update, None).await;
+        debug!("User of answers renamed in DB");
+        answers
+    }
+
+    /// Computes the score of each player of a game, the number of their correct answers, counted twice with double points.
+    pub async fn aggregate_scores(
+        &self,
+        game_id: String,
+    ) -> mongodb::error::Result<Cursor<Document>> {
+        debug!("Aggregating game scores in DB");
+        let pipeline = vec![
+            doc! {"$match": doc! {"game_id": game_id}},
+            doc! {"$group": doc! {"_id": "$user", "score": doc! {"$sum": Self::answer_points()}}},
+        ];
+        let scores = self.col.aggregate(pipeline, None).await;
+        debug!("Game scores aggregated in DB");
+        scores
+    }
+
+    /// Computes the share of correct answers of each question of the given topics, by question text.
+    /// Answers given in practice sessions or daily challenge attempts are left out.
+    pub async fn aggregate_question_rates(
+        &self,
+        topics: Vec<String>,
+    ) -> mongodb::error::Result<Cursor<Document>> {
+        debug!("Aggregating question correct rates in DB");
+        let pipeline = vec![
+            doc! {"$match": doc! {
+                "question.topic": doc! {"$in": topics},
+                "practice": doc! {"$ne": true},
+                "daily": doc! {"$ne": true},
+            }},
+            doc! {"$group": doc! {
+                "_id": "$question.question_text",
+                "correct_rate": doc! {"$avg": Self::correct_count()},
+            }},
+        ];
+        let rates = self.col.aggregate(pipeline, None).await;
+        debug!("Question correct rates aggregated in DB");
+        rates
+    }
+
+    /// Computes the answer statistics of a player: overall and per topic counts, average answer time,
+    /// and the outcomes of all their answers in answering order.
+    /// Answer times are the ones recorded with the answers, `question_seconds` is the time given to answer a question
+    /// and gives the time of older answers recorded without it.
+    /// Only answers of finished games count, answers given in practice sessions, daily challenge attempts
+    /// or games aborted, expired or still played are left out.
+    pub async fn aggregate_player_stats(
+        &self,
+        user: String,
+        question_seconds: i64,
+    ) -> mongodb::error::Result<Option<Document>> {
+        debug!("Aggregating player answer statistics in DB");
+        let answer_seconds = doc! {"$ifNull": [
+            "$answer_seconds",
+            doc! {"$subtract": [question_seconds, "$question.remaining_time"]},
+        ]};
+        let pipeline = vec![
+            doc! {"$match": doc! {
+                "user": user,
+                "practice": doc! {"$ne": true},
+                "daily": doc! {"$ne": true},
+            }},
+            doc! {"$lookup": doc! {
+                "from": GAME,
+                "let": doc! {"game_id": doc! {"$convert": doc! {
+                    "input": "$game_id",
+                    "to": "objectId",
+                    "onError": null,
+                    "onNull": null,
+                }}},
+                "pipeline": [
+                    doc! {"$match": doc! {"$expr": doc! {"$eq": ["$_id", "$$game_id"]}}},
+                    doc! {"$project": doc! {"status": 1}},
+                ],
+                "as": "game",
+            }},
+            doc! {"$match": doc! {"game.status": GameStatus::Finished.as_str()}},
+            doc! {"$sort": doc! {"_id": 1}},
+            doc! {"$facet": doc! {
+                "overall": [doc! {"$group": doc! {
+                    "_id": null,
+                    "answers": doc! {"$sum": 1},
+                    "correct_answers": doc! {"$sum": Self::correct_count()},
+                    "average_answer_seconds": doc! {"$avg": answer_seconds},
+                    "outcomes": doc! {"$push": doc! {"$eq": ["$answer", "$correct_answer"]}},
+                }}],
+                "topics": [
+                    doc! {"$group": doc! {
+                        "_id": "$question.topic",
+                        "answers": doc! {"$sum": 1},
+                        "correct_answers": doc! {"$sum": Self::correct_count()},
+                    }},
+                    doc! {"$sort": doc! {"_id": 1}},
+                ],
+            }},
+        ];
+        let mut stats = self.col.aggregate(pipeline, None).await?;
+        let stats = stats.try_next().await;
+        debug!("Player answer statistics aggregated in DB");
+        stats
+    }
+
+    /// Points of an answer in a game score: one when correct, two when the player used double points.
+    fn answer_points() -> Document {
+        doc! {"$multiply": [
+            Self::correct_count(),
+            doc! {"$cond": [doc! {"$eq": ["$power_up", "double_points"]}, 2, 1]},
+        ]}
+    }
+
+    fn correct_count() -> Document {
+        doc! {"$cond": [doc! {"$eq": ["$answer", "$correct_answer"]}, 1, 0]}
+    }
 }
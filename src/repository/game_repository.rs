@@ -2,12 +2,20 @@ use mongodb::results::{InsertOneResult, UpdateResult};
 use mongodb::{Client, Collection, Cursor};
 use std::env;
 extern crate dotenv;
-use crate::model::game::Game;
+use crate::model::buzzer::GameBuzz;
+use crate::model::duel::GameDamage;
+use crate::model::game::{Game, GameElimination, GameHostAction, GameStanding, GameStatus};
+use crate::model::hint::GameHint;
+use crate::model::power_up::GamePowerUp;
+use crate::model::team::TeamStanding;
+use crate::model::user::{nickname_of, GUEST_TAG_SEPARATOR};
 use dotenv::dotenv;
 use log::{debug, info};
 use mongodb::bson::doc;
 use mongodb::bson::oid::ObjectId;
-use mongodb::options::ClientOptions;
+use mongodb::bson::{to_bson, Bson, DateTime, Document, Regex};
+use mongodb::options::{ClientOptions, FindOneAndUpdateOptions, FindOptions, ReturnDocument};
+use rocket::futures::TryStreamExt;
 
 pub const MONGO_URI: &str = "MONGO_URI";
 pub const MONGO_DATABASE: &str = "CodingFighters";
@@ -50,17 +58,39 @@ impl GameRepo {
 
     /// Gets all the [Game]s from the database.
     /// Returns an empty list if there are no games.
-    /// Returns only public and not started games.
-    pub async fn get_games(&self) -> mongodb::error::Result<Cursor<Game>> {
+    /// Returns only public games having one of the given statuses.
+    pub async fn get_games(
+        &self,
+        statuses: Vec<GameStatus>,
+    ) -> mongodb::error::Result<Cursor<Game>> {
         debug!("Getting games from DB");
+        let statuses: Vec<&str> = statuses.iter().map(GameStatus::as_str).collect();
         let games = self
             .col
-            .find(doc!("is_private": false, "is_started": false), None)
+            .find(
+                doc!("is_private": false, "status": doc! {"$in": statuses}),
+                None,
+            )
             .await;
         info!("Games retrieved from DB");
         games
     }
 
+    /// Gets the finished [Game]s having standings from the database, oldest first.
+    pub async fn get_finished_games(&self) -> mongodb::error::Result<Cursor<Game>> {
+        debug!("Getting finished games from DB");
+        let filter = doc! {
+            "status": GameStatus::Finished.as_str(),
+            "standings.0": doc! {"$exists": true},
+        };
+        let options = FindOptions::builder()
+            .sort(doc! {"status_changed_at": 1})
+            .build();
+        let games = self.col.find(filter, options).await;
+        info!("Finished games retrieved from DB");
+        games
+    }
+
     /// Gets a [Game] by id from the database.
     /// Returns an error if the game does not exist.
     /// Returns an error if the id is not a valid ObjectId.
@@ -71,26 +101,624 @@ impl GameRepo {
         game
     }
 
-    /// Patches a [Game] by id from the database.
-    /// Returns an error if the game does not exist.
-    /// Returns an error if the id is not a valid ObjectId.
-    pub async fn patch_game(&self, id: ObjectId) -> mongodb::error::Result<UpdateResult> {
-        debug!("Patching game by id from DB");
-        let update = doc! { "$set": doc! {"is_started": true} };
+    /// Moves a [Game] from the `from` status to the `next` status and records the transition time.
+    /// Nothing is updated if the game is not in the `from` status anymore.
+    pub async fn update_status(
+        &self,
+        id: ObjectId,
+        from: GameStatus,
+        next: GameStatus,
+        changed_at: DateTime,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Updating game status in DB");
+        let filter = doc! {"_id": id, "status": from.as_str()};
+        let update = doc! {
+            "$set": doc! {"status": next.as_str(), "status_changed_at": changed_at},
+            "$push": doc! {"status_history": doc! {"status": next.as_str(), "changed_at": changed_at}},
+        };
+        let game = self.col.update_one(filter, update, None).await;
+        info!("Game status updated in DB");
+        game
+    }
+
+    /// Moves all the [Game]s left in the `from` status since before `stale_before` to the `next` status.
+    pub async fn update_stale_statuses(
+        &self,
+        from: GameStatus,
+        next: GameStatus,
+        stale_before: DateTime,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Updating stale game statuses in DB");
+        let changed_at = DateTime::now();
+        let filter =
+            doc! {"status": from.as_str(), "status_changed_at": doc! {"$lt": stale_before}};
+        let update = doc! {
+            "$set": doc! {"status": next.as_str(), "status_changed_at": changed_at},
+            "$push": doc! {"status_history": doc! {"status": next.as_str(), "changed_at": changed_at}},
+        };
+        let games = self.col.update_many(filter, update, None).await;
+        info!("Stale game statuses updated in DB");
+        games
+    }
+
+    /// Migrates [Game]s stored before the status lifecycle existed.
+    /// Not started games go back to the lobby, started games are expired as their progress task is gone.
+    /// The lobby entry of the history is dated from the creation time held by the ObjectId.
+    pub async fn migrate_legacy_statuses(&self) -> mongodb::error::Result<u64> {
+        debug!("Migrating legacy game statuses in DB");
+        let mut migrated = 0;
+        for (is_started, status) in [(false, GameStatus::Lobby), (true, GameStatus::Expired)] {
+            let filter = doc! {"status": doc! {"$exists": false}, "is_started": is_started};
+            let result = self
+                .col
+                .update_many(filter, Self::legacy_status_pipeline(status), None)
+                .await?;
+            migrated += result.modified_count;
+        }
+        info!("Legacy game statuses migrated in DB");
+        Ok(migrated)
+    }
+
+    fn legacy_status_pipeline(status: GameStatus) -> Vec<Document> {
+        let created_at = doc! {"$toDate": "$_id"};
+        let lobby = doc! {"status": GameStatus::Lobby.as_str(), "changed_at": created_at.clone()};
+        let (changed_at, history): (Bson, Vec<Document>) = match status {
+            GameStatus::Lobby => (created_at.into(), vec![lobby]),
+            _ => {
+                let now = DateTime::now();
+                let change = doc! {"status": status.as_str(), "changed_at": now};
+                (now.into(), vec![lobby, change])
+            }
+        };
+        vec![
+            doc! {"$set": doc! {
+                "status": status.as_str(),
+                "status_changed_at": changed_at,
+                "status_history": history,
+            }},
+            doc! {"$unset": "is_started"},
+        ]
+    }
+
+    /// Adds a player to a [Game] lobby in a single conditional update.
+    /// The player is added only if the game is in the lobby, is not full, if the player was not kicked from it
+    /// and if no other player goes by the same nickname.
+    /// Returns the updated game, or none if the player could not be added.
+    pub async fn add_user(
+        &self,
+        id: ObjectId,
+        user: String,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Adding user to game in DB");
+        // Nicknames only hold letters, digits, '_' and '-', they need no escaping
+        let same_nickname = Regex {
+            pattern: format!("^{}({}|$)", nickname_of(&user), GUEST_TAG_SEPARATOR),
+            options: String::new(),
+        };
+        let filter = doc! {
+            "_id": id,
+            "status": GameStatus::Lobby.as_str(),
+            "users": doc! {"$not": same_nickname},
+            "kicked_users": doc! {"$ne": &user},
+            "$expr": doc! {"$lt": [
+                doc! {"$size": "$users"},
+                doc! {"$ifNull": ["$lobby_settings.max_players", i32::MAX]},
+            ]},
+        };
+        let update = doc! {
+            "$addToSet": doc! {"users": &user},
+            "$pull": doc! {"spectators": &user},
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let game = self.col.find_one_and_update(filter, update, options).await;
+        info!("User added to game in DB");
+        game
+    }
+
+    /// Records an action of the host of a [Game] in the database.
+    pub async fn push_host_action(
+        &self,
+        id: ObjectId,
+        action: GameHostAction,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Saving host action in DB");
+        let update = doc! { "$push": doc! {"host_actions": to_bson(&action)?} };
+        let game = self.col.update_one(doc! {"_id": id}, update, None).await;
+        info!("Host action saved in DB");
+        game
+    }
+
+    /// Removes a player from a [Game] and prevents them from joining again.
+    pub async fn kick_user(
+        &self,
+        id: ObjectId,
+        action: GameHostAction,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Kicking user from game in DB");
+        let update = doc! {
+            "$pull": doc! {"users": &action.player, "ready_users": &action.player},
+            "$addToSet": doc! {"kicked_users": &action.player},
+            "$push": doc! {"host_actions": to_bson(&action)?},
+        };
+        let game = self.col.update_one(doc! {"_id": id}, update, None).await;
+        info!("User kicked from game in DB");
+        game
+    }
+
+    /// Hands the host role of a [Game] over to one of its players, the action may be performed by a moderator.
+    /// Nothing is updated if the host is not `current_host` anymore or if the new host left the game meanwhile.
+    pub async fn transfer_host(
+        &self,
+        id: ObjectId,
+        current_host: Option<String>,
+        action: GameHostAction,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Transferring game host in DB");
+        let filter = doc! {"_id": id, "creator": current_host, "users": &action.player};
+        let update = doc! {
+            "$set": doc! {"creator": &action.player},
+            "$push": doc! {"host_actions": to_bson(&action)?},
+        };
+        let game = self.col.update_one(filter, update, None).await;
+        info!("Game host transferred in DB");
+        game
+    }
+
+    /// Removes a player from a [Game], the host role is handed over to `next_creator` if any.
+    pub async fn remove_user(
+        &self,
+        id: ObjectId,
+        user: String,
+        next_creator: Option<String>,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Removing user from game in DB");
+        let mut update = doc! {
+            "$pull": doc! {"users": &user, "ready_users": &user},
+        };
+        if let Some(next_creator) = next_creator {
+            update.insert("$set", doc! {"creator": next_creator});
+        }
+        let game = self.col.update_one(doc! {"_id": id}, update, None).await;
+        info!("User removed from game in DB");
+        game
+    }
+
+    /// Marks a player of a [Game] as ready or not.
+    /// Nothing is updated if the user is not a player of the game.
+    pub async fn set_user_ready(
+        &self,
+        id: ObjectId,
+        user: String,
+        ready: bool,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Updating user readiness in DB");
+        let filter = doc! {"_id": id, "users": &user};
+        let update = if ready {
+            doc! { "$addToSet": doc! {"ready_users": &user} }
+        } else {
+            doc! { "$pull": doc! {"ready_users": &user} }
+        };
+        let game = self.col.update_one(filter, update, None).await;
+        info!("User readiness updated in DB");
+        game
+    }
+
+    /// Gets a [Game] by join code from the database.
+    pub async fn get_game_by_join_code(
+        &self,
+        code: String,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Getting game by join code from DB");
+        let game = self.col.find_one(doc! {"join_code": code}, None).await;
+        info!("Game retrieved by join code from DB");
+        game
+    }
+
+    /// Gets a [Game] by spectate code from the database.
+    pub async fn get_game_by_spectate_code(
+        &self,
+        code: String,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Getting game by spectate code from DB");
+        let game = self.col.find_one(doc! {"spectate_code": code}, None).await;
+        info!("Game retrieved by spectate code from DB");
+        game
+    }
+
+    /// Adds a spectator to a [Game] and returns the updated game.
+    /// The spectator is added only if the game is not over and if they neither play nor were kicked from it,
+    /// otherwise none is returned.
+    pub async fn add_spectator(
+        &self,
+        id: ObjectId,
+        user: String,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Adding spectator to game in DB");
+        let watchable = [
+            GameStatus::Lobby,
+            GameStatus::Countdown,
+            GameStatus::Running,
+            GameStatus::Paused,
+        ];
+        let filter = doc! {
+            "_id": id,
+            "status": doc! {"$in": watchable.iter().map(|status| status.as_str()).collect::<Vec<_>>()},
+            "users": doc! {"$ne": &user},
+            "kicked_users": doc! {"$ne": &user},
+        };
+        let update = doc! { "$addToSet": doc! {"spectators": &user} };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let game = self.col.find_one_and_update(filter, update, options).await;
+        info!("Spectator added to game in DB");
+        game
+    }
+
+    /// Removes a spectator from a [Game] and returns the updated game, or none if the game does not exist.
+    pub async fn remove_spectator(
+        &self,
+        id: ObjectId,
+        user: String,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Removing spectator from game in DB");
+        let update = doc! { "$pull": doc! {"spectators": &user} };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let game = self
+            .col
+            .find_one_and_update(doc! {"_id": id}, update, options)
+            .await;
+        info!("Spectator removed from game in DB");
+        game
+    }
+
+    /// Sets the join code of a [Game], the game cannot be joined by code anymore when `code` is none.
+    pub async fn set_join_code(
+        &self,
+        id: ObjectId,
+        code: Option<String>,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Saving game join code in DB");
+        let update = doc! { "$set": doc! {"join_code": code} };
         let game = self.col.update_one(doc! {"_id": id}, update, None).await;
-        info!("Game patched from DB");
+        info!("Game join code saved in DB");
+        game
+    }
+
+    /// Moves every trace of a player in the [Game]s, including the host action history, to another player name.
+    pub async fn rename_user(&self, from: String, to: String) -> mongodb::error::Result<u64> {
+        debug!("Renaming user in games in DB");
+        let filter = doc! {"$or": [
+            doc! {"users": &from},
+            doc! {"kicked_users": &from},
+            doc! {"spectators": &from},
+            doc! {"creator": &from},
+            doc! {"host_actions.host": &from},
+            doc! {"host_actions.player": &from},
+            doc! {"eliminations.user": &from},
+            doc! {"damages.user": &from},
+            doc! {"damages.from": &from},
+            doc! {"buzzes.user": &from},
+            doc! {"used_power_ups.user": &from},
+            doc! {"used_power_ups.target": &from},
+            doc! {"revealed_hints.user": &from},
+            doc! {"teams.users": &from},
+        ]};
+        let rename = |value: &str| doc! {"$cond": [doc! {"$eq": [value, &from]}, &to, value]};
+        let rename_all = |field: &str| {
+            doc! {"$map": doc! {
+                "input": doc! {"$ifNull": [field, []]},
+                "in": rename("$$this"),
+            }}
+        };
+        let pipeline = vec![doc! {"$set": doc! {
+            "creator": rename("$creator"),
+            "users": rename_all("$users"),
+            "ready_users": rename_all("$ready_users"),
+            "kicked_users": rename_all("$kicked_users"),
+            "spectators": rename_all("$spectators"),
+            "host_actions": doc! {"$map": doc! {
+                "input": doc! {"$ifNull": ["$host_actions", []]},
+                "in": doc! {"$mergeObjects": [
+                    "$$this",
+                    doc! {"host": rename("$$this.host"), "player": rename("$$this.player")},
+                ]},
+            }},
+            "standings": doc! {"$map": doc! {
+                "input": doc! {"$ifNull": ["$standings", []]},
+                "in": doc! {"$mergeObjects": ["$$this", doc! {"user": rename("$$this.user")}]},
+            }},
+            "eliminations": doc! {"$map": doc! {
+                "input": doc! {"$ifNull": ["$eliminations", []]},
+                "in": doc! {"$mergeObjects": ["$$this", doc! {"user": rename("$$this.user")}]},
+            }},
+            "damages": doc! {"$map": doc! {
+                "input": doc! {"$ifNull": ["$damages", []]},
+                "in": doc! {"$mergeObjects": [
+                    "$$this",
+                    doc! {"user": rename("$$this.user"), "from": rename("$$this.from")},
+                ]},
+            }},
+            "buzzes": doc! {"$map": doc! {
+                "input": doc! {"$ifNull": ["$buzzes", []]},
+                "in": doc! {"$mergeObjects": ["$$this", doc! {"user": rename("$$this.user")}]},
+            }},
+            "used_power_ups": doc! {"$map": doc! {
+                "input": doc! {"$ifNull": ["$used_power_ups", []]},
+                "in": doc! {"$mergeObjects": [
+                    "$$this",
+                    doc! {"user": rename("$$this.user"), "target": rename("$$this.target")},
+                ]},
+            }},
+            "revealed_hints": doc! {"$map": doc! {
+                "input": doc! {"$ifNull": ["$revealed_hints", []]},
+                "in": doc! {"$mergeObjects": ["$$this", doc! {"user": rename("$$this.user")}]},
+            }},
+            "teams": doc! {"$map": doc! {
+                "input": doc! {"$ifNull": ["$teams", []]},
+                "in": doc! {"$mergeObjects": ["$$this", doc! {"users": rename_all("$$this.users")}]},
+            }},
+        }}];
+        let result = self.col.update_many(filter, pipeline, None).await?;
+        info!("User renamed in games in DB");
+        Ok(result.modified_count)
+    }
+
+    /// Records the knock out of players from a [Game] in the database and returns the updated game.
+    pub async fn push_eliminations(
+        &self,
+        id: ObjectId,
+        eliminations: Vec<GameElimination>,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Saving game eliminations in DB");
+        let update =
+            doc! { "$push": doc! {"eliminations": doc! {"$each": to_bson(&eliminations)?}} };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let game = self
+            .col
+            .find_one_and_update(doc! {"_id": id}, update, options)
+            .await;
+        info!("Game eliminations saved in DB");
         game
     }
 
-    /// Saves a [Game] in the database.
-    pub async fn save_users_in_game(&self, new_game: Game) -> mongodb::error::Result<UpdateResult> {
-        debug!("Saving game in DB");
-        let update = doc! { "$set": doc! {"users": new_game.users} };
+    /// Records the damages dealt in a duel in the database and returns the updated game.
+    pub async fn push_damages(
+        &self,
+        id: ObjectId,
+        damages: Vec<GameDamage>,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Saving game damages in DB");
+        let update = doc! { "$push": doc! {"damages": doc! {"$each": to_bson(&damages)?}} };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
         let game = self
             .col
-            .update_one(doc! {"_id": new_game.id}, update, None)
+            .find_one_and_update(doc! {"_id": id}, update, options)
             .await;
-        info!("Game saved in DB");
+        info!("Game damages saved in DB");
+        game
+    }
+
+    /// Records the answer of a player of a buzzer [Game] in the database and returns the updated game.
+    /// Answers are kept in the order they are received, the update being atomic.
+    /// The answer is recorded only if the game is running, if the player plays it, if they did not answer the question yet
+    /// and if nobody answered it correctly, otherwise none is returned.
+    pub async fn push_buzz(
+        &self,
+        id: ObjectId,
+        buzz: GameBuzz,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Saving game buzz in DB");
+        let filter = doc! {
+            "_id": id,
+            "status": GameStatus::Running.as_str(),
+            "users": &buzz.user,
+            "buzzes": doc! {"$not": doc! {"$elemMatch": doc! {
+                "question_index": i32::from(buzz.question_index),
+                "$or": [doc! {"user": &buzz.user}, doc! {"correct": true}],
+            }}},
+        };
+        let update = doc! { "$push": doc! {"buzzes": to_bson(&buzz)?} };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let game = self.col.find_one_and_update(filter, update, options).await;
+        info!("Game buzz saved in DB");
         game
     }
+
+    /// Records the use of a power-up by a player of a [Game] in the database and returns the updated game.
+    /// The use is recorded only if the game is running and if the player did not use a power-up on the question yet,
+    /// the update being atomic, otherwise none is returned.
+    pub async fn push_power_up(
+        &self,
+        id: ObjectId,
+        power_up: GamePowerUp,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Saving game power-up in DB");
+        let filter = doc! {
+            "_id": id,
+            "status": GameStatus::Running.as_str(),
+            "used_power_ups": doc! {"$not": doc! {"$elemMatch": doc! {
+                "question_index": i32::from(power_up.question_index),
+                "user": &power_up.user,
+            }}},
+        };
+        let update = doc! { "$push": doc! {"used_power_ups": to_bson(&power_up)?} };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let game = self.col.find_one_and_update(filter, update, options).await;
+        info!("Game power-up saved in DB");
+        game
+    }
+
+    /// Records a hint revealed to a player of a [Game] in the database and returns the updated game.
+    /// The hint is recorded only if the game is running and if it was not revealed to the player yet,
+    /// the update being atomic, otherwise none is returned.
+    pub async fn push_hint(
+        &self,
+        id: ObjectId,
+        hint: GameHint,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Saving game hint in DB");
+        let filter = doc! {
+            "_id": id,
+            "status": GameStatus::Running.as_str(),
+            "revealed_hints": doc! {"$not": doc! {"$elemMatch": doc! {
+                "question_index": i32::from(hint.question_index),
+                "hint_index": hint.hint_index,
+                "user": &hint.user,
+            }}},
+        };
+        let update = doc! { "$push": doc! {"revealed_hints": to_bson(&hint)?} };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let game = self.col.find_one_and_update(filter, update, options).await;
+        info!("Game hint saved in DB");
+        game
+    }
+
+    /// Moves a player of a [Game] to a team, out of the team they were in.
+    /// The player is moved only if the game is in the lobby, if they are part of it and if the team exists.
+    /// Returns the updated game, or none if the player could not be moved.
+    pub async fn set_user_team(
+        &self,
+        id: ObjectId,
+        user: String,
+        team: String,
+    ) -> mongodb::error::Result<Option<Game>> {
+        debug!("Saving user team in DB");
+        let filter = doc! {
+            "_id": id,
+            "status": GameStatus::Lobby.as_str(),
+            "users": &user,
+            "teams.name": &team,
+        };
+        let others = doc! {"$filter": doc! {
+            "input": "$$this.users",
+            "as": "user",
+            "cond": doc! {"$ne": ["$$user", &user]},
+        }};
+        let pipeline = vec![doc! {"$set": doc! {
+            "teams": doc! {"$map": doc! {
+                "input": "$teams",
+                "in": doc! {"$mergeObjects": ["$$this", doc! {"users": doc! {"$cond": [
+                    doc! {"$eq": ["$$this.name", &team]},
+                    doc! {"$concatArrays": [others.clone(), [&user]]},
+                    others,
+                ]}}]},
+            }},
+        }}];
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let game = self
+            .col
+            .find_one_and_update(filter, pipeline, options)
+            .await;
+        info!("User team saved in DB");
+        game
+    }
+
+    /// Removes a player from the teams of a [Game].
+    pub async fn remove_team_user(
+        &self,
+        id: ObjectId,
+        user: String,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Removing user from game teams in DB");
+        let update = doc! { "$pull": doc! {"teams.$[].users": &user} };
+        let game = self.col.update_one(doc! {"_id": id}, update, None).await;
+        info!("User removed from game teams in DB");
+        game
+    }
+
+    /// Saves the final standings of a [Game] in the database, with the standings of its teams.
+    pub async fn set_standings(
+        &self,
+        id: ObjectId,
+        standings: Vec<GameStanding>,
+        team_standings: Vec<TeamStanding>,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Saving game standings in DB");
+        let update = doc! { "$set": doc! {
+            "standings": to_bson(&standings)?,
+            "team_standings": to_bson(&team_standings)?,
+        }};
+        let game = self.col.update_one(doc! {"_id": id}, update, None).await;
+        info!("Game standings saved in DB");
+        game
+    }
+
+    /// Computes the finished game statistics of a player: games played, wins,
+    /// their standing in the `recent` last finished games, and their best score in survival games of each topic.
+    pub async fn aggregate_player_games(
+        &self,
+        user: String,
+        recent: i64,
+    ) -> mongodb::error::Result<Option<Document>> {
+        debug!("Aggregating player games in DB");
+        let standing = doc! {"$first": doc! {"$filter": doc! {
+            "input": "$standings",
+            "cond": doc! {"$eq": ["$$this.user", &user]},
+        }}};
+        let pipeline = vec![
+            doc! {"$match": doc! {"status": GameStatus::Finished.as_str(), "standings.user": &user}},
+            doc! {"$facet": doc! {
+                "played": [doc! {"$count": "count"}],
+                "wins": [
+                    doc! {"$match": doc! {"standings": doc! {"$elemMatch": {"user": &user, "rank": 1}}}},
+                    doc! {"$count": "count"},
+                ],
+                "recent": [
+                    doc! {"$sort": doc! {"status_changed_at": -1}},
+                    doc! {"$limit": recent},
+                    doc! {"$set": doc! {"standing": standing.clone()}},
+                    doc! {"$project": doc! {
+                        "_id": 0,
+                        "game_id": doc! {"$toString": "$_id"},
+                        "topics": 1,
+                        "score": "$standing.score",
+                        "rank": "$standing.rank",
+                        "players": doc! {"$size": "$standings"},
+                        "finished_at": "$status_changed_at",
+                    }},
+                ],
+                "survival_bests": [
+                    doc! {"$match": doc! {"mode": "survival"}},
+                    doc! {"$set": doc! {"standing": standing}},
+                    doc! {"$unwind": "$topics"},
+                    doc! {"$sort": doc! {"standing.score": -1, "status_changed_at": 1}},
+                    doc! {"$group": doc! {
+                        "_id": "$topics",
+                        "score": doc! {"$first": "$standing.score"},
+                        "game_id": doc! {"$first": doc! {"$toString": "$_id"}},
+                        "achieved_at": doc! {"$first": "$status_changed_at"},
+                    }},
+                    doc! {"$project": doc! {
+                        "_id": 0,
+                        "topic": "$_id",
+                        "score": 1,
+                        "game_id": 1,
+                        "achieved_at": 1,
+                    }},
+                    doc! {"$sort": doc! {"topic": 1}},
+                ],
+            }},
+        ];
+        let mut games = self.col.aggregate(pipeline, None).await?;
+        let games = games.try_next().await;
+        debug!("Player games aggregated in DB");
+        games
+    }
 }
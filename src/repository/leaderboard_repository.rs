@@ -0,0 +1,155 @@
+use mongodb::bson::doc;
+use mongodb::options::{ClientOptions, FindOptions, IndexOptions, UpdateOptions};
+use mongodb::results::{CreateIndexesResult, UpdateResult};
+use mongodb::{Client, Collection, Cursor, IndexModel};
+use std::env;
+extern crate dotenv;
+use crate::model::leaderboard::LeaderboardEntry;
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const LEADERBOARD_ENTRY: &str = "LeaderboardEntry";
+
+/// Repository for [LeaderboardEntry] object to interact with the database
+pub struct LeaderboardRepo {
+    col: Collection<LeaderboardEntry>,
+}
+
+impl LeaderboardRepo {
+    /// Creates a new instance of [LeaderboardRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<LeaderboardEntry> = db.collection(LEADERBOARD_ENTRY);
+        debug!("DB client created");
+        LeaderboardRepo { col }
+    }
+
+    /// Creates the unique index on seasons, topics and players, so a player has one entry per leaderboard,
+    /// and the index on scores used to page through the leaderboards.
+    pub async fn create_indexes(&self) -> mongodb::error::Result<CreateIndexesResult> {
+        debug!("Creating leaderboard indexes in DB");
+        let player = IndexModel::builder()
+            .keys(doc! {"season": 1, "topic": 1, "user": 1})
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        let ranking = IndexModel::builder()
+            .keys(doc! {"season": 1, "topic": 1, "score": -1, "user": 1})
+            .build();
+        let created = self.col.create_indexes(vec![player, ranking], None).await;
+        info!("Leaderboard indexes created in DB");
+        created
+    }
+
+    /// Adds the results of a player in a finished game to their entry in a leaderboard, creating it if needed.
+    /// `results` holds the leaderboard, the player and the amounts to add.
+    pub async fn add_results(
+        &self,
+        results: LeaderboardEntry,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Adding results to leaderboard in DB");
+        let filter = doc! {"season": results.season, "topic": results.topic, "user": results.user};
+        let update = doc! {
+            "$inc": doc! {
+                "score": results.score,
+                "answers": results.answers,
+                "games": results.games,
+                "wins": results.wins,
+            },
+            "$set": doc! {"updated_at": results.updated_at},
+        };
+        let options = UpdateOptions::builder().upsert(true).build();
+        let entry = self.col.update_one(filter, update, options).await;
+        info!("Results added to leaderboard in DB");
+        entry
+    }
+
+    /// Gets a page of the [LeaderboardEntry]s of a leaderboard from the database, best score first.
+    pub async fn get_entries(
+        &self,
+        season: String,
+        topic: String,
+        skip: u64,
+        limit: i64,
+    ) -> mongodb::error::Result<Cursor<LeaderboardEntry>> {
+        debug!("Getting leaderboard entries from DB");
+        let options = FindOptions::builder()
+            .sort(doc! {"score": -1, "user": 1})
+            .skip(skip)
+            .limit(limit)
+            .build();
+        let entries = self
+            .col
+            .find(doc! {"season": season, "topic": topic}, options)
+            .await;
+        info!("Leaderboard entries retrieved from DB");
+        entries
+    }
+
+    /// Gets the [LeaderboardEntry] of a player in a leaderboard from the database.
+    pub async fn get_entry(
+        &self,
+        season: String,
+        topic: String,
+        user: String,
+    ) -> mongodb::error::Result<Option<LeaderboardEntry>> {
+        debug!("Getting leaderboard entry from DB");
+        let entry = self
+            .col
+            .find_one(doc! {"season": season, "topic": topic, "user": user}, None)
+            .await;
+        info!("Leaderboard entry retrieved from DB");
+        entry
+    }
+
+    /// Counts the players of a leaderboard having a better score than the given one.
+    pub async fn count_ahead(
+        &self,
+        season: String,
+        topic: String,
+        score: i32,
+    ) -> mongodb::error::Result<u64> {
+        debug!("Counting leaderboard entries ahead in DB");
+        let filter = doc! {"season": season, "topic": topic, "score": doc! {"$gt": score}};
+        let count = self.col.count_documents(filter, None).await;
+        info!("Leaderboard entries ahead counted in DB");
+        count
+    }
+
+    /// Tells whether no game was ever recorded in the leaderboards.
+    pub async fn is_empty(&self) -> mongodb::error::Result<bool> {
+        debug!("Checking leaderboard entries in DB");
+        let entry = self.col.find_one(None, None).await?;
+        info!("Leaderboard entries checked in DB");
+        Ok(entry.is_none())
+    }
+
+    /// Moves all the [LeaderboardEntry]s of a player to another player name.
+    pub async fn rename_user(
+        &self,
+        from: String,
+        to: String,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Renaming user of leaderboard entries in DB");
+        let update = doc! { "$set": doc! {"user": to} };
+        let entries = self
+            .col
+            .update_many(doc! {"user": from}, update, None)
+            .await;
+        info!("User of leaderboard entries renamed in DB");
+        entries
+    }
+}
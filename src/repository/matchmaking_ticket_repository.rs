@@ -0,0 +1,186 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, DateTime};
+use mongodb::options::{
+    ClientOptions, FindOneAndUpdateOptions, FindOptions, IndexOptions, ReturnDocument,
+};
+use mongodb::results::{CreateIndexesResult, InsertOneResult, UpdateResult};
+use mongodb::{Client, Collection, Cursor, IndexModel};
+use std::env;
+extern crate dotenv;
+use crate::model::matchmaking::{MatchmakingTicket, TicketStatus};
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const MATCHMAKING_TICKET: &str = "MatchmakingTicket";
+
+/// Repository for [MatchmakingTicket] object to interact with the database
+pub struct MatchmakingTicketRepo {
+    col: Collection<MatchmakingTicket>,
+}
+
+impl MatchmakingTicketRepo {
+    /// Creates a new instance of [MatchmakingTicketRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<MatchmakingTicket> = db.collection(MATCHMAKING_TICKET);
+        debug!("DB client created");
+        MatchmakingTicketRepo { col }
+    }
+
+    /// Creates the unique index on the players of waiting tickets, so a player waits in the queue only once,
+    /// and the index used to get the queue.
+    pub async fn create_indexes(&self) -> mongodb::error::Result<CreateIndexesResult> {
+        debug!("Creating matchmaking ticket indexes in DB");
+        let waiting_user = IndexModel::builder()
+            .keys(doc! {"user": 1})
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .partial_filter_expression(doc! {"status": TicketStatus::Waiting.as_str()})
+                    .build(),
+            )
+            .build();
+        let queue = IndexModel::builder()
+            .keys(doc! {"status": 1, "created_at": 1})
+            .build();
+        let created = self
+            .col
+            .create_indexes(vec![waiting_user, queue], None)
+            .await;
+        info!("Matchmaking ticket indexes created in DB");
+        created
+    }
+
+    /// Creates a new [MatchmakingTicket] in the database.
+    /// Returns an error if the player is already waiting in the queue.
+    pub async fn create_ticket(
+        &self,
+        ticket: MatchmakingTicket,
+    ) -> mongodb::error::Result<InsertOneResult> {
+        debug!("Creating matchmaking ticket in DB");
+        let ticket_created = self.col.insert_one(ticket, None).await;
+        info!("Matchmaking ticket created in DB");
+        ticket_created
+    }
+
+    /// Gets a [MatchmakingTicket] by id from the database.
+    pub async fn get_ticket(
+        &self,
+        id: ObjectId,
+    ) -> mongodb::error::Result<Option<MatchmakingTicket>> {
+        debug!("Getting matchmaking ticket from DB");
+        let ticket = self.col.find_one(doc! {"_id": id}, None).await;
+        info!("Matchmaking ticket retrieved from DB");
+        ticket
+    }
+
+    /// Gets the waiting [MatchmakingTicket]s from the database, oldest first.
+    pub async fn get_waiting_tickets(&self) -> mongodb::error::Result<Cursor<MatchmakingTicket>> {
+        debug!("Getting waiting matchmaking tickets from DB");
+        let options = FindOptions::builder().sort(doc! {"created_at": 1}).build();
+        let tickets = self
+            .col
+            .find(doc! {"status": TicketStatus::Waiting.as_str()}, options)
+            .await;
+        info!("Waiting matchmaking tickets retrieved from DB");
+        tickets
+    }
+
+    /// Cancels a waiting [MatchmakingTicket] of a player in the database and returns it.
+    /// Returns none if the player has no such waiting ticket.
+    pub async fn cancel_ticket(
+        &self,
+        id: ObjectId,
+        user: String,
+    ) -> mongodb::error::Result<Option<MatchmakingTicket>> {
+        debug!("Cancelling matchmaking ticket in DB");
+        let filter = doc! {"_id": id, "user": user, "status": TicketStatus::Waiting.as_str()};
+        let update = doc! { "$set": doc! {
+            "status": TicketStatus::Cancelled.as_str(),
+            "status_changed_at": DateTime::now(),
+        }};
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let ticket = self.col.find_one_and_update(filter, update, options).await;
+        info!("Matchmaking ticket cancelled in DB");
+        ticket
+    }
+
+    /// Expires the [MatchmakingTicket]s waiting since before the given date.
+    pub async fn expire_tickets(&self, before: DateTime) -> mongodb::error::Result<UpdateResult> {
+        debug!("Expiring matchmaking tickets in DB");
+        let filter = doc! {
+            "status": TicketStatus::Waiting.as_str(),
+            "created_at": doc! {"$lt": before},
+        };
+        let update = doc! { "$set": doc! {
+            "status": TicketStatus::Expired.as_str(),
+            "status_changed_at": DateTime::now(),
+        }};
+        let tickets = self.col.update_many(filter, update, None).await;
+        info!("Matchmaking tickets expired in DB");
+        tickets
+    }
+
+    /// Marks the given [MatchmakingTicket]s as matched, only the ones still waiting are changed.
+    pub async fn claim_tickets(&self, ids: Vec<ObjectId>) -> mongodb::error::Result<UpdateResult> {
+        debug!("Claiming matchmaking tickets in DB");
+        let filter = doc! {"_id": doc! {"$in": ids}, "status": TicketStatus::Waiting.as_str()};
+        let update = doc! { "$set": doc! {
+            "status": TicketStatus::Matched.as_str(),
+            "status_changed_at": DateTime::now(),
+        }};
+        let tickets = self.col.update_many(filter, update, None).await;
+        info!("Matchmaking tickets claimed in DB");
+        tickets
+    }
+
+    /// Puts back in the queue the given matched [MatchmakingTicket]s which did not get a game.
+    pub async fn release_tickets(
+        &self,
+        ids: Vec<ObjectId>,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Releasing matchmaking tickets in DB");
+        let filter = doc! {
+            "_id": doc! {"$in": ids},
+            "status": TicketStatus::Matched.as_str(),
+            "game_id": null,
+        };
+        let update = doc! { "$set": doc! {
+            "status": TicketStatus::Waiting.as_str(),
+            "status_changed_at": DateTime::now(),
+        }};
+        let tickets = self.col.update_many(filter, update, None).await;
+        info!("Matchmaking tickets released in DB");
+        tickets
+    }
+
+    /// Gives the game created for the given matched [MatchmakingTicket]s.
+    pub async fn set_game(
+        &self,
+        ids: Vec<ObjectId>,
+        game_id: String,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Saving game of matchmaking tickets in DB");
+        let filter = doc! {"_id": doc! {"$in": ids}};
+        let update = doc! { "$set": doc! {"game_id": game_id} };
+        let tickets = self.col.update_many(filter, update, None).await;
+        info!("Game of matchmaking tickets saved in DB");
+        tickets
+    }
+}
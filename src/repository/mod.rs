@@ -1,3 +1,15 @@
+pub mod api_key_repository;
+pub mod daily_attempt_repository;
+pub mod daily_challenge_repository;
 pub mod game_answer_repository;
 pub mod game_progress_repository;
 pub mod game_repository;
+pub mod leaderboard_repository;
+pub mod matchmaking_ticket_repository;
+pub mod oidc_login_repository;
+pub mod player_profile_repository;
+pub mod player_rating_repository;
+pub mod practice_session_repository;
+pub mod question_repository;
+pub mod rating_history_repository;
+pub mod user_repository;
@@ -0,0 +1,80 @@
+use mongodb::bson::doc;
+use mongodb::options::{ClientOptions, IndexOptions};
+use mongodb::results::{CreateIndexResult, InsertOneResult};
+use mongodb::{Client, Collection, IndexModel};
+use std::env;
+use std::time::Duration;
+extern crate dotenv;
+use crate::model::oidc_login::OidcLogin;
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const OIDC_LOGIN: &str = "OidcLogin";
+
+/// Time left to the user to log in at the provider, pending logins are dropped afterwards.
+pub const OIDC_LOGIN_DURATION: Duration = Duration::from_secs(10 * 60);
+
+/// Repository for [OidcLogin] object to interact with the database
+pub struct OidcLoginRepo {
+    col: Collection<OidcLogin>,
+}
+
+impl OidcLoginRepo {
+    /// Creates a new instance of [OidcLoginRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<OidcLogin> = db.collection(OIDC_LOGIN);
+        debug!("DB client created");
+        OidcLoginRepo { col }
+    }
+
+    /// Creates the index expiring pending logins after [OIDC_LOGIN_DURATION].
+    pub async fn create_indexes(&self) -> mongodb::error::Result<CreateIndexResult> {
+        debug!("Creating OIDC login indexes in DB");
+        let index = IndexModel::builder()
+            .keys(doc! {"created_at": 1})
+            .options(
+                IndexOptions::builder()
+                    .expire_after(OIDC_LOGIN_DURATION)
+                    .build(),
+            )
+            .build();
+        let created = self.col.create_index(index, None).await;
+        info!("OIDC login indexes created in DB");
+        created
+    }
+
+    /// Saves a pending [OidcLogin] in the database.
+    pub async fn create_login(&self, login: OidcLogin) -> mongodb::error::Result<InsertOneResult> {
+        debug!("Creating OIDC login in DB");
+        let login_created = self.col.insert_one(login, None).await;
+        info!("OIDC login created in DB");
+        login_created
+    }
+
+    /// Removes the pending [OidcLogin] of the given state from the database and returns it.
+    /// A state can only be used once.
+    pub async fn take_login(&self, state: String) -> mongodb::error::Result<Option<OidcLogin>> {
+        debug!("Taking OIDC login from DB");
+        let login = self
+            .col
+            .find_one_and_delete(doc! {"state": state}, None)
+            .await;
+        info!("OIDC login taken from DB");
+        login
+    }
+}
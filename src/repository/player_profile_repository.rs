@@ -0,0 +1,63 @@
+use mongodb::bson::doc;
+use mongodb::options::{ClientOptions, ReplaceOptions};
+use mongodb::results::UpdateResult;
+use mongodb::{Client, Collection};
+use std::env;
+extern crate dotenv;
+use crate::model::profile::PlayerProfile;
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const PLAYER_PROFILE: &str = "PlayerProfile";
+
+/// Repository for [PlayerProfile] object to interact with the database
+pub struct PlayerProfileRepo {
+    col: Collection<PlayerProfile>,
+}
+
+impl PlayerProfileRepo {
+    /// Creates a new instance of [PlayerProfileRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<PlayerProfile> = db.collection(PLAYER_PROFILE);
+        debug!("DB client created");
+        PlayerProfileRepo { col }
+    }
+
+    /// Saves a [PlayerProfile] in the database, replacing the previous one of the player.
+    pub async fn save_profile(
+        &self,
+        profile: PlayerProfile,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Saving player profile in DB");
+        let options = ReplaceOptions::builder().upsert(true).build();
+        let profile_saved = self
+            .col
+            .replace_one(doc! {"_id": &profile.user}, &profile, options)
+            .await;
+        info!("Player profile saved in DB");
+        profile_saved
+    }
+
+    /// Gets the [PlayerProfile] of a player from the database.
+    pub async fn get_profile(&self, user: String) -> mongodb::error::Result<Option<PlayerProfile>> {
+        debug!("Getting player profile from DB");
+        let profile = self.col.find_one(doc! {"_id": user}, None).await;
+        info!("Player profile retrieved from DB");
+        profile
+    }
+}
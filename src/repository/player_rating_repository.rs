@@ -0,0 +1,141 @@
+use mongodb::bson::{doc, DateTime};
+use mongodb::options::{ClientOptions, FindOptions, IndexOptions, UpdateOptions};
+use mongodb::results::{CreateIndexesResult, UpdateResult};
+use mongodb::{Client, Collection, Cursor, IndexModel};
+use std::env;
+extern crate dotenv;
+use crate::model::rating::PlayerRating;
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const PLAYER_RATING: &str = "PlayerRating";
+
+/// Repository for [PlayerRating] object to interact with the database
+pub struct PlayerRatingRepo {
+    col: Collection<PlayerRating>,
+}
+
+impl PlayerRatingRepo {
+    /// Creates a new instance of [PlayerRatingRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<PlayerRating> = db.collection(PLAYER_RATING);
+        debug!("DB client created");
+        PlayerRatingRepo { col }
+    }
+
+    /// Creates the unique index on players and topics, so a player has one rating per topic,
+    /// and the index on topics and ratings used by the leaderboards.
+    pub async fn create_indexes(&self) -> mongodb::error::Result<CreateIndexesResult> {
+        debug!("Creating player rating indexes in DB");
+        let player = IndexModel::builder()
+            .keys(doc! {"user": 1, "topic": 1})
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        let leaderboard = IndexModel::builder()
+            .keys(doc! {"topic": 1, "rating": -1})
+            .build();
+        let created = self
+            .col
+            .create_indexes(vec![player, leaderboard], None)
+            .await;
+        info!("Player rating indexes created in DB");
+        created
+    }
+
+    /// Gets the [PlayerRating]s of some players on a topic from the database.
+    /// Players who never played the topic have no rating.
+    pub async fn get_players_ratings(
+        &self,
+        topic: String,
+        users: Vec<String>,
+    ) -> mongodb::error::Result<Cursor<PlayerRating>> {
+        debug!("Getting player ratings from DB");
+        let filter = doc! {"topic": topic, "user": doc! {"$in": users}};
+        let ratings = self.col.find(filter, None).await;
+        info!("Player ratings retrieved from DB");
+        ratings
+    }
+
+    /// Gets the [PlayerRating]s of a player on every topic from the database, best first.
+    pub async fn get_user_ratings(
+        &self,
+        user: String,
+    ) -> mongodb::error::Result<Cursor<PlayerRating>> {
+        debug!("Getting user ratings from DB");
+        let options = FindOptions::builder().sort(doc! {"rating": -1}).build();
+        let ratings = self.col.find(doc! {"user": user}, options).await;
+        info!("User ratings retrieved from DB");
+        ratings
+    }
+
+    /// Gets a page of the [PlayerRating]s of a topic from the database, best first.
+    pub async fn get_topic_ratings(
+        &self,
+        topic: String,
+        skip: u64,
+        limit: i64,
+    ) -> mongodb::error::Result<Cursor<PlayerRating>> {
+        debug!("Getting topic ratings from DB");
+        let options = FindOptions::builder()
+            .sort(doc! {"rating": -1, "user": 1})
+            .skip(skip)
+            .limit(limit)
+            .build();
+        let ratings = self.col.find(doc! {"topic": topic}, options).await;
+        info!("Topic ratings retrieved from DB");
+        ratings
+    }
+
+    /// Saves the new rating of a player on a topic in the database, counting one more game.
+    pub async fn save_rating(
+        &self,
+        user: String,
+        topic: String,
+        rating: f64,
+        updated_at: DateTime,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Saving player rating in DB");
+        let update = doc! {
+            "$set": doc! {"rating": rating, "updated_at": updated_at},
+            "$inc": doc! {"games": 1},
+        };
+        let options = UpdateOptions::builder().upsert(true).build();
+        let rating_saved = self
+            .col
+            .update_one(doc! {"user": user, "topic": topic}, update, options)
+            .await;
+        info!("Player rating saved in DB");
+        rating_saved
+    }
+
+    /// Moves all the [PlayerRating]s of a player to another player name.
+    pub async fn rename_user(
+        &self,
+        from: String,
+        to: String,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Renaming user of ratings in DB");
+        let update = doc! { "$set": doc! {"user": to} };
+        let ratings = self
+            .col
+            .update_many(doc! {"user": from}, update, None)
+            .await;
+        info!("User of ratings renamed in DB");
+        ratings
+    }
+}
@@ -0,0 +1,104 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, DateTime};
+use mongodb::options::{ClientOptions, FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::results::{InsertOneResult, UpdateResult};
+use mongodb::{Client, Collection};
+use std::env;
+extern crate dotenv;
+use crate::model::practice::PracticeSession;
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const PRACTICE_SESSION: &str = "PracticeSession";
+
+/// Repository for [PracticeSession] object to interact with the database
+pub struct PracticeSessionRepo {
+    col: Collection<PracticeSession>,
+}
+
+impl PracticeSessionRepo {
+    /// Creates a new instance of [PracticeSessionRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<PracticeSession> = db.collection(PRACTICE_SESSION);
+        debug!("DB client created");
+        PracticeSessionRepo { col }
+    }
+
+    /// Creates a new [PracticeSession] in the database.
+    pub async fn create_session(
+        &self,
+        session: PracticeSession,
+    ) -> mongodb::error::Result<InsertOneResult> {
+        debug!("Creating practice session in DB");
+        let session_created = self.col.insert_one(session, None).await;
+        info!("Practice session created in DB");
+        session_created
+    }
+
+    /// Gets a [PracticeSession] by id from the database.
+    pub async fn get_session(
+        &self,
+        id: ObjectId,
+    ) -> mongodb::error::Result<Option<PracticeSession>> {
+        debug!("Getting practice session from DB");
+        let session = self.col.find_one(doc! {"_id": id}, None).await;
+        info!("Practice session retrieved from DB");
+        session
+    }
+
+    /// Moves a [PracticeSession] from the given question to the next one, finishing it after its last question,
+    /// and returns the updated session.
+    /// Returns none if the session is not on the given question anymore, so a question is answered only once.
+    pub async fn next_question(
+        &self,
+        id: ObjectId,
+        current_question: i8,
+        finished: bool,
+        now: DateTime,
+    ) -> mongodb::error::Result<Option<PracticeSession>> {
+        debug!("Moving practice session to next question in DB");
+        let filter =
+            doc! {"_id": id, "current_question": current_question as i32, "finished_at": null};
+        let update = doc! { "$set": doc! {
+            "current_question": current_question as i32 + 1,
+            "question_started_at": now,
+            "finished_at": if finished { Some(now) } else { None },
+        }};
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let session = self.col.find_one_and_update(filter, update, options).await;
+        info!("Practice session moved to next question in DB");
+        session
+    }
+
+    pub async fn rename_user(
+        &self,
+        from: String,
+        to: String,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Renaming user of practice sessions in DB");
+        let update = doc! { "$set": doc! {"user": to} };
+        let sessions = self
+            .col
+            .update_many(doc! {"user": from}, update, None)
+            .await;
+        info!("User of practice sessions renamed in DB");
+        sessions
+    }
+}
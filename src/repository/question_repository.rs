@@ -0,0 +1,109 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, Bson};
+use mongodb::options::ClientOptions;
+use mongodb::results::{DeleteResult, InsertOneResult};
+use mongodb::{Client, Collection, Cursor};
+use std::env;
+extern crate dotenv;
+use crate::model::question::AuthoredQuestion;
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const QUESTION: &str = "Question";
+
+/// Repository for [AuthoredQuestion] object to interact with the database
+pub struct QuestionRepo {
+    col: Collection<AuthoredQuestion>,
+}
+
+impl QuestionRepo {
+    /// Creates a new instance of [QuestionRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<AuthoredQuestion> = db.collection(QUESTION);
+        debug!("DB client created");
+        QuestionRepo { col }
+    }
+
+    /// Creates a new [AuthoredQuestion] in the database.
+    pub async fn create_question(
+        &self,
+        new_question: AuthoredQuestion,
+    ) -> mongodb::error::Result<InsertOneResult> {
+        debug!("Creating question in DB");
+        let question_created = self.col.insert_one(new_question, None).await;
+        info!("Question created in DB");
+        question_created
+    }
+
+    /// Gets the [AuthoredQuestion]s of the given author from the database, or all of them when no author is given.
+    pub async fn get_questions(
+        &self,
+        author: Option<String>,
+    ) -> mongodb::error::Result<Cursor<AuthoredQuestion>> {
+        debug!("Getting questions from DB");
+        let filter = author.map(|author| doc! {"author": author});
+        let questions = self.col.find(filter, None).await;
+        info!("Questions retrieved from DB");
+        questions
+    }
+
+    /// Gets the topics among the given ones having at least one [AuthoredQuestion] in the database.
+    pub async fn get_question_topics(
+        &self,
+        topics: Vec<String>,
+    ) -> mongodb::error::Result<Vec<Bson>> {
+        debug!("Getting question topics from DB");
+        let filter = doc! {"topic": doc! {"$in": topics}};
+        let question_topics = self.col.distinct("topic", filter, None).await;
+        info!("Question topics retrieved from DB");
+        question_topics
+    }
+
+    /// Gets the [AuthoredQuestion]s having one of the given topics from the database.
+    pub async fn get_questions_by_topics(
+        &self,
+        topics: Vec<String>,
+    ) -> mongodb::error::Result<Cursor<AuthoredQuestion>> {
+        debug!("Getting questions by topics from DB");
+        let questions = self
+            .col
+            .find(doc! {"topic": doc! {"$in": topics}}, None)
+            .await;
+        info!("Questions retrieved by topics from DB");
+        questions
+    }
+
+    /// Gets an [AuthoredQuestion] by id from the database.
+    pub async fn get_question(
+        &self,
+        id: ObjectId,
+    ) -> mongodb::error::Result<Option<AuthoredQuestion>> {
+        debug!("Getting question by id from DB");
+        let question = self.col.find_one(doc! {"_id": id}, None).await;
+        info!("Question retrieved by id from DB");
+        question
+    }
+
+    /// Deletes an [AuthoredQuestion] from the database.
+    pub async fn delete_question(&self, id: ObjectId) -> mongodb::error::Result<DeleteResult> {
+        debug!("Deleting question from DB");
+        let deleted = self.col.delete_one(doc! {"_id": id}, None).await;
+        info!("Question deleted from DB");
+        deleted
+    }
+}
@@ -0,0 +1,94 @@
+use mongodb::bson::doc;
+use mongodb::options::{ClientOptions, FindOptions};
+use mongodb::results::{CreateIndexesResult, InsertManyResult, UpdateResult};
+use mongodb::{Client, Collection, Cursor, IndexModel};
+use std::env;
+extern crate dotenv;
+use crate::model::rating::RatingChange;
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const RATING_HISTORY: &str = "RatingHistory";
+
+/// Repository for [RatingChange] object to interact with the database
+pub struct RatingHistoryRepo {
+    col: Collection<RatingChange>,
+}
+
+impl RatingHistoryRepo {
+    /// Creates a new instance of [RatingHistoryRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<RatingChange> = db.collection(RATING_HISTORY);
+        debug!("DB client created");
+        RatingHistoryRepo { col }
+    }
+
+    /// Creates the index used to get the history of a player on a topic.
+    pub async fn create_indexes(&self) -> mongodb::error::Result<CreateIndexesResult> {
+        debug!("Creating rating history indexes in DB");
+        let history = IndexModel::builder()
+            .keys(doc! {"user": 1, "topic": 1, "changed_at": 1})
+            .build();
+        let created = self.col.create_indexes(vec![history], None).await;
+        info!("Rating history indexes created in DB");
+        created
+    }
+
+    /// Saves the [RatingChange]s of a finished game in the database.
+    pub async fn save_changes(
+        &self,
+        changes: Vec<RatingChange>,
+    ) -> mongodb::error::Result<InsertManyResult> {
+        debug!("Saving rating changes in DB");
+        let changes_saved = self.col.insert_many(changes, None).await;
+        info!("Rating changes saved in DB");
+        changes_saved
+    }
+
+    /// Gets the [RatingChange]s of a player on a topic from the database, oldest first.
+    pub async fn get_history(
+        &self,
+        user: String,
+        topic: String,
+    ) -> mongodb::error::Result<Cursor<RatingChange>> {
+        debug!("Getting rating history from DB");
+        let options = FindOptions::builder().sort(doc! {"changed_at": 1}).build();
+        let history = self
+            .col
+            .find(doc! {"user": user, "topic": topic}, options)
+            .await;
+        info!("Rating history retrieved from DB");
+        history
+    }
+
+    /// Moves all the [RatingChange]s of a player to another player name.
+    pub async fn rename_user(
+        &self,
+        from: String,
+        to: String,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Renaming user of rating history in DB");
+        let update = doc! { "$set": doc! {"user": to} };
+        let history = self
+            .col
+            .update_many(doc! {"user": from}, update, None)
+            .await;
+        info!("User of rating history renamed in DB");
+        history
+    }
+}
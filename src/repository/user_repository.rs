@@ -0,0 +1,166 @@
+use mongodb::bson::{doc, to_bson, DateTime};
+use mongodb::options::{ClientOptions, FindOneAndUpdateOptions, IndexOptions, ReturnDocument};
+use mongodb::results::{CreateIndexesResult, InsertOneResult, UpdateResult};
+use mongodb::{Client, Collection, IndexModel};
+use std::env;
+extern crate dotenv;
+use crate::model::user::{User, UserRole};
+use dotenv::dotenv;
+use log::{debug, info};
+
+pub const MONGO_URI: &str = "MONGO_URI";
+pub const MONGO_DATABASE: &str = "CodingFighters";
+pub const USER: &str = "User";
+
+/// Repository for [User] object to interact with the database
+pub struct UserRepo {
+    col: Collection<User>,
+}
+
+impl UserRepo {
+    /// Creates a new instance of [UserRepo] with the collection to interact with the database
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let uri = match env::var(MONGO_URI) {
+            Ok(v) => v.to_string(),
+            Err(_) => "Error loading env variable".to_string(),
+        };
+        let client_options = ClientOptions::parse(uri)
+            .await
+            .expect("Failed to create client options");
+        debug!("Creating DB client");
+        let client = Client::with_options(client_options).expect("Failed to create mongo client");
+        debug!("Accessing DB");
+        let db = client.database(MONGO_DATABASE);
+        debug!("Accessing collection");
+        let col: Collection<User> = db.collection(USER);
+        debug!("DB client created");
+        UserRepo { col }
+    }
+
+    /// Creates the unique indexes on usernames and on provider identities,
+    /// so two users cannot register the same name or be linked to the same provider account,
+    /// and the index on the guests users were upgraded from.
+    pub async fn create_indexes(&self) -> mongodb::error::Result<CreateIndexesResult> {
+        debug!("Creating user indexes in DB");
+        let username = IndexModel::builder()
+            .keys(doc! {"username": 1})
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        let identity = IndexModel::builder()
+            .keys(doc! {"identities.issuer": 1, "identities.subject": 1})
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .partial_filter_expression(doc! {"identities.subject": doc! {"$exists": true}})
+                    .build(),
+            )
+            .build();
+        let upgraded_from = IndexModel::builder()
+            .keys(doc! {"upgraded_from": 1})
+            .options(
+                IndexOptions::builder()
+                    .partial_filter_expression(doc! {"upgraded_from": doc! {"$type": "string"}})
+                    .build(),
+            )
+            .build();
+        let created = self
+            .col
+            .create_indexes(vec![username, identity, upgraded_from], None)
+            .await;
+        info!("User indexes created in DB");
+        created
+    }
+
+    /// Creates a new [User] in the database.
+    /// Returns an error if the username is already taken.
+    pub async fn create_user(&self, new_user: User) -> mongodb::error::Result<InsertOneResult> {
+        debug!("Creating user in DB");
+        let user_created = self.col.insert_one(new_user, None).await;
+        info!("User created in DB");
+        user_created
+    }
+
+    /// Gets a [User] by username from the database.
+    pub async fn get_user_by_username(
+        &self,
+        username: String,
+    ) -> mongodb::error::Result<Option<User>> {
+        debug!("Getting user by username from DB");
+        let user = self.col.find_one(doc! {"username": username}, None).await;
+        info!("User retrieved by username from DB");
+        user
+    }
+
+    /// Gets the [User] owning the given identity at an OpenID Connect provider from the database.
+    pub async fn get_user_by_identity(
+        &self,
+        issuer: String,
+        subject: String,
+    ) -> mongodb::error::Result<Option<User>> {
+        debug!("Getting user by identity from DB");
+        let filter =
+            doc! {"identities": doc! {"$elemMatch": {"issuer": issuer, "subject": subject}}};
+        let user = self.col.find_one(filter, None).await;
+        info!("User retrieved by identity from DB");
+        user
+    }
+
+    /// Records in the database that the upgrade of a guest to a [User] is complete.
+    pub async fn complete_upgrade(
+        &self,
+        username: String,
+        upgraded_at: DateTime,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Completing guest upgrade in DB");
+        let update = doc! { "$set": doc! {"upgraded_at": upgraded_at} };
+        let user = self
+            .col
+            .update_one(doc! {"username": username}, update, None)
+            .await;
+        info!("Guest upgrade completed in DB");
+        user
+    }
+
+    /// Tells if a guest was upgraded to a [User] whose upgrade is complete.
+    pub async fn is_upgraded_guest(&self, guest: String) -> mongodb::error::Result<bool> {
+        debug!("Checking guest upgrade in DB");
+        let filter = doc! {"upgraded_from": guest, "upgraded_at": doc! {"$ne": null}};
+        let count = self.col.count_documents(filter, None).await;
+        info!("Guest upgrade checked in DB");
+        count.map(|count| count > 0)
+    }
+
+    /// Replaces the roles of a [User] in the database and returns the updated user.
+    pub async fn set_roles(
+        &self,
+        username: String,
+        roles: Vec<UserRole>,
+    ) -> mongodb::error::Result<Option<User>> {
+        debug!("Saving user roles in DB");
+        let update = doc! { "$set": doc! {"roles": to_bson(&roles)?} };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        let user = self
+            .col
+            .find_one_and_update(doc! {"username": username}, update, options)
+            .await;
+        info!("User roles saved in DB");
+        user
+    }
+
+    /// Gives a role to the [User]s having one of the given usernames.
+    pub async fn add_role(
+        &self,
+        usernames: Vec<String>,
+        role: UserRole,
+    ) -> mongodb::error::Result<UpdateResult> {
+        debug!("Adding role to users in DB");
+        let filter = doc! {"username": doc! {"$in": usernames}};
+        let update = doc! { "$addToSet": doc! {"roles": to_bson(&role)?} };
+        let users = self.col.update_many(filter, update, None).await;
+        info!("Role added to users in DB");
+        users
+    }
+}
@@ -0,0 +1,149 @@
+use crate::dto::api_key_dto::{ApiKeyDto, NewApiKeyDto};
+use crate::errors::api_key_service_error::{ApiKeyServiceError, ApiKeyServiceErrorKind};
+use crate::mapper::api_key_mapper;
+use crate::security::guard::AuthenticatedUser;
+use crate::service::api_key_service::ApiKeyService;
+use log::{debug, error};
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+
+/// POST request to create an API key for the authenticated user.
+/// The key is sent as `Authorization: Bearer <key>` header and only allows what its scopes cover.
+/// Returns the created key, the key itself is not given again afterwards.
+/// Returns an error if the name is not valid or if no scope is given.
+/// Returns an error if the user is a guest or authenticated with an API key.
+#[post("/api-keys", format = "json", data = "<new_api_key>")]
+pub async fn create_api_key(
+    new_api_key: Json<NewApiKeyDto>,
+    user: AuthenticatedUser,
+) -> Result<Json<ApiKeyDto>, Status> {
+    debug!("create_api_key resource started");
+    let api_key_service = ApiKeyService::init().await;
+    let new_api_key = new_api_key.into_inner();
+    let api_key_created = api_key_service
+        .create_api_key(&user, new_api_key.name, new_api_key.scopes)
+        .await;
+    let result = match api_key_created {
+        Ok((key, api_key)) => Ok(Json(api_key_mapper::to_created_dto(key, api_key))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("create_api_key resource ending");
+    result
+}
+
+/// GET request to get the API keys of the authenticated user, with their last use.
+/// Returns an error if the user is a guest or authenticated with an API key.
+#[get("/api-keys", format = "json")]
+pub async fn get_api_keys(user: AuthenticatedUser) -> Result<Json<Vec<ApiKeyDto>>, Status> {
+    debug!("get_api_keys resource started");
+    let api_key_service = ApiKeyService::init().await;
+    let result = match api_key_service.get_api_keys(&user).await {
+        Ok(api_keys) => Ok(Json(
+            api_keys.into_iter().map(api_key_mapper::to_dto).collect(),
+        )),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_api_keys resource ending");
+    result
+}
+
+/// DELETE request to revoke an API key of the authenticated user.
+/// Returns an error if the user has no such key or if it is already revoked.
+/// Returns an error if the user is a guest or authenticated with an API key.
+#[delete("/api-keys/<id>")]
+pub async fn revoke_api_key(id: String, user: AuthenticatedUser) -> Result<Status, Status> {
+    debug!("revoke_api_key resource started");
+    let api_key_service = ApiKeyService::init().await;
+    let result = match api_key_service.revoke_api_key(&user, id).await {
+        Ok(_) => Ok(Status::NoContent),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("revoke_api_key resource ending");
+    result
+}
+
+fn process_service_error(error: ApiKeyServiceError) -> Status {
+    error!("Error: {}", error.message);
+    match error.kind {
+        ApiKeyServiceErrorKind::NotFound => Status::NotFound,
+        ApiKeyServiceErrorKind::InvalidRequest => Status::BadRequest,
+        ApiKeyServiceErrorKind::Unauthorized => Status::Unauthorized,
+        ApiKeyServiceErrorKind::Forbidden => Status::Forbidden,
+        ApiKeyServiceErrorKind::Internal => Status::InternalServerError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::api_key_dto::NewApiKeyDto;
+    use crate::dto::user_dto::CredentialsDto;
+    use crate::model::api_key::ApiKeyScope;
+    use crate::resource::api_key_resource::{create_api_key, get_api_keys, revoke_api_key};
+    use crate::resource::test_fixtures::user;
+    use crate::resource::user_resource::register_user;
+    use crate::service::api_key_service::ApiKeyService;
+    use crate::service::policy::{is_allowed, Permission};
+    use log::info;
+    use rocket::async_test;
+    use rocket::http::Status;
+    use rocket::serde::json::Json;
+    use serial_test::serial;
+    use std::env;
+    use testcontainers::clients::Cli;
+    use testcontainers::GenericImage;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[async_test]
+    #[serial]
+    async fn api_keys_should_authenticate_their_owner_until_revoked() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let _ = register_user(Json(CredentialsDto {
+            username: "bob".to_string(),
+            password: "correct horse".to_string(),
+        }))
+        .await;
+        let new_api_key = || {
+            Json(NewApiKeyDto {
+                name: "quiz bot".to_string(),
+                scopes: vec![ApiKeyScope::GamesCreate],
+            })
+        };
+        let api_key = create_api_key(new_api_key(), user("bob"))
+            .await
+            .unwrap()
+            .into_inner();
+        let key = api_key.key.unwrap();
+        assert!(key.starts_with(&api_key.hint));
+
+        let api_key_service = ApiKeyService::init().await;
+        let caller = api_key_service.authenticate(&key).await.unwrap();
+        assert_eq!(caller.username, "bob");
+        assert!(is_allowed(&caller, Permission::CreateGames));
+        assert!(!is_allowed(&caller, Permission::HostGames));
+        let error = create_api_key(new_api_key(), caller).await.unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+
+        let api_keys = get_api_keys(user("bob")).await.unwrap().into_inner();
+        assert_eq!(api_keys.len(), 1);
+        assert!(api_keys[0].key.is_none());
+        assert!(api_keys[0].last_used_at.is_some());
+
+        let id = api_keys[0].id.clone().unwrap();
+        let error = revoke_api_key(id.clone(), user("alice")).await.unwrap_err();
+        assert_eq!(error, Status::NotFound);
+        let status = revoke_api_key(id, user("bob")).await.unwrap();
+        assert_eq!(status, Status::NoContent);
+        assert!(api_key_service.authenticate(&key).await.is_err());
+    }
+}
@@ -0,0 +1,348 @@
+use crate::dto::daily_challenge_dto::{
+    DailyAttemptDto, DailyChallengeDto, DailyFeedbackDto, DailyLeaderboardDto, DailyStreakDto,
+};
+use crate::errors::daily_challenge_service_error::{
+    DailyChallengeServiceError, DailyChallengeServiceErrorKind,
+};
+use crate::mapper::{daily_challenge_mapper, question_mapper};
+use crate::model::daily_challenge::{DailyChallenge, DAILY_CHALLENGE_TOPICS};
+use crate::resource::game_resource::question_pool;
+use crate::security::guard::{AuthenticatedUser, Player};
+use crate::service::daily_challenge_service::DailyChallengeService;
+use crate::service::rating_service::DEFAULT_PAGE_SIZE;
+use log::{debug, error};
+use mongodb::bson::DateTime;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{get, post};
+
+/// GET request to get the challenge of the day, the same questions for every player.
+/// When the request is authenticated, the attempt and the streaks of the player are given too.
+#[get("/daily", format = "json")]
+pub async fn get_daily_challenge(
+    user: Option<AuthenticatedUser>,
+) -> Result<Json<DailyChallengeDto>, Status> {
+    debug!("get_daily_challenge resource started");
+    let daily_challenge_service = DailyChallengeService::init().await;
+    let now = DateTime::now();
+    let challenge = today_challenge(&daily_challenge_service, now)
+        .await
+        .map_err(process_service_error)?;
+    let (attempt, streak) = match user {
+        Some(user) => {
+            let attempt = match daily_challenge_service
+                .get_attempt(challenge.day.clone(), user.username.clone())
+                .await
+            {
+                Ok((challenge, attempt)) => Some(daily_challenge_mapper::attempt_to_dto(
+                    &challenge, attempt, now,
+                )),
+                Err(DailyChallengeServiceError {
+                    kind: DailyChallengeServiceErrorKind::NotFound,
+                    ..
+                }) => None,
+                Err(err) => return Err(process_service_error(err)),
+            };
+            let streak = daily_challenge_service
+                .get_streak(user.username)
+                .await
+                .map_err(process_service_error)?;
+            (attempt, Some(streak))
+        }
+        None => (None, None),
+    };
+    debug!("get_daily_challenge resource ending");
+    Ok(Json(daily_challenge_mapper::to_dto(
+        &challenge, attempt, streak,
+    )))
+}
+
+/// POST request to start the attempt of the authenticated player at the challenge of the day, with its first question.
+/// Returns an error if the day is not the one of the challenge in progress.
+/// Returns an error if the player already attempted the challenge.
+#[post("/daily/<day>/attempt")]
+pub async fn start_daily_attempt(
+    day: String,
+    player: Player,
+) -> Result<Json<DailyAttemptDto>, Status> {
+    debug!("start_daily_attempt resource started");
+    let daily_challenge_service = DailyChallengeService::init().await;
+    let now = DateTime::now();
+    today_challenge(&daily_challenge_service, now)
+        .await
+        .map_err(process_service_error)?;
+    let result = match daily_challenge_service
+        .start_attempt(day, player.0.username)
+        .await
+    {
+        Ok((challenge, attempt)) => Ok(Json(daily_challenge_mapper::attempt_to_dto(
+            &challenge, attempt, now,
+        ))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("start_daily_attempt resource ending");
+    result
+}
+
+/// GET request to get the attempt of the authenticated player at the challenge of a day, with the question to answer.
+/// Returns an error if the player did not attempt the challenge.
+/// Returns an error if the user is not allowed to play.
+#[get("/daily/<day>/attempt", format = "json")]
+pub async fn get_daily_attempt(
+    day: String,
+    player: Player,
+) -> Result<Json<DailyAttemptDto>, Status> {
+    debug!("get_daily_attempt resource started");
+    let daily_challenge_service = DailyChallengeService::init().await;
+    let result = match daily_challenge_service
+        .get_attempt(day, player.0.username)
+        .await
+    {
+        Ok((challenge, attempt)) => Ok(Json(daily_challenge_mapper::attempt_to_dto(
+            &challenge,
+            attempt,
+            DateTime::now(),
+        ))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_daily_attempt resource ending");
+    result
+}
+
+/// POST request to answer the current question of the attempt of the authenticated player at the challenge of a day.
+/// Returns whether the answer is correct and the attempt with its next question.
+/// Returns an error if the answer is not between 1 and 4 or if the attempt is finished.
+/// Returns an error if the player did not attempt the challenge.
+/// Returns an error if the user is not allowed to play.
+#[post("/daily/<day>/attempt/answer/<answer>")]
+pub async fn answer_daily_attempt(
+    day: String,
+    answer: i8,
+    player: Player,
+) -> Result<Json<DailyFeedbackDto>, Status> {
+    debug!("answer_daily_attempt resource started");
+    let daily_challenge_service = DailyChallengeService::init().await;
+    let result = match daily_challenge_service
+        .answer(day, player.0.username, answer)
+        .await
+    {
+        Ok((challenge, feedback)) => Ok(Json(daily_challenge_mapper::feedback_to_dto(
+            &challenge,
+            feedback,
+            DateTime::now(),
+        ))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("answer_daily_attempt resource ending");
+    result
+}
+
+/// GET request to get a page of the leaderboard of a day, players being ranked by their number of correct answers,
+/// then by the time they took to answer. Pages start at 1 and hold 20 players unless another `size` is asked for.
+/// When the request is authenticated, the attempt of the player is given too.
+/// Returns an error if the page or its size are not valid.
+#[get("/daily/<day>/leaderboard?<page>&<size>", format = "json")]
+pub async fn get_daily_leaderboard(
+    day: String,
+    page: Option<u64>,
+    size: Option<i64>,
+    user: Option<AuthenticatedUser>,
+) -> Result<Json<DailyLeaderboardDto>, Status> {
+    debug!("get_daily_leaderboard resource started");
+    let daily_challenge_service = DailyChallengeService::init().await;
+    let page = page.unwrap_or(1);
+    let size = size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let leaderboard = daily_challenge_service
+        .get_leaderboard(day, page, size, user.map(|user| user.username))
+        .await;
+    let result = match leaderboard {
+        Ok(leaderboard) => Ok(Json(daily_challenge_mapper::leaderboard_to_dto(
+            leaderboard,
+            page,
+            size,
+        ))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_daily_leaderboard resource ending");
+    result
+}
+
+/// GET request to get the streaks of a player, the days in a row they attempted the daily challenge.
+#[get("/users/<username>/daily-streak", format = "json")]
+pub async fn get_daily_streak(username: String) -> Result<Json<DailyStreakDto>, Status> {
+    debug!("get_daily_streak resource started");
+    let daily_challenge_service = DailyChallengeService::init().await;
+    let result = match daily_challenge_service.get_streak(username).await {
+        Ok(streak) => Ok(Json(daily_challenge_mapper::streak_to_dto(streak))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_daily_streak resource ending");
+    result
+}
+
+/// Gets the challenge in progress at a date, generating it from the question pool if it was not yet.
+pub async fn today_challenge(
+    daily_challenge_service: &DailyChallengeService,
+    now: DateTime,
+) -> Result<DailyChallenge, DailyChallengeServiceError> {
+    let day = daily_challenge_service.day_of(now);
+    match daily_challenge_service.get_challenge(day.clone()).await {
+        Err(DailyChallengeServiceError {
+            kind: DailyChallengeServiceErrorKind::NotFound,
+            ..
+        }) => {
+            let topics: Vec<String> = DAILY_CHALLENGE_TOPICS
+                .iter()
+                .map(|topic| topic.to_string())
+                .collect();
+            let pool = question_pool(&topics)
+                .await
+                .into_iter()
+                .map(question_mapper::to_entity)
+                .collect();
+            daily_challenge_service.generate(day, pool).await
+        }
+        challenge => challenge,
+    }
+}
+
+fn process_service_error(error: DailyChallengeServiceError) -> Status {
+    error!("Error: {}", error.message);
+    match error.kind {
+        DailyChallengeServiceErrorKind::NotFound => Status::NotFound,
+        DailyChallengeServiceErrorKind::InvalidRequest => Status::BadRequest,
+        DailyChallengeServiceErrorKind::Conflict => Status::Conflict,
+        DailyChallengeServiceErrorKind::Internal => Status::InternalServerError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resource::daily_challenge_resource::{
+        answer_daily_attempt, get_daily_attempt, get_daily_challenge, get_daily_leaderboard,
+        get_daily_streak, start_daily_attempt,
+    };
+    use crate::resource::profile_resource::get_profile;
+    use crate::resource::test_fixtures::user;
+    use crate::security::guard::Player;
+    use crate::service::daily_challenge_service::DailyChallengeService;
+    use log::info;
+    use rocket::async_test;
+    use rocket::http::Status;
+    use serial_test::serial;
+    use std::env;
+    use testcontainers::clients::Cli;
+    use testcontainers::GenericImage;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    /// Answers every question of the attempt of a player, correctly unless `wrong` is true.
+    async fn play(day: &str, username: &str, wrong: bool) {
+        loop {
+            let attempt = get_daily_attempt(day.to_string(), Player(user(username)))
+                .await
+                .unwrap()
+                .into_inner();
+            let question = match attempt.question {
+                Some(question) => question,
+                None => break,
+            };
+            let answer = if wrong {
+                question.good_answer_number % 4 + 1
+            } else {
+                question.good_answer_number
+            };
+            let feedback = answer_daily_attempt(day.to_string(), answer, Player(user(username)))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(feedback.correct, !wrong);
+        }
+    }
+
+    #[async_test]
+    #[serial]
+    async fn daily_challenge_should_be_attempted_once_and_ranked() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        DailyChallengeService::init()
+            .await
+            .create_indexes()
+            .await
+            .unwrap();
+
+        let challenge = get_daily_challenge(None).await.unwrap().into_inner();
+        assert_eq!(challenge.question_number, 10);
+        assert_eq!(challenge.topics, vec!["Java", "Kotlin", "Rust"]);
+        assert!(challenge.attempt.is_none());
+        let day = challenge.day;
+        let again = get_daily_challenge(None).await.unwrap().into_inner();
+        assert_eq!(again.starts_at, challenge.starts_at);
+
+        let error = start_daily_attempt("2000-01-01".to_string(), Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let attempt = start_daily_attempt(day.clone(), Player(user("alice")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(attempt.current_question, 0);
+        assert!(attempt.question.is_some());
+        let error = start_daily_attempt(day.clone(), Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+        play(&day, "alice", false).await;
+        let error = answer_daily_attempt(day.clone(), 1, Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+
+        start_daily_attempt(day.clone(), Player(user("bob")))
+            .await
+            .unwrap();
+        play(&day, "bob", true).await;
+        // Daily answers are left out of the statistics of the player
+        let error = get_profile("bob".to_string()).await.unwrap_err();
+        assert_eq!(error, Status::NotFound);
+        let error = get_daily_attempt(day.clone(), Player(user("carol")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::NotFound);
+
+        let leaderboard = get_daily_leaderboard(day.clone(), None, None, Some(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let users: Vec<&str> = leaderboard
+            .attempts
+            .iter()
+            .map(|attempt| attempt.user.as_str())
+            .collect();
+        assert_eq!(users, vec!["alice", "bob"]);
+        assert_eq!(leaderboard.attempts[0].score, 10);
+        assert!(leaderboard.attempts[0].finished);
+        assert_eq!(leaderboard.me.unwrap().rank, 2);
+
+        let challenge = get_daily_challenge(Some(user("alice")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(challenge.attempt.unwrap().finished);
+        assert_eq!(challenge.streak.unwrap().current, 1);
+        let streak = get_daily_streak("carol".to_string())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(streak.best, 0);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::dto::answer::GameAnswerDto;
-use crate::dto::game_dto::GameDto;
+use crate::dto::game_dto::{
+    GameBuzzDto, GameDamageDto, GameDto, GameEliminationDto, GameHintDto, GameHostActionDto,
+    GamePowerUpDto, JoinGameDto, LobbyDto, ScoreboardDto,
+};
 use crate::dto::game_progress_dto::{
     questions_java, questions_kotlin, questions_rust, GameProgressDto, QuestionDto,
 };
@@ -7,28 +10,44 @@ use crate::errors::game_service_error::{GameServiceError, GameServiceErrorKind};
 use crate::mapper::game_mapper::{self, answer_to_entity};
 use crate::mapper::game_mapper::{entity_to_progress, progress_to_entity};
 use crate::mapper::question_mapper;
-use crate::model::game::Game;
+use crate::model::game::{Game, GameMode, GameStatus};
+use crate::model::game_progress::Question;
+use crate::model::hint::GameHint;
+use crate::model::power_up::{answer_seconds, PowerUp, EXTRA_TIME_SECONDS};
+use crate::model::survival::{pick_survival_question, survival_seconds};
+use crate::security::guard::{AuthenticatedUser, GameHost, Player};
 use crate::service::game_service::GameService;
+use crate::service::leaderboard_service::LeaderboardService;
+use crate::service::profile_service::ProfileService;
+use crate::service::question_service::QuestionService;
+use crate::service::rating_service::RatingService;
 use log::{debug, error, info};
 use rand::Rng;
 use rocket::http::Status;
 use rocket::response::stream::{Event, EventStream};
 use rocket::serde::json::Json;
 use rocket::tokio::{task, time};
-use rocket::{get, patch, post};
+use rocket::{delete, get, patch, post};
+use std::collections::BTreeMap;
 use std::time::Duration;
 use std::vec;
 
 pub const QUESTION_SECONDS: u64 = 20;
+pub const COUNTDOWN_SECONDS: u64 = 3;
 
-/// POST request to create a new game.
-/// Returns the created game.
+/// POST request to create a new game, hosted by the authenticated user.
+/// Private games get a join code, and can be protected by a password.
+/// Returns the created game, with its join code.
+/// Returns an error if the user is not allowed to host games.
 #[post("/game", format = "json", data = "<new_game>")]
-pub async fn create_game(new_game: Json<GameDto>) -> Result<Json<GameDto>, Status> {
+pub async fn create_game(new_game: Json<GameDto>, host: GameHost) -> Result<Json<GameDto>, Status> {
     debug!("create_games resource started");
     let game_service = GameService::init().await;
-    let game_entity = game_mapper::to_entity(new_game.into_inner());
-    let game_created = game_service.create_game(game_entity).await;
+    let mut new_game = new_game.into_inner();
+    new_game.creator = Some(host.0.username);
+    let password = new_game.password.clone();
+    let game_entity = game_mapper::to_entity(new_game);
+    let game_created = game_service.create_game(game_entity, password).await;
     let result = match game_created {
         Ok(game_created) => {
             let game_output = game_mapper::to_dto(game_created);
@@ -40,18 +59,24 @@ pub async fn create_game(new_game: Json<GameDto>) -> Result<Json<GameDto>, Statu
     result
 }
 
-/// GET request to get all the games.
+/// GET request to get all the public games.
+/// Games can be filtered by status with one or several `status` query parameters, lobby games are returned by default.
 /// Returns a list of games.
-#[get("/games", format = "json")]
-pub async fn get_games() -> Result<Json<Vec<GameDto>>, Status> {
+#[get("/games?<status>", format = "json")]
+pub async fn get_games(status: Vec<GameStatus>) -> Result<Json<Vec<GameDto>>, Status> {
     debug!("get_games resource started");
+    let statuses = if status.is_empty() {
+        vec![GameStatus::Lobby]
+    } else {
+        status
+    };
     let game_service = GameService::init().await;
-    let games_fetched = game_service.get_games().await;
+    let games_fetched = game_service.get_games(statuses).await;
     let result = match games_fetched {
         Ok(games_fetched) => {
             let game_output: Vec<GameDto> = games_fetched
                 .iter()
-                .map(|game| game_mapper::to_dto(game.clone()))
+                .map(|game| without_join_code(game_mapper::to_dto(game.clone())))
                 .collect();
             Ok(Json(game_output))
         }
@@ -62,7 +87,7 @@ pub async fn get_games() -> Result<Json<Vec<GameDto>>, Status> {
 }
 
 /// GET request to get a game by id.
-/// Returns the game.
+/// Returns the game, without its join code.
 /// Returns an error if the game does not exist.
 /// Returns an error if the id is not a valid ObjectId.
 #[get("/game/<id>", format = "json")]
@@ -71,7 +96,7 @@ pub async fn get_game(id: String) -> Result<Json<GameDto>, Status> {
     let game_service = GameService::init().await;
     let game_fetched = game_service.get_game(id).await;
     let result = match game_fetched {
-        Ok(game_fetched) => Ok(Json(game_mapper::to_dto(game_fetched))),
+        Ok(game_fetched) => Ok(Json(without_join_code(game_mapper::to_dto(game_fetched)))),
         Err(err) => Err(process_service_error(err)),
     };
     debug!("get_game resource ending");
@@ -80,38 +105,93 @@ pub async fn get_game(id: String) -> Result<Json<GameDto>, Status> {
 
 /// GET request to get a game progress.
 /// Returns events to sync game progress with clients.
+/// Actions of the host are sent as `host_action` events.
+/// Players knocked out of an elimination game are sent as `elimination` events, they can keep following the game.
+/// Damages dealt in a duel are sent as `damage` events, with the hit points left to the player hurt.
+/// Answers of a buzzer game are sent as `buzz` events, in the order they were received.
+/// Power-ups used by the players are sent as `power_up` events, without the answers removed by a 50/50.
+/// Hints revealed to the authenticated player are sent to them as `hint` events, with their text.
+/// Changes of the lobby players and of their readiness are sent as `lobby` events, with the number of spectators.
+/// Once the game started, changes of the number of spectators are sent as `spectators` events.
 /// Returns an error if the game does not exist.
 /// Returns an error if the id is not a valid ObjectId.
 #[get("/game/<id>/progress")]
-pub async fn game_progress(id: String) -> EventStream![] {
+pub async fn game_progress(id: String, user: Option<AuthenticatedUser>) -> EventStream![] {
+    let viewer = user.map(|user| user.username);
     EventStream! {
         debug!("game_progress events started");
         let mut interval = time::interval(Duration::from_secs(1));
-        loop {
+        let game_service = GameService::init().await;
+        let mut seen_host_actions = None;
+        let mut seen_eliminations = None;
+        let mut seen_damages = None;
+        let mut seen_buzzes = None;
+        let mut seen_power_ups = None;
+        let mut seen_hints = None;
+        let mut last_lobby = None;
+        let mut last_spectators = None;
+        let status = loop {
             let mut users: Vec<String> = vec![];
-            match get_game(id.clone()).await {
+            match game_service.get_game(id.clone()).await {
                 Ok(result) => {
+                    for action in new_host_actions(&result, &mut seen_host_actions) {
+                        yield Event::json(&action).event("host_action");
+                    }
+                    let lobby = game_mapper::to_lobby(&result);
+                    if last_lobby.as_ref() != Some(&lobby) {
+                        yield Event::json(&lobby).event("lobby");
+                        last_lobby = Some(lobby);
+                    }
                     users = result.users.clone();
-                    if result.is_started {break}
+                    if !matches!(result.status, GameStatus::Lobby | GameStatus::Countdown) {break result.status}
                 },
                 Err(_) => error!("Problem occurred when fetching game in sse game progress"),
             }
             let users_string = users.iter().map(|u| u.to_owned() + "\n").collect::<String>();
             yield Event::data("NOT STARTED".to_owned() + users_string.as_str());
             interval.tick().await;
+        };
+        if status.is_over() {
+            yield Event::data("END");
+            return;
         }
-        let game_service = GameService::init().await;
-        let mut current = game_service.get_game_progress(id.clone()).await.expect("Failed to get game progress");
-        while current.current_question < current.question_number && current.question_content.remaining_time > 0 {
-            let game_service = GameService::init().await;
+        loop {
             match game_service.get_game_progress(id.clone()).await {
                 Ok(result) => {
-                    current = result.clone();
                     let game_progress_dto = entity_to_progress(result);
                     yield Event::json(&game_progress_dto);
                 },
                 Err(_) => error!("Problem occurred when fetching game in sse game progress"),
             }
+            match game_service.get_game(id.clone()).await {
+                Ok(result) => {
+                    for action in new_host_actions(&result, &mut seen_host_actions) {
+                        yield Event::json(&action).event("host_action");
+                    }
+                    for elimination in new_eliminations(&result, &mut seen_eliminations) {
+                        yield Event::json(&elimination).event("elimination");
+                    }
+                    for damage in new_damages(&result, &mut seen_damages) {
+                        yield Event::json(&damage).event("damage");
+                    }
+                    for buzz in new_buzzes(&result, &mut seen_buzzes) {
+                        yield Event::json(&buzz).event("buzz");
+                    }
+                    for power_up in new_power_ups(&result, &mut seen_power_ups) {
+                        yield Event::json(&power_up).event("power_up");
+                    }
+                    for hint in new_hints(&result, &mut seen_hints, viewer.as_deref()) {
+                        yield Event::json(&hint).event("hint");
+                    }
+                    let spectators = game_mapper::to_spectators(&result);
+                    if last_spectators.as_ref() != Some(&spectators) {
+                        yield Event::json(&spectators).event("spectators");
+                        last_spectators = Some(spectators);
+                    }
+                    if result.status.is_over() {break}
+                },
+                Err(_) => error!("Problem occurred when fetching game in sse game progress"),
+            }
             interval.tick().await;
         }
         yield Event::data("END");
@@ -119,57 +199,342 @@ pub async fn game_progress(id: String) -> EventStream![] {
     }
 }
 
-/// POST request to save resonse of a player
-#[post("/game/<id>/progress/<answer>", format = "json", data = "<user>")]
-pub async fn game_progress_answer(id: String, answer: i8, user: String) {
+/// POST request to save resonse of the authenticated player.
+/// Answers of players knocked out of an elimination game are ignored.
+/// In buzzer games, answers of players locked out of the question and answers to a question already won are ignored.
+/// In wager games, the stake of the player is given as `wager`.
+/// In games played with power-ups, answers given after the time left to the player ran out are ignored,
+/// and the power-up the player used on the question is recorded with the answer.
+/// Returns an error if the stake is missing or not covered by the points of the player.
+/// Returns an error if the user does not play the game, like its spectators and kicked players.
+/// Returns an error if the user is not allowed to play.
+#[post("/game/<id>/progress/<answer>?<wager>")]
+pub async fn game_progress_answer(
+    id: String,
+    answer: i8,
+    wager: Option<i32>,
+    player: Player,
+) -> Status {
     debug!("game_progress_answer started");
+    let user = player.0.username;
     let game_service = GameService::init().await;
-    let game_progress = game_service
-        .get_game_progress(id.clone())
+    let game = match game_service.get_game(id.clone()).await {
+        Ok(game) => game,
+        Err(err) => return process_service_error(err),
+    };
+    if !game.users.contains(&user) {
+        debug!("game_progress_answer refused to a user not playing the game");
+        return Status::Forbidden;
+    }
+    if game.status != GameStatus::Running || game.is_eliminated(&user) {
+        debug!("game_progress_answer ignored");
+        return Status::Ok;
+    }
+    let game_progress = match game_service.get_game_progress(id.clone()).await {
+        Ok(game_progress) => game_progress,
+        Err(err) => return process_service_error(err),
+    };
+    let question_index = game_progress.current_question;
+    if game.power_ups
+        && game.time_left(
+            &user,
+            question_index,
+            game_progress.question_content.remaining_time,
+            game_progress.overtime,
+        ) == 0
+    {
+        debug!("game_progress_answer ignored: time ran out");
+        return Status::Ok;
+    }
+    let power_up = game
+        .power_up_of(&user, question_index)
+        .map(|used| used.power_up);
+    let wager = match game_service
+        .check_wager(&game, &user, question_index, wager)
         .await
-        .expect("Failed to get game progress");
+    {
+        Ok(wager) => wager,
+        Err(err) => return process_service_error(err),
+    };
+    let answer_seconds = answer_seconds(
+        question_seconds(&game, question_index),
+        game_progress.question_content.remaining_time,
+        game_progress.overtime,
+    );
     let answer = GameAnswerDto {
         game_id: id,
         user,
         answer,
-        question_index: game_progress.current_question,
+        question_index,
         correct_answer: game_progress.question_content.good_answer_number,
         question: question_mapper::to_dto(game_progress.question_content),
+        wager,
+        power_up,
+        answer_seconds: Some(answer_seconds),
     };
     let answer = answer_to_entity(answer);
+    if game.mode == GameMode::Buzzer {
+        if let Err(err) = game_service.buzz(&answer).await {
+            debug!("game_progress_answer ignored: {}", err.message);
+            return Status::Ok;
+        }
+    }
     game_service.save_game_answer(&answer).await;
     debug!("game_progress_answer ending");
+    Status::Ok
 }
 
-/// POST request to register new player
-#[post("/game/<id>/users/<user>", format = "json")]
-pub async fn game_register_user(id: String, user: String) {
-    debug!("game_register_user started");
+/// POST request to use a power-up as the authenticated player, on the current question of a game played with power-ups.
+/// The power-up is given as `kind`, and the opponent hit by a freeze as `target`.
+/// Returns the use of the power-up, with the answers removed for the player by a 50/50.
+/// Returns an error if the game is not played with power-ups, if the player has no power-up left or if the target is not valid.
+/// Returns an error if the player is not in the game, already used a power-up on the question, answered it or has no time left.
+/// Returns an error if the user is not allowed to play.
+#[post("/game/<id>/power_up?<kind>&<target>")]
+pub async fn use_power_up(
+    id: String,
+    kind: PowerUp,
+    target: Option<String>,
+    player: Player,
+) -> Result<Json<GamePowerUpDto>, Status> {
+    debug!("use_power_up resource started");
     let game_service = GameService::init().await;
-    let mut game = game_service
-        .get_game(id)
+    let result = match game_service
+        .use_power_up(id, player.0.username, kind, target)
         .await
-        .expect("Failed to get game progress");
-    if !game.users.contains(&user) {
-        game.users.push(user);
-        game_service.save_users_in_game(&game).await;
-    }
+    {
+        Ok(used) => {
+            let removed_answers = used.removed_answers.clone();
+            let mut power_up = game_mapper::power_up_to_dto(used);
+            power_up.removed_answers = removed_answers;
+            Ok(Json(power_up))
+        }
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("use_power_up resource ending");
+    result
+}
+
+/// POST request to reveal the next hint of the current question of a game to the authenticated player.
+/// The hint costs the hint penalty of the game, taken from the score of the player.
+/// Returns the revealed hint, which is sent to the progress stream of the player too.
+/// Returns an error if the question has no hint left for the player.
+/// Returns an error if the player is not in the game, already answered the question or has no time left.
+/// Returns an error if the user is not allowed to play.
+#[post("/game/<id>/hint")]
+pub async fn reveal_hint(id: String, player: Player) -> Result<Json<GameHintDto>, Status> {
+    debug!("reveal_hint resource started");
+    let game_service = GameService::init().await;
+    let result = match game_service.reveal_hint(id, player.0.username).await {
+        Ok(hint) => Ok(Json(revealed_hint_to_dto(hint))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("reveal_hint resource ending");
+    result
+}
+
+/// POST request to register the authenticated user as new player
+/// Returns the resulting lobby.
+/// Returns an error if the game is private, is not in the lobby or is full.
+/// Returns an error if the player was kicked from the game or is not allowed to play.
+#[post("/game/<id>/users")]
+pub async fn game_register_user(id: String, player: Player) -> Result<Json<LobbyDto>, Status> {
+    debug!("game_register_user started");
+    let game_service = GameService::init().await;
+    let result = match game_service.register_user(id, player.0.username).await {
+        Ok(game) => Ok(Json(game_mapper::to_lobby(&game))),
+        Err(err) => Err(process_service_error(err)),
+    };
     debug!("game_register_user ending");
+    result
+}
+
+/// POST request to join a private game with its join code as the authenticated user.
+/// Returns the game, without its join and spectate codes.
+/// Returns an error if no game has this join code.
+/// Returns an error if the password of the game does not match.
+/// Returns an error if the game is not in the lobby or is full.
+/// Returns an error if the user is not allowed to play.
+#[post("/join/<code>", format = "json", data = "<join_game>")]
+pub async fn join_game(
+    code: String,
+    join_game: Json<JoinGameDto>,
+    player: Player,
+) -> Result<Json<GameDto>, Status> {
+    debug!("join_game resource started");
+    let game_service = GameService::init().await;
+    let join_game = join_game.into_inner();
+    let game_joined = game_service
+        .join_game(code, player.0.username, join_game.password)
+        .await;
+    let result = to_game_response(game_joined);
+    debug!("join_game resource ending");
+    result
+}
+
+/// POST request to spectate a public game as the authenticated user, who then watches it without playing.
+/// Games can be spectated from the lobby until they are over, late arrivals included.
+/// Returns the game.
+/// Returns an error if the game is private, it is spectated with its spectate code.
+/// Returns an error if the user plays or was kicked from the game, or if the game is over.
+/// Returns an error if the user is not allowed to play.
+#[post("/game/<id>/spectators")]
+pub async fn spectate_game(id: String, player: Player) -> Result<Json<GameDto>, Status> {
+    debug!("spectate_game resource started");
+    let game_service = GameService::init().await;
+    let game = game_service.spectate_game(id, player.0.username).await;
+    let result = match game {
+        Ok(game) => Ok(Json(without_join_code(game_mapper::to_dto(game)))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("spectate_game resource ending");
+    result
+}
+
+/// POST request to spectate a game with its spectate code as the authenticated user, private games included.
+/// Returns the game, without its join code.
+/// Returns an error if no game has this spectate code.
+/// Returns an error if the user plays or was kicked from the game, or if the game is over.
+/// Returns an error if the user is not allowed to play.
+#[post("/spectate/<code>")]
+pub async fn spectate_by_code(code: String, player: Player) -> Result<Json<GameDto>, Status> {
+    debug!("spectate_by_code resource started");
+    let game_service = GameService::init().await;
+    let game = game_service.spectate_by_code(code, player.0.username).await;
+    let result = match game {
+        Ok(game) => {
+            let mut game = game_mapper::to_dto(game);
+            game.join_code = None;
+            Ok(Json(game))
+        }
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("spectate_by_code resource ending");
+    result
+}
+
+/// DELETE request to stop spectating a game as the authenticated user.
+/// Returns the game.
+/// Returns an error if the user is not spectating the game.
+/// Returns an error if the user is not allowed to play.
+#[delete("/game/<id>/spectators")]
+pub async fn stop_spectating(id: String, player: Player) -> Result<Json<GameDto>, Status> {
+    debug!("stop_spectating resource started");
+    let game_service = GameService::init().await;
+    let game = game_service.stop_spectating(id, player.0.username).await;
+    let result = match game {
+        Ok(game) => Ok(Json(without_join_code(game_mapper::to_dto(game)))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("stop_spectating resource ending");
+    result
+}
+
+/// PATCH request to replace the join code of a private game.
+/// Returns the game, with its new join code.
+/// Returns an error if the user is neither the host of the game nor a moderator.
+/// Returns an error if the game is not private.
+#[patch("/game/<id>/code")]
+pub async fn rotate_join_code(
+    id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<GameDto>, Status> {
+    debug!("rotate_join_code resource started");
+    let game_service = GameService::init().await;
+    let result = to_host_game_response(game_service.rotate_join_code(id, user).await);
+    debug!("rotate_join_code resource ending");
+    result
+}
+
+/// DELETE request to revoke the join code of a private game.
+/// Returns the game.
+/// Returns an error if the user is neither the host of the game nor a moderator.
+/// Returns an error if the game is not private.
+#[delete("/game/<id>/code")]
+pub async fn revoke_join_code(
+    id: String,
+    user: AuthenticatedUser,
+) -> Result<Json<GameDto>, Status> {
+    debug!("revoke_join_code resource started");
+    let game_service = GameService::init().await;
+    let result = to_host_game_response(game_service.revoke_join_code(id, user).await);
+    debug!("revoke_join_code resource ending");
+    result
+}
+
+/// DELETE request to remove a player from a game.
+/// Players leave the game when they remove themselves.
+/// Otherwise the authenticated user must be the host kicking the player out of the game.
+/// Returns the game.
+/// Returns an error if the player is not part of the game.
+/// Returns an error if the user kicking the player is not the host of the game.
+#[delete("/game/<id>/users/<player>")]
+pub async fn remove_player(
+    id: String,
+    player: String,
+    user: AuthenticatedUser,
+) -> Result<Json<GameDto>, Status> {
+    debug!("remove_player resource started");
+    let game_service = GameService::init().await;
+    let game = if user.username == player {
+        game_service.leave_game(id, player).await
+    } else {
+        game_service.kick_player(id, user, player).await
+    };
+    let result = to_game_response(game);
+    debug!("remove_player resource ending");
+    result
+}
+
+/// PATCH request to toggle the readiness of the authenticated player in a game lobby.
+/// Returns the game.
+/// Returns an error if the player is not part of the game.
+/// Returns an error if the game is not in the lobby.
+/// Returns an error if the user is not allowed to play.
+#[patch("/game/<id>/ready")]
+pub async fn toggle_ready(id: String, player: Player) -> Result<Json<GameDto>, Status> {
+    debug!("toggle_ready resource started");
+    let game_service = GameService::init().await;
+    let result = to_game_response(game_service.toggle_ready(id, player.0.username).await);
+    debug!("toggle_ready resource ending");
+    result
+}
+
+/// PATCH request to move the authenticated player of a team game lobby to another team.
+/// Players are balanced into a team when they join, they may then pick the team of their choice.
+/// Returns the game.
+/// Returns an error if the game is not a team game or is not in the lobby.
+/// Returns an error if the player is not part of the game or if the team does not exist.
+/// Returns an error if the user is not allowed to play.
+#[patch("/game/<id>/team/<team>")]
+pub async fn choose_team(
+    id: String,
+    team: String,
+    player: Player,
+) -> Result<Json<GameDto>, Status> {
+    debug!("choose_team resource started");
+    let game_service = GameService::init().await;
+    let result = to_game_response(game_service.choose_team(id, player.0.username, team).await);
+    debug!("choose_team resource ending");
+    result
 }
 
-/// PATCH request to update a game content.
+/// PATCH request to start a game.
+/// The game enters its countdown and runs once the countdown is over.
 /// Returns the game.
 /// Returns an error if the game does not exist.
 /// Returns an error if the id is not a valid ObjectId.
-#[patch("/game/<id>", format = "json")]
-pub async fn patch_game(id: String) -> Result<Json<String>, Status> {
+/// Returns an error if the user is neither the host of the game nor a moderator.
+/// Returns an error if the game is not in the lobby.
+#[patch("/game/<id>")]
+pub async fn patch_game(id: String, user: AuthenticatedUser) -> Result<Json<GameDto>, Status> {
     debug!("patch_game resource started");
     let game_service = GameService::init().await;
-    let game_fetched = game_service.patch_game(id.clone()).await;
+    let game_fetched = game_service.start_game(id.clone(), user).await;
     let result = match game_fetched {
-        Ok(_) => {
+        Ok(game) => {
             task::spawn(async move { start_new_game(id.clone()).await });
-            Ok(Json("".to_string()))
+            Ok(Json(game_mapper::to_dto(game)))
         }
         Err(err) => Err(process_service_error(err)),
     };
@@ -177,20 +542,102 @@ pub async fn patch_game(id: String) -> Result<Json<String>, Status> {
     result
 }
 
+/// PATCH request to pause a running game.
+/// Returns the game.
+/// Returns an error if the user is neither the host of the game nor a moderator.
+/// Returns an error if the game is not running.
+#[patch("/game/<id>/pause")]
+pub async fn pause_game(id: String, user: AuthenticatedUser) -> Result<Json<GameDto>, Status> {
+    debug!("pause_game resource started");
+    let game_service = GameService::init().await;
+    let result = to_game_response(game_service.pause_game(id, user).await);
+    debug!("pause_game resource ending");
+    result
+}
+
+/// PATCH request to resume a paused game.
+/// Returns the game.
+/// Returns an error if the user is neither the host of the game nor a moderator.
+/// Returns an error if the game is not paused.
+#[patch("/game/<id>/resume")]
+pub async fn resume_game(id: String, user: AuthenticatedUser) -> Result<Json<GameDto>, Status> {
+    debug!("resume_game resource started");
+    let game_service = GameService::init().await;
+    let result = to_game_response(game_service.resume_game(id, user).await);
+    debug!("resume_game resource ending");
+    result
+}
+
+/// PATCH request to end the current question of a running game.
+/// Returns the game.
+/// Returns an error if the user is neither the host of the game nor a moderator.
+/// Returns an error if the game is not running.
+#[patch("/game/<id>/skip")]
+pub async fn skip_question(id: String, user: AuthenticatedUser) -> Result<Json<GameDto>, Status> {
+    debug!("skip_question resource started");
+    let game_service = GameService::init().await;
+    let result = to_game_response(game_service.skip_question(id, user).await);
+    debug!("skip_question resource ending");
+    result
+}
+
+/// PATCH request to abort a game which is not over.
+/// Returns the game.
+/// Returns an error if the user is neither the host of the game nor a moderator.
+/// Returns an error if the game is already over.
+#[patch("/game/<id>/abort")]
+pub async fn abort_game(id: String, user: AuthenticatedUser) -> Result<Json<GameDto>, Status> {
+    debug!("abort_game resource started");
+    let game_service = GameService::init().await;
+    let result = to_game_response(game_service.abort_game(id, user).await);
+    debug!("abort_game resource ending");
+    result
+}
+
+/// PATCH request to hand the host role of a game over to one of its players.
+/// Returns the game.
+/// Returns an error if the user is neither the host of the game nor a moderator.
+/// Returns an error if the player is not part of the game.
+#[patch("/game/<id>/creator/<player>")]
+pub async fn transfer_host(
+    id: String,
+    player: String,
+    user: AuthenticatedUser,
+) -> Result<Json<GameDto>, Status> {
+    debug!("transfer_host resource started");
+    let game_service = GameService::init().await;
+    let result = to_game_response(game_service.transfer_host(id, user, player).await);
+    debug!("transfer_host resource ending");
+    result
+}
+
+/// GET request to get the scoreboard of a game.
+/// Returns the standings of the players from the answers given so far, and the standings of the teams in team games.
+/// Returns the final standings once the game is finished.
+/// Returns an error if the game does not exist.
+#[get("/game/<id>/scoreboard", format = "json")]
+pub async fn get_scoreboard(id: String) -> Result<Json<ScoreboardDto>, Status> {
+    debug!("get_scoreboard resource started");
+    let game_service = GameService::init().await;
+    let result = match game_service.get_scoreboard(id.clone()).await {
+        Ok(scoreboard) => Ok(Json(game_mapper::scoreboard_to_dto(id, scoreboard))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_scoreboard resource ending");
+    result
+}
+
 /// GET request to get a game answers.
 /// Returns the game result.
 /// Returns an error if the game does not exist.
+/// Returns an error if the game is not over, games ending early being over too.
 #[get("/game/<id>/answers", format = "json")]
 pub async fn get_game_answers(id: String) -> Result<Json<Vec<GameAnswerDto>>, Status> {
     debug!("get_game_result resource started");
     let game_service = GameService::init().await;
-    let game_progress = game_service.get_game_progress(id.clone()).await;
-    match game_progress {
-        Ok(game_progress) => {
-            if game_progress.current_question < game_progress.question_number - 1 {
-                return Err(Status::Locked);
-            }
-        }
+    match game_service.get_game(id.clone()).await {
+        Ok(game) if !game.status.is_over() => return Err(Status::Locked),
+        Ok(_) => {}
         Err(error) => return Err(process_service_error(error)),
     }
     let game_answers = game_service.get_game_result(id.clone()).await;
@@ -208,9 +655,8 @@ pub async fn get_game_answers(id: String) -> Result<Json<Vec<GameAnswerDto>>, St
     result
 }
 
-fn resolve_question_pool(game: &Game) -> Vec<QuestionDto> {
+fn resolve_question_pool(topics: &[String]) -> Vec<QuestionDto> {
     let mut questions = vec![];
-    let topics = game.topics.clone();
     if topics.contains(&"Java".to_string()) {
         questions.append(&mut questions_java());
     }
@@ -223,40 +669,135 @@ fn resolve_question_pool(game: &Game) -> Vec<QuestionDto> {
     questions
 }
 
-async fn start_new_game(id: String) {
+/// Gathers the questions of the given topics, the built-in ones and the ones written by question authors.
+pub async fn question_pool(topics: &[String]) -> Vec<QuestionDto> {
+    let mut questions = resolve_question_pool(topics);
+    let question_service = QuestionService::init().await;
+    match question_service.get_topic_questions(topics.to_vec()).await {
+        Ok(authored) => questions.extend(
+            authored
+                .into_iter()
+                .map(question_mapper::authored_to_game_question),
+        ),
+        Err(err) => error!("Failed to get authored questions: {}", err.message),
+    }
+    questions
+}
+
+/// Runs a game from the end of its countdown: asks its questions one after the other, then finishes it
+/// and updates the ratings, leaderboards and profiles of its players.
+/// The game is aborted when there is no question on its topics.
+pub async fn start_new_game(id: String) {
     info!("Starting the game");
     let game_service = GameService::init().await;
-    let game = game_service.get_game(id.clone()).await;
+    time::sleep(Duration::from_secs(COUNTDOWN_SECONDS)).await;
+    let game = game_service
+        .transition_game(id.clone(), GameStatus::Running)
+        .await;
     if let Ok(game) = game {
-        let questions = resolve_question_pool(&game);
-        let random_index = rand::thread_rng().gen_range(0..questions.len());
-        let question = questions.get(random_index).unwrap().clone();
+        let questions = question_pool(&game.topics).await;
+        if questions.is_empty() {
+            error!("No question on the topics of the game, aborting it");
+            if let Err(err) = game_service
+                .transition_game(id.clone(), GameStatus::Aborted)
+                .await
+            {
+                error!("Failed to abort the game: {}", err.message);
+            }
+            return;
+        }
+        let tiers = match game.mode {
+            GameMode::Survival => game_service
+                .get_question_tiers(game.topics.clone())
+                .await
+                .unwrap_or_else(|err| {
+                    error!("Failed to get the question tiers: {}", err.message);
+                    BTreeMap::new()
+                }),
+            _ => BTreeMap::new(),
+        };
+        let question = pick_question(&game, &questions, &tiers, 0);
         let mut game_proress_dto = GameProgressDto {
             game_id: game.id.unwrap().to_string(),
             current_question: 0,
             question_number: game.question_number,
             question_content: question.clone(),
+            overtime: 0,
         };
         let game_progress_entity = progress_to_entity(game_proress_dto.clone());
         game_service.save_game_progress(&game_progress_entity).await;
         let mut interval = time::interval(Duration::from_secs(1));
-        for question_index in 0..game_proress_dto.question_number {
-            for _ in 0..QUESTION_SECONDS {
+        'questions: for question_index in 0..game_proress_dto.question_number {
+            while game_proress_dto.question_content.remaining_time > 0 {
+                interval.tick().await;
+                let game = game_service.get_game(id.clone()).await;
+                match &game {
+                    Ok(game) if game.status == GameStatus::Paused => continue,
+                    Ok(game) if game.status != GameStatus::Running => break 'questions,
+                    Ok(game) if game.is_question_over(question_index) => {
+                        info!("Question over before its time ran out");
+                        game_proress_dto.question_content.remaining_time = 0;
+                    }
+                    _ => game_proress_dto.question_content.remaining_time -= 1,
+                }
+                let overtime = game.is_ok_and(|game| {
+                    game.has_overtime(question_index) && !game.is_question_over(question_index)
+                });
+                if game_proress_dto.question_content.remaining_time == 0 && overtime {
+                    game_proress_dto.overtime = EXTRA_TIME_SECONDS;
+                }
+                let game_progress_entity = progress_to_entity(game_proress_dto.clone());
+                game_service
+                    .replace_game_progress(&game_progress_entity)
+                    .await;
+            }
+            while game_proress_dto.overtime > 0 {
                 interval.tick().await;
-                game_proress_dto.question_content.remaining_time -= 1;
+                match game_service.get_game(id.clone()).await {
+                    Ok(game) if game.status == GameStatus::Paused => continue,
+                    Ok(game) if game.status != GameStatus::Running => break 'questions,
+                    Ok(game) if game.is_question_over(question_index) => {
+                        game_proress_dto.overtime = 0;
+                    }
+                    _ => game_proress_dto.overtime -= 1,
+                }
                 let game_progress_entity = progress_to_entity(game_proress_dto.clone());
                 game_service
                     .replace_game_progress(&game_progress_entity)
                     .await;
             }
-            if question_index == game_proress_dto.question_number - 1 {
+            let mut last_question = question_index == game_proress_dto.question_number - 1;
+            match game_service.end_question(id.clone(), question_index).await {
+                Ok(game) if game.is_decided() => {
+                    info!("The game is decided");
+                    last_question = true;
+                }
+                Ok(_) => {}
+                Err(err) => error!("Failed to end the question: {}", err.message),
+            }
+            if last_question {
+                match game_service.finish_game(id.clone()).await {
+                    Ok(game) => {
+                        if let Err(err) = RatingService::init().await.update_ratings(&game).await {
+                            error!("Failed to update the ratings: {}", err.message);
+                        }
+                        if let Err(err) = LeaderboardService::init().await.record_game(&game).await
+                        {
+                            error!("Failed to record the game: {}", err.message);
+                        }
+                        ProfileService::init()
+                            .await
+                            .refresh_profiles(game.users)
+                            .await
+                    }
+                    Err(err) => error!("Failed to finish the game: {}", err.message),
+                }
                 break;
             }
             info!("Next question");
             game_proress_dto.current_question += 1;
-            let random_index = rand::thread_rng().gen_range(0..questions.len());
             game_proress_dto.question_content =
-                questions.get(random_index).unwrap().clone().clone();
+                pick_question(&game, &questions, &tiers, game_proress_dto.current_question);
             let game_progress_entity = progress_to_entity(game_proress_dto.clone());
             game_service
                 .replace_game_progress(&game_progress_entity)
@@ -266,23 +807,186 @@ async fn start_new_game(id: String) {
     info!("End of the game");
 }
 
+/// Picks the question of the given index of a game at random in the pool.
+/// In survival games, the question is picked in the difficulty tier of its level and the time to answer it is shorter.
+fn pick_question(
+    game: &Game,
+    pool: &[QuestionDto],
+    tiers: &BTreeMap<String, u8>,
+    question_index: i8,
+) -> QuestionDto {
+    if game.mode == GameMode::Survival {
+        let pool: Vec<Question> = pool
+            .iter()
+            .cloned()
+            .map(question_mapper::to_entity)
+            .collect();
+        if let Some(question) = pick_survival_question(&pool, tiers, question_index) {
+            return question_mapper::to_dto(Question {
+                remaining_time: question_seconds(game, question_index),
+                ..question
+            });
+        }
+    }
+    let random_index = rand::thread_rng().gen_range(0..pool.len());
+    QuestionDto {
+        remaining_time: question_seconds(game, question_index),
+        ..pool[random_index].clone()
+    }
+}
+
+/// Gives the seconds players have to answer the question of the given index of a game, shorter in survival games.
+fn question_seconds(game: &Game, question_index: i8) -> u64 {
+    match game.mode {
+        GameMode::Survival => survival_seconds(question_index, QUESTION_SECONDS),
+        _ => QUESTION_SECONDS,
+    }
+}
+
+/// Gives the host actions of the game not sent yet to a progress stream.
+/// Actions already performed when the stream connects are not sent.
+fn new_host_actions(game: &Game, seen: &mut Option<usize>) -> Vec<GameHostActionDto> {
+    let already_seen = seen.unwrap_or(game.host_actions.len());
+    *seen = Some(game.host_actions.len());
+    game.host_actions
+        .iter()
+        .skip(already_seen)
+        .cloned()
+        .map(game_mapper::host_action_to_dto)
+        .collect()
+}
+
+/// Gives the eliminations of the game not sent yet to a progress stream.
+/// Players knocked out before the stream connects are not sent.
+fn new_eliminations(game: &Game, seen: &mut Option<usize>) -> Vec<GameEliminationDto> {
+    let already_seen = seen.unwrap_or(game.eliminations.len());
+    *seen = Some(game.eliminations.len());
+    game.eliminations
+        .iter()
+        .skip(already_seen)
+        .cloned()
+        .map(game_mapper::elimination_to_dto)
+        .collect()
+}
+
+/// Gives the damages of the duel not sent yet to a progress stream.
+/// Damages dealt before the stream connects are not sent.
+fn new_damages(game: &Game, seen: &mut Option<usize>) -> Vec<GameDamageDto> {
+    let already_seen = seen.unwrap_or(game.damages.len());
+    *seen = Some(game.damages.len());
+    game.damages
+        .iter()
+        .skip(already_seen)
+        .cloned()
+        .map(game_mapper::damage_to_dto)
+        .collect()
+}
+
+/// Gives the buzzes of the game not sent yet to a progress stream.
+/// Buzzes received before the stream connects are not sent.
+fn new_buzzes(game: &Game, seen: &mut Option<usize>) -> Vec<GameBuzzDto> {
+    let already_seen = seen.unwrap_or(game.buzzes.len());
+    *seen = Some(game.buzzes.len());
+    game.buzzes
+        .iter()
+        .skip(already_seen)
+        .cloned()
+        .map(game_mapper::buzz_to_dto)
+        .collect()
+}
+
+/// Gives the power-ups used in the game not sent yet to a progress stream.
+/// Power-ups used before the stream connects are not sent.
+fn new_power_ups(game: &Game, seen: &mut Option<usize>) -> Vec<GamePowerUpDto> {
+    let already_seen = seen.unwrap_or(game.used_power_ups.len());
+    *seen = Some(game.used_power_ups.len());
+    game.used_power_ups
+        .iter()
+        .skip(already_seen)
+        .cloned()
+        .map(game_mapper::power_up_to_dto)
+        .collect()
+}
+
+/// Gives the hints revealed to the given player not sent yet to their progress stream, with their text.
+/// Hints revealed before the stream connects are not sent, and nothing is sent to anonymous streams.
+fn new_hints(game: &Game, seen: &mut Option<usize>, viewer: Option<&str>) -> Vec<GameHintDto> {
+    let already_seen = seen.unwrap_or(game.revealed_hints.len());
+    *seen = Some(game.revealed_hints.len());
+    game.revealed_hints
+        .iter()
+        .skip(already_seen)
+        .filter(|hint| Some(hint.user.as_str()) == viewer)
+        .cloned()
+        .map(revealed_hint_to_dto)
+        .collect()
+}
+
+/// Maps a hint for the player it was revealed to, with its text.
+fn revealed_hint_to_dto(hint: GameHint) -> GameHintDto {
+    let text = hint.hint.clone();
+    let mut hint = game_mapper::hint_to_dto(hint);
+    hint.hint = Some(text);
+    hint
+}
+
+/// Hides the join and spectate codes of a game from users who were not given them.
+fn without_join_code(mut game: GameDto) -> GameDto {
+    game.join_code = None;
+    game.spectate_code = None;
+    game
+}
+
+/// Maps the game given back to a user, without its join and spectate codes.
+fn to_game_response(game: Result<Game, GameServiceError>) -> Result<Json<GameDto>, Status> {
+    to_host_game_response(game).map(|game| Json(without_join_code(game.into_inner())))
+}
+
+/// Maps the game given back to its host, with its join and spectate codes.
+fn to_host_game_response(game: Result<Game, GameServiceError>) -> Result<Json<GameDto>, Status> {
+    match game {
+        Ok(game) => Ok(Json(game_mapper::to_dto(game))),
+        Err(err) => Err(process_service_error(err)),
+    }
+}
+
 fn process_service_error(error: GameServiceError) -> Status {
     error!("Error: {}", error.message);
     match error.kind {
         GameServiceErrorKind::NotFound => Status::NotFound,
+        GameServiceErrorKind::InvalidTransition => Status::Conflict,
+        GameServiceErrorKind::InvalidRequest => Status::BadRequest,
+        GameServiceErrorKind::Conflict => Status::Conflict,
+        GameServiceErrorKind::Forbidden => Status::Forbidden,
         GameServiceErrorKind::Internal => Status::InternalServerError,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::dto::game_dto::GameDto;
+    use crate::dto::game_dto::{GameDto, GameTeamDto, JoinGameDto};
+    use crate::model::duel::DUEL_PLAYERS;
+    use crate::model::game::{GameMode, GameStatus};
+    use crate::model::power_up::PowerUp;
+    use crate::model::question::AuthoredQuestion;
+    use crate::model::user::UserRole;
     use crate::resource::game_resource::{
-        create_game, game_progress_answer, get_game, get_game_answers, get_games, patch_game,
-        start_new_game,
+        abort_game, choose_team, create_game, game_progress_answer, game_register_user, get_game,
+        get_game_answers, get_games, get_scoreboard, join_game, patch_game, pause_game,
+        remove_player, reveal_hint, revoke_join_code, rotate_join_code, spectate_by_code,
+        spectate_game, stop_spectating, toggle_ready, transfer_host, use_power_up,
+        COUNTDOWN_SECONDS,
     };
+    use crate::resource::profile_resource::get_profile;
+    use crate::resource::test_fixtures::user;
+    use crate::security::guard::{AuthenticatedUser, GameHost, Player};
+    use crate::service::game_service::GameService;
+    use crate::service::question_service::QuestionService;
     use log::info;
+    use mongodb::bson::oid::ObjectId;
+    use mongodb::bson::DateTime;
     use rocket::async_test;
+    use rocket::futures::future::join_all;
     use rocket::http::Status;
     use rocket::serde::json::Json;
     use rocket::tokio::task;
@@ -297,6 +1001,40 @@ mod tests {
         let _ = env_logger::builder().is_test(true).try_init();
     }
 
+    fn new_game(is_private: bool) -> GameDto {
+        GameDto {
+            id: None,
+            topics: vec!["Java".to_string()],
+            question_number: 10,
+            is_private,
+            join_code: None,
+            password: None,
+            spectate_code: None,
+            status: GameStatus::Lobby,
+            status_history: vec![],
+            mode: Default::default(),
+            creator: Some("bob".to_string()),
+            users: vec![],
+            lobby_settings: Default::default(),
+            ready_users: vec![],
+            spectator_count: 0,
+            host_actions: vec![],
+            standings: vec![],
+            eliminations: vec![],
+            hit_points: vec![],
+            damages: vec![],
+            buzzes: vec![],
+            miss_limit: None,
+            power_ups: false,
+            used_power_ups: vec![],
+            hint_penalty: None,
+            revealed_hints: vec![],
+            teams: vec![],
+            team_scoring: Default::default(),
+            team_standings: vec![],
+        }
+    }
+
     #[async_test]
     #[serial]
     async fn create_game_should_insert_game_entity_and_return_created_game() {
@@ -308,21 +1046,16 @@ mod tests {
         let uri = format!("mongodb://localhost:{}", port);
         env::set_var("MONGO_URI", uri.clone());
         info!("Mongo container created");
-        let new_game = GameDto {
-            id: None,
-            topics: vec!["Java".to_string()],
-            question_number: 10,
-            is_private: false,
-            is_started: false,
-            creator: Some("bob".to_string()),
-            users: vec![],
-        };
-        let game_created = create_game(Json(new_game)).await.unwrap().into_inner();
-        assert_eq!(game_created.id.is_some(), true);
+        let game_created = create_game(Json(new_game(false)), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(game_created.id.is_some());
         assert_eq!(game_created.topics, vec!["Java"]);
         assert_eq!(game_created.question_number, 10);
-        assert_eq!(game_created.is_private, false);
-        assert_eq!(game_created.is_started, false);
+        assert!(!game_created.is_private);
+        assert_eq!(game_created.status, GameStatus::Lobby);
+        assert_eq!(game_created.status_history.len(), 1);
         assert_eq!(game_created.creator, Some("bob".to_string()));
 
         //Verify that the game was inserted in the DB
@@ -330,10 +1063,55 @@ mod tests {
             .await
             .unwrap()
             .into_inner();
-        assert_eq!(game_db.id.is_some(), true);
+        assert!(game_db.id.is_some());
         assert_eq!(game_db.topics, vec!["Java"]);
         assert_eq!(game_db.question_number, 10);
-        assert_eq!(game_db.is_private, false);
+        assert!(!game_db.is_private);
+        assert_eq!(game_db.status, GameStatus::Lobby);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn create_game_should_ignore_client_status() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.status = GameStatus::Finished;
+        let game_created = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(game_created.status, GameStatus::Lobby);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn create_game_should_reject_topics_without_questions() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.topics = vec!["Java".to_string(), "Cobol".to_string()];
+        let error = create_game(Json(game.clone()), GameHost(user("bob")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        game.topics = vec![];
+        let error = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
     }
 
     #[async_test]
@@ -381,7 +1159,7 @@ mod tests {
         let uri = format!("mongodb://localhost:{}", port);
         env::set_var("MONGO_URI", uri.clone());
         info!("Mongo container created");
-        let games = get_games().await.unwrap().into_inner();
+        let games = get_games(vec![]).await.unwrap().into_inner();
         assert_eq!(games.len(), 0);
     }
 
@@ -396,27 +1174,18 @@ mod tests {
         let uri = format!("mongodb://localhost:{}", port);
         env::set_var("MONGO_URI", uri.clone());
         info!("Mongo container created");
-        let new_game = GameDto {
-            id: None,
-            topics: vec!["Java".to_string()],
-            question_number: 10,
-            is_private: false,
-            is_started: false,
-            creator: Some("bob".to_string()),
-            users: vec![],
-        };
         info!("Creating game 1");
-        let _ = create_game(Json(new_game.clone())).await;
+        let _ = create_game(Json(new_game(false)), GameHost(user("bob"))).await;
         info!("Creating game 2");
-        let _ = create_game(Json(new_game.clone())).await;
+        let _ = create_game(Json(new_game(false)), GameHost(user("bob"))).await;
         info!("Get games");
-        let games = get_games().await.unwrap().into_inner();
+        let games = get_games(vec![]).await.unwrap().into_inner();
         assert_eq!(games.len(), 2);
     }
 
     #[async_test]
     #[serial]
-    async fn get_games_should_return_only_public_and_not_started_games() {
+    async fn get_games_should_return_only_public_and_lobby_games_by_default() {
         init();
         info!("Creating mongo container");
         let docker = Cli::default();
@@ -425,53 +1194,30 @@ mod tests {
         let uri = format!("mongodb://localhost:{}", port);
         env::set_var("MONGO_URI", uri.clone());
         info!("Mongo container created");
-        let new_game = GameDto {
-            id: None,
-            topics: vec!["Java".to_string()],
-            question_number: 10,
-            is_private: false,
-            is_started: false,
-            creator: Some("bob".to_string()),
-            users: vec![],
-        };
-        let new_game_private = GameDto {
-            id: None,
-            topics: vec!["Java".to_string()],
-            question_number: 10,
-            is_private: true,
-            is_started: false,
-            creator: Some("bob".to_string()),
-            users: vec![],
-        };
-        let new_game_started = GameDto {
-            id: None,
-            topics: vec!["Java".to_string()],
-            question_number: 10,
-            is_private: false,
-            is_started: true,
-            creator: Some("bob".to_string()),
-            users: vec![],
-        };
         info!("Creating game 1");
-        let _ = create_game(Json(new_game.clone())).await;
+        let _ = create_game(Json(new_game(false)), GameHost(user("bob"))).await;
         info!("Creating game 2");
-        let _ = create_game(Json(new_game.clone())).await;
+        let _ = create_game(Json(new_game(false)), GameHost(user("bob"))).await;
         info!("Creating game 3");
-        let _ = create_game(Json(new_game_private.clone())).await;
+        let _ = create_game(Json(new_game(true)), GameHost(user("bob"))).await;
         info!("Creating game 4");
-        let _ = create_game(Json(new_game_private.clone())).await;
+        let _ = create_game(Json(new_game(true)), GameHost(user("bob"))).await;
         info!("Creating game 5");
-        let _ = create_game(Json(new_game.clone())).await;
+        let _ = create_game(Json(new_game(false)), GameHost(user("bob"))).await;
         info!("Creating game 6");
-        let _ = create_game(Json(new_game_started.clone())).await;
+        let started = create_game(Json(new_game(false)), GameHost(user("bob")))
+            .await
+            .unwrap();
+        let _ = game_register_user(started.id.clone().unwrap(), Player(user("alice"))).await;
+        let _ = patch_game(started.id.clone().unwrap(), user("bob")).await;
         info!("Get games");
-        let games = get_games().await.unwrap().into_inner();
+        let games = get_games(vec![]).await.unwrap().into_inner();
         assert_eq!(games.len(), 3);
     }
 
     #[async_test]
     #[serial]
-    async fn patch_game_should_modify_existing_game() {
+    async fn get_games_should_filter_games_by_status() {
         init();
         info!("Creating mongo container");
         let docker = Cli::default();
@@ -480,34 +1226,73 @@ mod tests {
         let uri = format!("mongodb://localhost:{}", port);
         env::set_var("MONGO_URI", uri.clone());
         info!("Mongo container created");
-        let new_game = GameDto {
-            id: None,
-            topics: vec!["Java".to_string()],
-            question_number: 10,
-            is_private: false,
-            is_started: false,
-            creator: Some("bob".to_string()),
-            users: vec![],
-        };
-        let game_created = create_game(Json(new_game)).await.unwrap().into_inner();
-        assert_eq!(game_created.is_started, false);
-
-        //Update the game
-        let game_id = game_created.id.expect("Failed to get game id");
-        let _ = GameDto {
-            id: Some(game_id.clone()),
-            topics: vec!["Java".to_string()],
-            question_number: 10,
-            is_private: false,
-            is_started: true,
-            creator: Some("bob".to_string()),
-            users: vec![],
-        };
-        let _ = patch_game(game_id.clone()).await;
-
-        //Verify that the game was inserted in the DB
+        let _ = create_game(Json(new_game(false)), GameHost(user("bob"))).await;
+        let started = create_game(Json(new_game(false)), GameHost(user("bob")))
+            .await
+            .unwrap();
+        let _ = game_register_user(started.id.clone().unwrap(), Player(user("alice"))).await;
+        let _ = patch_game(started.id.clone().unwrap(), user("bob")).await;
+        let games = get_games(vec![GameStatus::Countdown])
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].id, started.id);
+        let games = get_games(vec![GameStatus::Lobby, GameStatus::Countdown])
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(games.len(), 2);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn patch_game_should_start_the_countdown() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let game_created = create_game(Json(new_game(false)), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(game_created.status, GameStatus::Lobby);
+
+        //Start the game
+        let game_id = game_created.id.expect("Failed to get game id");
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+
+        //Verify that the game status was updated in the DB
         let game_db = get_game(game_id).await.unwrap().into_inner();
-        assert_eq!(game_db.is_started, true);
+        assert_eq!(game_db.status, GameStatus::Countdown);
+        assert_eq!(game_db.status_history.len(), 2);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn patch_game_should_reject_a_game_already_started() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let game_created = create_game(Json(new_game(false)), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game_created.id.expect("Failed to get game id");
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        let error = patch_game(game_id, user("bob")).await.unwrap_err();
+        assert_eq!(error, Status::Conflict);
     }
 
     #[async_test]
@@ -521,26 +1306,947 @@ mod tests {
         let uri = format!("mongodb://localhost:{}", port);
         env::set_var("MONGO_URI", uri.clone());
         info!("Mongo container created");
-        let new_game = GameDto {
+        let mut game = new_game(false);
+        game.question_number = 1;
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let _ = game_register_user(game.id.clone().unwrap(), Player(user("alice"))).await;
+        let _ = patch_game(game.id.clone().unwrap(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        game_progress_answer(game.id.clone().unwrap(), 2, None, Player(user("bob"))).await;
+        game_progress_answer(game.id.clone().unwrap(), 1, None, Player(user("bob"))).await;
+        sleep(Duration::from_secs(21));
+        let answers = get_game_answers(game.id.clone().unwrap())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(answers.len(), 1);
+        let game_db = get_game(game.id.unwrap()).await.unwrap().into_inner();
+        assert_eq!(game_db.status, GameStatus::Finished);
+        let status =
+            game_progress_answer("not-an-id".to_string(), 1, None, Player(user("bob"))).await;
+        assert_eq!(status, Status::NotFound);
+        let status =
+            game_progress_answer(ObjectId::new().to_hex(), 1, None, Player(user("bob"))).await;
+        assert_eq!(status, Status::NotFound);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn elimination_game_should_finish_with_the_last_player_standing() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.question_number = 3;
+        game.mode = GameMode::Elimination;
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(game.mode, GameMode::Elimination);
+        let game_id = game.id.unwrap();
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        let progress = GameService::init()
+            .await
+            .get_game_progress(game_id.clone())
+            .await
+            .unwrap();
+        let correct_answer = progress.question_content.good_answer_number;
+        game_progress_answer(game_id.clone(), correct_answer, None, Player(user("bob"))).await;
+        game_progress_answer(
+            game_id.clone(),
+            correct_answer % 4 + 1,
+            None,
+            Player(user("alice")),
+        )
+        .await;
+        let error = get_game_answers(game_id.clone()).await.unwrap_err();
+        assert_eq!(error, Status::Locked);
+        sleep(Duration::from_secs(21));
+        let answers = get_game_answers(game_id.clone())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(answers.len(), 2);
+        let game_db = get_game(game_id).await.unwrap().into_inner();
+        assert_eq!(game_db.status, GameStatus::Finished);
+        assert_eq!(game_db.eliminations.len(), 1);
+        assert_eq!(game_db.eliminations[0].user, "alice");
+        assert_eq!(game_db.eliminations[0].question_index, 0);
+        assert_eq!(game_db.standings[0].user, "bob");
+        assert_eq!(game_db.standings[1].rank, 2);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn duel_should_hurt_the_slower_player() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.question_number = 1;
+        game.mode = GameMode::Duel;
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(game.lobby_settings.max_players, Some(DUEL_PLAYERS));
+        let game_id = game.id.unwrap();
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let error = game_register_user(game_id.clone(), Player(user("carol")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        let progress = GameService::init()
+            .await
+            .get_game_progress(game_id.clone())
+            .await
+            .unwrap();
+        let correct_answer = progress.question_content.good_answer_number;
+        game_progress_answer(game_id.clone(), correct_answer, None, Player(user("bob"))).await;
+        game_progress_answer(
+            game_id.clone(),
+            correct_answer % 4 + 1,
+            None,
+            Player(user("alice")),
+        )
+        .await;
+        sleep(Duration::from_secs(21));
+        let game_db = get_game(game_id).await.unwrap().into_inner();
+        assert_eq!(game_db.status, GameStatus::Finished);
+        assert_eq!(game_db.damages.len(), 2);
+        assert!(game_db.damages.iter().all(|damage| damage.user == "alice"));
+        assert_eq!(game_db.standings[0].user, "bob");
+    }
+
+    #[async_test]
+    #[serial]
+    async fn team_game_should_balance_players_and_rank_teams() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.question_number = 1;
+        game.mode = GameMode::Team;
+        let error = create_game(Json(game.clone()), GameHost(user("bob")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        game.teams = ["Sales", "IT"]
+            .iter()
+            .map(|name| GameTeamDto {
+                name: name.to_string(),
+                users: vec![],
+            })
+            .collect();
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let lobby = game_register_user(game_id.clone(), Player(user("carol")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(lobby.teams[0].users, vec!["bob", "carol"]);
+        assert_eq!(lobby.teams[1].users, vec!["alice"]);
+        let error = choose_team(game_id.clone(), "HR".to_string(), Player(user("carol")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let game = choose_team(game_id.clone(), "IT".to_string(), Player(user("carol")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(game.teams[1].users, vec!["alice", "carol"]);
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        let progress = GameService::init()
+            .await
+            .get_game_progress(game_id.clone())
+            .await
+            .unwrap();
+        let correct_answer = progress.question_content.good_answer_number;
+        game_progress_answer(game_id.clone(), correct_answer, None, Player(user("alice"))).await;
+        let scoreboard = get_scoreboard(game_id.clone()).await.unwrap().into_inner();
+        assert_eq!(scoreboard.standings[0].user, "alice");
+        assert_eq!(scoreboard.team_standings[0].team, "IT");
+        sleep(Duration::from_secs(21));
+        let game_db = get_game(game_id).await.unwrap().into_inner();
+        assert_eq!(game_db.status, GameStatus::Finished);
+        assert_eq!(game_db.standings.len(), 3);
+        assert_eq!(game_db.team_standings[0].team, "IT");
+        assert_eq!(game_db.team_standings[0].score, 1.0);
+        assert_eq!(game_db.team_standings[1].rank, 2);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn buzzer_game_should_end_the_question_on_the_first_correct_answer() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.question_number = 1;
+        game.mode = GameMode::Buzzer;
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let _ = game_register_user(game_id.clone(), Player(user("carol"))).await;
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        let progress = GameService::init()
+            .await
+            .get_game_progress(game_id.clone())
+            .await
+            .unwrap();
+        let correct_answer = progress.question_content.good_answer_number;
+        game_progress_answer(
+            game_id.clone(),
+            correct_answer % 4 + 1,
+            None,
+            Player(user("alice")),
+        )
+        .await;
+        let status =
+            game_progress_answer(game_id.clone(), correct_answer, None, Player(user("dave"))).await;
+        assert_eq!(status, Status::Forbidden);
+        game_progress_answer(game_id.clone(), correct_answer, None, Player(user("alice"))).await;
+        game_progress_answer(game_id.clone(), correct_answer, None, Player(user("carol"))).await;
+        game_progress_answer(game_id.clone(), correct_answer, None, Player(user("bob"))).await;
+        sleep(Duration::from_secs(3));
+        let game_db = get_game(game_id).await.unwrap().into_inner();
+        assert_eq!(game_db.status, GameStatus::Finished);
+        let buzzers: Vec<(&str, bool)> = game_db
+            .buzzes
+            .iter()
+            .map(|buzz| (buzz.user.as_str(), buzz.correct))
+            .collect();
+        assert_eq!(buzzers, vec![("alice", false), ("carol", true)]);
+        assert_eq!(game_db.standings[0].user, "carol");
+        assert_eq!(game_db.standings[0].score, 1);
+        assert_eq!(game_db.standings[1].rank, 2);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn wager_game_should_score_the_stakes_of_the_players() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.question_number = 1;
+        game.mode = GameMode::Wager;
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        let progress = GameService::init()
+            .await
+            .get_game_progress(game_id.clone())
+            .await
+            .unwrap();
+        let correct_answer = progress.question_content.good_answer_number;
+        let status =
+            game_progress_answer(game_id.clone(), correct_answer, None, Player(user("bob"))).await;
+        assert_eq!(status, Status::BadRequest);
+        let status = game_progress_answer(
+            game_id.clone(),
+            correct_answer,
+            Some(11),
+            Player(user("bob")),
+        )
+        .await;
+        assert_eq!(status, Status::BadRequest);
+        let status = game_progress_answer(
+            game_id.clone(),
+            correct_answer,
+            Some(3),
+            Player(user("bob")),
+        )
+        .await;
+        assert_eq!(status, Status::Ok);
+        let wrong_answer = correct_answer % 4 + 1;
+        game_progress_answer(
+            game_id.clone(),
+            wrong_answer,
+            Some(10),
+            Player(user("alice")),
+        )
+        .await;
+        let scoreboard = get_scoreboard(game_id.clone()).await.unwrap().into_inner();
+        assert_eq!(scoreboard.standings[0].user, "bob");
+        assert_eq!(scoreboard.standings[0].score, 13);
+        assert_eq!(scoreboard.standings[1].score, 0);
+        sleep(Duration::from_secs(21));
+        let answers = get_game_answers(game_id).await.unwrap().into_inner();
+        assert!(answers.iter().all(|answer| answer.wager.is_some()));
+    }
+
+    #[async_test]
+    #[serial]
+    async fn power_ups_should_be_validated_and_recorded_with_the_answers() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.question_number = 1;
+        game.power_ups = true;
+        game.mode = GameMode::Wager;
+        let error = create_game(Json(game.clone()), GameHost(user("bob")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        game.mode = GameMode::Classic;
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(game.power_ups);
+        let game_id = game.id.unwrap();
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        let progress = GameService::init()
+            .await
+            .get_game_progress(game_id.clone())
+            .await
+            .unwrap();
+        let correct_answer = progress.question_content.good_answer_number;
+        let used = use_power_up(
+            game_id.clone(),
+            PowerUp::DoublePoints,
+            None,
+            Player(user("bob")),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+        assert_eq!(used.question_index, 0);
+        let error = use_power_up(
+            game_id.clone(),
+            PowerUp::ExtraTime,
+            None,
+            Player(user("bob")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+        let error = use_power_up(
+            game_id.clone(),
+            PowerUp::Freeze,
+            Some("alice".to_string()),
+            Player(user("alice")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let used = use_power_up(
+            game_id.clone(),
+            PowerUp::FiftyFifty,
+            None,
+            Player(user("alice")),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+        assert_eq!(used.removed_answers.len(), 2);
+        assert!(!used.removed_answers.contains(&correct_answer));
+        game_progress_answer(game_id.clone(), correct_answer, None, Player(user("bob"))).await;
+        game_progress_answer(game_id.clone(), correct_answer, None, Player(user("alice"))).await;
+        let scoreboard = get_scoreboard(game_id.clone()).await.unwrap().into_inner();
+        assert_eq!(scoreboard.standings[0].user, "bob");
+        assert_eq!(scoreboard.standings[0].score, 2);
+        assert_eq!(scoreboard.standings[1].score, 1);
+        let game_db = get_game(game_id.clone()).await.unwrap().into_inner();
+        assert_eq!(game_db.used_power_ups.len(), 2);
+        assert!(game_db.used_power_ups[1].removed_answers.is_empty());
+        sleep(Duration::from_secs(21));
+        let answers = get_game_answers(game_id).await.unwrap().into_inner();
+        let bob_answer = answers.iter().find(|answer| answer.user == "bob").unwrap();
+        assert_eq!(bob_answer.power_up, Some(PowerUp::DoublePoints));
+    }
+
+    #[async_test]
+    #[serial]
+    async fn hints_should_be_revealed_to_the_player_and_cost_points() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let question = AuthoredQuestion {
             id: None,
-            topics: vec!["Java".to_string()],
-            question_number: 1,
-            is_private: false,
-            is_started: true,
-            creator: Some("bob".to_string()),
-            users: vec![],
+            author: "carol".to_string(),
+            question_text: "In Go, which keyword starts a goroutine ?".to_string(),
+            answer_1: "go".to_string(),
+            answer_2: "async".to_string(),
+            answer_3: "spawn".to_string(),
+            answer_4: "thread".to_string(),
+            good_answer_number: 1,
+            topic: "Go".to_string(),
+            hints: vec!["It is the name of the language".to_string()],
+            created_at: DateTime::now(),
         };
-        let game = create_game(Json(new_game)).await.unwrap().into_inner();
-        let id_clone = game.id.clone().unwrap();
-        task::spawn(async move { start_new_game(id_clone.clone()).await });
-        sleep(Duration::from_millis(100));
-        game_progress_answer(game.id.clone().unwrap(), 2, game.creator.clone().unwrap()).await;
-        game_progress_answer(game.id.clone().unwrap(), 1, game.creator.unwrap()).await;
+        let _ = QuestionService::init()
+            .await
+            .create_question(question)
+            .await;
+        let mut game = new_game(false);
+        game.topics = vec!["Go".to_string()];
+        game.question_number = 1;
+        game.hint_penalty = Some(11);
+        let error = create_game(Json(game.clone()), GameHost(user("bob")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        game.hint_penalty = Some(2);
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let error = reveal_hint(game_id.clone(), Player(user("bob")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        let hint = reveal_hint(game_id.clone(), Player(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            hint.hint,
+            Some("It is the name of the language".to_string())
+        );
+        assert_eq!(hint.penalty, 2);
+        let error = reveal_hint(game_id.clone(), Player(user("bob")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let error = reveal_hint(game_id.clone(), Player(user("dave")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+        game_progress_answer(game_id.clone(), 1, None, Player(user("bob"))).await;
+        game_progress_answer(game_id.clone(), 1, None, Player(user("alice"))).await;
+        let scoreboard = get_scoreboard(game_id.clone()).await.unwrap().into_inner();
+        assert_eq!(scoreboard.standings[0].user, "alice");
+        assert_eq!(scoreboard.standings[0].score, 1);
+        assert_eq!(scoreboard.standings[1].score, -1);
+        let game_db = get_game(game_id).await.unwrap().into_inner();
+        assert_eq!(game_db.revealed_hints.len(), 1);
+        assert_eq!(game_db.revealed_hints[0].hint, None);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn spectators_should_watch_private_games_without_answering() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let game = create_game(Json(new_game(true)), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let join_code = game.join_code.expect("Failed to get join code");
+        let spectate_code = game.spectate_code.expect("Failed to get spectate code");
+        assert_ne!(join_code, spectate_code);
+        let join = JoinGameDto { password: None };
+        let _ = join_game(join_code, Json(join), Player(user("alice"))).await;
+        let error = spectate_game(game_id.clone(), Player(user("carol")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+        let watched = spectate_by_code(spectate_code.to_lowercase(), Player(user("carol")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(watched.spectator_count, 1);
+        assert_eq!(watched.join_code, None);
+        let error = spectate_by_code(spectate_code.clone(), Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        let late = spectate_by_code(spectate_code.clone(), Player(user("dave")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(late.spectator_count, 2);
+        let status = game_progress_answer(game_id.clone(), 1, None, Player(user("carol"))).await;
+        assert_eq!(status, Status::Forbidden);
+        let status = game_progress_answer(game_id.clone(), 1, None, Player(user("erin"))).await;
+        assert_eq!(status, Status::Forbidden);
+        let game_db = get_game(game_id.clone()).await.unwrap().into_inner();
+        assert_eq!(game_db.spectate_code, None);
+        assert_eq!(game_db.users, vec!["bob", "alice"]);
+        let left = stop_spectating(game_id.clone(), Player(user("dave")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(left.spectator_count, 1);
+        let error = stop_spectating(game_id.clone(), Player(user("dave")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let _ = abort_game(game_id.clone(), user("bob")).await;
+        let error = spectate_by_code(spectate_code, Player(user("erin")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn survival_game_should_go_on_until_the_miss_limit() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.mode = GameMode::Survival;
+        game.lobby_settings.min_players = 1;
+        game.miss_limit = Some(1);
+        game.topics = vec!["Java".to_string(), "Rust".to_string()];
+        let error = create_game(Json(game.clone()), GameHost(user("bob")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        game.topics = vec!["Java".to_string()];
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(game.question_number, i8::MAX);
+        assert_eq!(game.miss_limit, Some(1));
+        let game_id = game.id.unwrap();
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        let service = GameService::init().await;
+        let progress = service.get_game_progress(game_id.clone()).await.unwrap();
+        let correct_answer = progress.question_content.good_answer_number;
+        game_progress_answer(game_id.clone(), correct_answer, None, Player(user("bob"))).await;
         sleep(Duration::from_secs(21));
-        let answers = get_game_answers(game.id.unwrap())
+        let progress = service.get_game_progress(game_id.clone()).await.unwrap();
+        assert_eq!(progress.current_question, 1);
+        let correct_answer = progress.question_content.good_answer_number;
+        game_progress_answer(
+            game_id.clone(),
+            correct_answer % 4 + 1,
+            None,
+            Player(user("bob")),
+        )
+        .await;
+        sleep(Duration::from_secs(21));
+        let game_db = get_game(game_id.clone()).await.unwrap().into_inner();
+        assert_eq!(game_db.status, GameStatus::Finished);
+        assert_eq!(game_db.eliminations[0].question_index, 1);
+        assert_eq!(game_db.standings[0].score, 1);
+        let answers = get_game_answers(game_id).await.unwrap().into_inner();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0].question_index, 0);
+        assert_eq!(answers[1].question_index, 1);
+        assert!(answers.iter().all(|answer| answer.answer_seconds.is_some()));
+        let profile = get_profile("bob".to_string()).await.unwrap().into_inner();
+        assert_eq!(profile.survival_bests[0].topic, "Java");
+        assert_eq!(profile.survival_bests[0].score, 1);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn host_controls_should_be_forbidden_to_other_players() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let game = create_game(Json(new_game(false)), GameHost(user("bob")))
             .await
             .unwrap()
             .into_inner();
-        assert_eq!(answers.len(), 1);
+        let game_id = game.id.unwrap();
+        let error = patch_game(game_id.clone(), user("alice"))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+        let error = abort_game(game_id.clone(), user("alice"))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+        let game_db = get_game(game_id).await.unwrap().into_inner();
+        assert_eq!(game_db.status, GameStatus::Lobby);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn moderators_should_control_any_game() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let game = create_game(Json(new_game(false)), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let moderator = AuthenticatedUser {
+            roles: vec![UserRole::Moderator],
+            ..user("carol")
+        };
+        let game = abort_game(game.id.unwrap(), moderator)
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(game.status, GameStatus::Aborted);
+        assert_eq!(game.host_actions[0].host, "carol");
+    }
+
+    #[async_test]
+    #[serial]
+    async fn abort_game_should_stop_a_running_game() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let game = create_game(Json(new_game(false)), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let _ = patch_game(game_id.clone(), user("bob")).await;
+        sleep(Duration::from_millis(COUNTDOWN_SECONDS * 1000 + 100));
+        let paused = pause_game(game_id.clone(), user("bob"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(paused.status, GameStatus::Paused);
+        let aborted = abort_game(game_id.clone(), user("bob"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(aborted.status, GameStatus::Aborted);
+        assert_eq!(aborted.host_actions.len(), 3);
+        let error = pause_game(game_id, user("bob")).await.unwrap_err();
+        assert_eq!(error, Status::Conflict);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn kick_player_should_prevent_the_player_from_joining_again() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let game = create_game(Json(new_game(false)), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let _ = remove_player(game_id.clone(), "alice".to_string(), user("bob")).await;
+        let error = game_register_user(game_id.clone(), Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let game_db = get_game(game_id.clone()).await.unwrap().into_inner();
+        assert_eq!(game_db.users, vec!["bob"]);
+        let error = transfer_host(game_id, "alice".to_string(), user("bob"))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn lobby_should_respect_capacity_and_ready_check() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.lobby_settings.max_players = Some(2);
+        game.lobby_settings.ready_check = true;
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let error = patch_game(game_id.clone(), user("bob")).await.unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let error = game_register_user(game_id.clone(), Player(user("carol")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let _ = toggle_ready(game_id.clone(), Player(user("alice"))).await;
+        let error = patch_game(game_id.clone(), user("bob")).await.unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let lobby = toggle_ready(game_id.clone(), Player(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(lobby.ready_users, vec!["alice", "bob"]);
+        let started = patch_game(game_id, user("bob")).await.unwrap().into_inner();
+        assert_eq!(started.status, GameStatus::Countdown);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn remove_player_should_hand_the_host_role_over_when_the_host_leaves() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let game = create_game(Json(new_game(false)), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let _ = game_register_user(game_id.clone(), Player(user("alice"))).await;
+        let game = remove_player(game_id.clone(), "bob".to_string(), user("bob"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(game.creator, Some("alice".to_string()));
+        let game = remove_player(game_id, "alice".to_string(), user("alice"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(game.status, GameStatus::Aborted);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn join_game_should_register_players_of_private_games_by_code() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(true);
+        game.password = Some("secret".to_string());
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let code = game.join_code.expect("Failed to get join code");
+        assert!(get_game(game_id.clone())
+            .await
+            .unwrap()
+            .into_inner()
+            .join_code
+            .is_none());
+        let error = game_register_user(game_id.clone(), Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+        let join = |password: &str| {
+            Json(JoinGameDto {
+                password: Some(password.to_string()),
+            })
+        };
+        let error = join_game(code.clone(), join("wrong"), Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+        let joined = join_game(code.to_lowercase(), join("secret"), Player(user("alice")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(joined.users, vec!["bob", "alice"]);
+
+        //Rotate then revoke the join code
+        let rotated = rotate_join_code(game_id.clone(), user("bob"))
+            .await
+            .unwrap()
+            .into_inner();
+        let new_code = rotated.join_code.expect("Failed to get join code");
+        let error = join_game(code, join("secret"), Player(user("carol")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::NotFound);
+        let _ = revoke_join_code(game_id, user("bob")).await;
+        let error = join_game(new_code, join("secret"), Player(user("carol")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::NotFound);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn private_game_codes_should_only_be_given_back_to_the_host() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(true);
+        game.mode = GameMode::Team;
+        game.teams = ["Sales", "IT"]
+            .iter()
+            .map(|name| GameTeamDto {
+                name: name.to_string(),
+                users: vec![],
+            })
+            .collect();
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let code = game.join_code.expect("Failed to get join code");
+        assert!(game.spectate_code.is_some());
+        let joined = join_game(
+            code,
+            Json(JoinGameDto { password: None }),
+            Player(user("alice")),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+        let ready = toggle_ready(game_id.clone(), Player(user("alice")))
+            .await
+            .unwrap()
+            .into_inner();
+        let team = choose_team(game_id.clone(), "Sales".to_string(), Player(user("alice")))
+            .await
+            .unwrap()
+            .into_inner();
+        for game in [joined, ready, team] {
+            assert_eq!(game.join_code, None);
+            assert_eq!(game.spectate_code, None);
+        }
+        let rotated = rotate_join_code(game_id, user("bob"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(rotated.join_code.is_some());
+        assert!(rotated.spectate_code.is_some());
+    }
+
+    #[async_test]
+    #[serial]
+    async fn game_register_user_should_not_lose_players_joining_concurrently() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let mut game = new_game(false);
+        game.lobby_settings.max_players = Some(20);
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let game_id = game.id.unwrap();
+        let registrations = (0..30).map(|index| {
+            let game_id = game_id.clone();
+            task::spawn(async move {
+                game_register_user(game_id, Player(user(&format!("player{}", index)))).await
+            })
+        });
+        let results = join_all(registrations).await;
+        let registered = results
+            .into_iter()
+            .filter(|result| matches!(result, Ok(Ok(_))))
+            .count();
+        assert_eq!(registered, 19);
+        let game_db = get_game(game_id).await.unwrap().into_inner();
+        assert_eq!(game_db.users.len(), 20);
     }
 }
@@ -0,0 +1,167 @@
+use crate::dto::leaderboard_dto::LeaderboardDto;
+use crate::errors::leaderboard_service_error::{
+    LeaderboardServiceError, LeaderboardServiceErrorKind,
+};
+use crate::mapper::leaderboard_mapper;
+use crate::security::guard::AuthenticatedUser;
+use crate::service::leaderboard_service::LeaderboardService;
+use crate::service::rating_service::DEFAULT_PAGE_SIZE;
+use log::{debug, error};
+use rocket::get;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+
+/// GET request to get a page of a leaderboard, players being ranked by their number of correct answers.
+/// The leaderboard is all-time and for every topic unless a `season` or a `topic` is asked for,
+/// the `current` season being the season in progress. Pages start at 1 and hold 20 players unless another `size` is asked for.
+/// When the request is authenticated, the entry of the player is given too.
+/// Returns an error if the page or its size are not valid.
+/// Returns an error if no season is in progress.
+#[get("/leaderboards?<topic>&<season>&<page>&<size>", format = "json")]
+pub async fn get_leaderboard(
+    topic: Option<String>,
+    season: Option<String>,
+    page: Option<u64>,
+    size: Option<i64>,
+    user: Option<AuthenticatedUser>,
+) -> Result<Json<LeaderboardDto>, Status> {
+    debug!("get_leaderboard resource started");
+    let leaderboard_service = LeaderboardService::init().await;
+    let page = page.unwrap_or(1);
+    let size = size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let leaderboard = leaderboard_service
+        .get_leaderboard(topic, season, page, size, user.map(|user| user.username))
+        .await;
+    let result = match leaderboard {
+        Ok(leaderboard) => Ok(Json(leaderboard_mapper::to_dto(leaderboard, page, size))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_leaderboard resource ending");
+    result
+}
+
+fn process_service_error(error: LeaderboardServiceError) -> Status {
+    error!("Error: {}", error.message);
+    match error.kind {
+        LeaderboardServiceErrorKind::NotFound => Status::NotFound,
+        LeaderboardServiceErrorKind::InvalidRequest => Status::BadRequest,
+        LeaderboardServiceErrorKind::Internal => Status::InternalServerError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::game_dto::GameDto;
+    use crate::model::game::GameStatus;
+    use crate::resource::game_resource::{create_game, game_register_user};
+    use crate::resource::leaderboard_resource::get_leaderboard;
+    use crate::resource::test_fixtures::{answer, user};
+    use crate::security::guard::{GameHost, Player};
+    use crate::service::game_service::GameService;
+    use crate::service::leaderboard_service::LeaderboardService;
+    use log::info;
+    use rocket::async_test;
+    use rocket::serde::json::Json;
+    use serial_test::serial;
+    use std::env;
+    use testcontainers::clients::Cli;
+    use testcontainers::GenericImage;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    /// Plays a finished game of bob against the given player, the player answering the given number of questions right.
+    async fn play_game(player: &str, correct_answers: i8) {
+        let game = GameDto {
+            topics: vec!["Rust".to_string(), "Java".to_string()],
+            question_number: 3,
+            ..Default::default()
+        };
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let id = game.id.unwrap();
+        let _ = game_register_user(id.clone(), Player(user(player))).await;
+        let game_service = GameService::init().await;
+        for status in [GameStatus::Countdown, GameStatus::Running] {
+            game_service
+                .transition_game(id.clone(), status)
+                .await
+                .unwrap();
+        }
+        game_service
+            .save_game_answer(&answer(&id, "bob", 0, true, "Java"))
+            .await;
+        for index in 0..correct_answers {
+            game_service
+                .save_game_answer(&answer(&id, player, index, true, "Rust"))
+                .await;
+        }
+        let game = game_service.finish_game(id).await.unwrap();
+        LeaderboardService::init()
+            .await
+            .record_game(&game)
+            .await
+            .unwrap();
+    }
+
+    #[async_test]
+    #[serial]
+    async fn leaderboards_should_rank_players_by_season_and_topic() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        play_game("alice", 3).await;
+        play_game("carol", 1).await;
+
+        let leaderboard = get_leaderboard(None, None, None, None, Some(user("carol")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(leaderboard.season, "all-time");
+        assert_eq!(leaderboard.topic, "all");
+        let ranking: Vec<(u64, &str, i32)> = leaderboard
+            .entries
+            .iter()
+            .map(|entry| (entry.rank, entry.user.as_str(), entry.score))
+            .collect();
+        assert_eq!(
+            ranking,
+            vec![(1, "alice", 3), (2, "bob", 2), (3, "carol", 1)]
+        );
+        assert_eq!(leaderboard.entries[1].games, 2);
+        assert_eq!(leaderboard.entries[1].wins, 1);
+        assert_eq!(leaderboard.me.unwrap().rank, 3);
+
+        let leaderboard = get_leaderboard(
+            Some("Rust".to_string()),
+            Some("current".to_string()),
+            Some(2),
+            Some(1),
+            Some(user("alice")),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+        assert_ne!(leaderboard.season, "all-time");
+        assert_eq!(leaderboard.entries.len(), 1);
+        assert_eq!(leaderboard.entries[0].user, "carol");
+        assert_eq!(leaderboard.entries[0].rank, 2);
+        assert_eq!(leaderboard.me.unwrap().rank, 1);
+
+        let leaderboard = get_leaderboard(Some("Java".to_string()), None, None, None, None)
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(leaderboard.entries.len(), 1);
+        assert_eq!(leaderboard.entries[0].user, "bob");
+        assert!(leaderboard.me.is_none());
+    }
+}
@@ -0,0 +1,204 @@
+use crate::dto::matchmaking_dto::{MatchmakingRequestDto, TicketDto};
+use crate::errors::matchmaking_service_error::{
+    MatchmakingServiceError, MatchmakingServiceErrorKind,
+};
+use crate::mapper::matchmaking_mapper;
+use crate::model::matchmaking::TicketStatus;
+use crate::security::guard::{AuthenticatedUser, Player};
+use crate::service::matchmaking_service::MatchmakingService;
+use log::{debug, error};
+use mongodb::bson::DateTime;
+use rocket::http::Status;
+use rocket::response::stream::{Event, EventStream};
+use rocket::serde::json::Json;
+use rocket::tokio::time;
+use rocket::{delete, get, post};
+use std::time::Duration;
+
+/// POST request to put the authenticated player in the matchmaking queue, for a game on one of the given topics.
+/// Players are grouped by topic and rating, and the game of the group starts on its own.
+/// Returns the ticket of the player, to follow with the events of the ticket.
+/// Returns an error if no topic is given or if the player is already waiting in the queue.
+#[post("/matchmaking", format = "json", data = "<request>")]
+pub async fn enqueue(
+    request: Json<MatchmakingRequestDto>,
+    player: Player,
+) -> Result<Json<TicketDto>, Status> {
+    debug!("enqueue resource started");
+    let matchmaking_service = MatchmakingService::init().await;
+    let ticket = matchmaking_service
+        .enqueue(player.0.username, request.into_inner().topics)
+        .await;
+    let result = match ticket {
+        Ok(ticket) => Ok(Json(matchmaking_mapper::to_dto(ticket, DateTime::now()))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("enqueue resource ending");
+    result
+}
+
+/// GET request to get a matchmaking ticket.
+/// Returns an error if the ticket does not exist.
+#[get("/matchmaking/<id>", format = "json")]
+pub async fn get_ticket(id: String) -> Result<Json<TicketDto>, Status> {
+    debug!("get_ticket resource started");
+    let matchmaking_service = MatchmakingService::init().await;
+    let result = match matchmaking_service.get_ticket(id).await {
+        Ok(ticket) => Ok(Json(matchmaking_mapper::to_dto(ticket, DateTime::now()))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_ticket resource ending");
+    result
+}
+
+/// GET request to follow a matchmaking ticket.
+/// Returns `ticket` events each second while the player waits, then a last one once the player is matched
+/// with the game to play, or once the ticket is cancelled or expired.
+#[get("/matchmaking/<id>/events")]
+pub async fn ticket_events(id: String) -> EventStream![] {
+    EventStream! {
+        debug!("ticket_events events started");
+        let mut interval = time::interval(Duration::from_secs(1));
+        let matchmaking_service = MatchmakingService::init().await;
+        loop {
+            match matchmaking_service.get_ticket(id.clone()).await {
+                Ok(ticket) => {
+                    let ticket = matchmaking_mapper::to_dto(ticket, DateTime::now());
+                    yield Event::json(&ticket).event("ticket");
+                    let matching = ticket.status == TicketStatus::Waiting
+                        || (ticket.status == TicketStatus::Matched && ticket.game_id.is_none());
+                    if !matching {
+                        break;
+                    }
+                },
+                Err(err) => {
+                    error!("Problem occurred when fetching ticket in sse ticket events: {}", err.message);
+                    break;
+                },
+            }
+            interval.tick().await;
+        }
+        yield Event::data("END");
+    }
+}
+
+/// DELETE request to take the authenticated player out of the matchmaking queue.
+/// Returns an error if the player has no such ticket waiting in the queue.
+#[delete("/matchmaking/<id>")]
+pub async fn cancel_ticket(id: String, user: AuthenticatedUser) -> Result<Status, Status> {
+    debug!("cancel_ticket resource started");
+    let matchmaking_service = MatchmakingService::init().await;
+    let result = match matchmaking_service.cancel(id, user.username).await {
+        Ok(_) => Ok(Status::NoContent),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("cancel_ticket resource ending");
+    result
+}
+
+fn process_service_error(error: MatchmakingServiceError) -> Status {
+    error!("Error: {}", error.message);
+    match error.kind {
+        MatchmakingServiceErrorKind::NotFound => Status::NotFound,
+        MatchmakingServiceErrorKind::InvalidRequest => Status::BadRequest,
+        MatchmakingServiceErrorKind::Conflict => Status::Conflict,
+        MatchmakingServiceErrorKind::Internal => Status::InternalServerError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::matchmaking_dto::MatchmakingRequestDto;
+    use crate::model::game::GameStatus;
+    use crate::model::matchmaking::{TicketStatus, MATCH_PLAYERS};
+    use crate::resource::matchmaking_resource::{cancel_ticket, enqueue, get_ticket};
+    use crate::resource::test_fixtures::user;
+    use crate::security::guard::Player;
+    use crate::service::matchmaking_service::MatchmakingService;
+    use log::info;
+    use rocket::async_test;
+    use rocket::http::Status;
+    use rocket::serde::json::Json;
+    use serial_test::serial;
+    use std::env;
+    use testcontainers::clients::Cli;
+    use testcontainers::GenericImage;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn request(topics: &[&str]) -> Json<MatchmakingRequestDto> {
+        Json(MatchmakingRequestDto {
+            topics: topics.iter().map(|topic| topic.to_string()).collect(),
+        })
+    }
+
+    #[async_test]
+    #[serial]
+    async fn matchmaking_should_group_waiting_players_into_a_started_game() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let matchmaking_service = MatchmakingService::init().await;
+        matchmaking_service.create_indexes().await.unwrap();
+        let error = enqueue(request(&[" "]), Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+
+        let mut tickets = vec![];
+        for index in 0..MATCH_PLAYERS {
+            let ticket = enqueue(
+                request(&["Rust"]),
+                Player(user(&format!("player{}", index))),
+            )
+            .await
+            .unwrap()
+            .into_inner();
+            assert_eq!(ticket.status, TicketStatus::Waiting);
+            assert_eq!(ticket.rating, 1500.0);
+            tickets.push(ticket.id.unwrap());
+        }
+        let error = enqueue(request(&["Rust"]), Player(user("player0")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+        let leaving = enqueue(request(&["Java"]), Player(user("alice")))
+            .await
+            .unwrap()
+            .into_inner()
+            .id
+            .unwrap();
+        let error = cancel_ticket(leaving.clone(), user("bob"))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::NotFound);
+        let status = cancel_ticket(leaving, user("alice")).await.unwrap();
+        assert_eq!(status, Status::NoContent);
+
+        let games = matchmaking_service.match_players().await.unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].status, GameStatus::Countdown);
+        assert!(games[0].is_private);
+        assert_eq!(games[0].topics, vec!["Rust"]);
+        assert_eq!(games[0].users.len(), MATCH_PLAYERS);
+        assert_eq!(games[0].creator, Some("player0".to_string()));
+        let game_id = games[0].id.unwrap().to_string();
+        for id in tickets {
+            let ticket = get_ticket(id).await.unwrap().into_inner();
+            assert_eq!(ticket.status, TicketStatus::Matched);
+            assert_eq!(ticket.game_id, Some(game_id.clone()));
+        }
+        assert!(matchmaking_service
+            .match_players()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
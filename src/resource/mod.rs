@@ -1 +1,12 @@
+pub mod api_key_resource;
+pub mod daily_challenge_resource;
 pub mod game_resource;
+pub mod leaderboard_resource;
+pub mod matchmaking_resource;
+pub mod practice_resource;
+pub mod profile_resource;
+pub mod question_resource;
+pub mod rating_resource;
+#[cfg(test)]
+pub mod test_fixtures;
+pub mod user_resource;
@@ -0,0 +1,231 @@
+use crate::dto::practice_dto::{
+    PracticeFeedbackDto, PracticeRequestDto, PracticeSessionDto, PracticeSummaryDto,
+};
+use crate::errors::practice_service_error::{PracticeServiceError, PracticeServiceErrorKind};
+use crate::mapper::{practice_mapper, question_mapper};
+use crate::model::practice::DEFAULT_PRACTICE_QUESTIONS;
+use crate::resource::game_resource::question_pool;
+use crate::security::guard::Player;
+use crate::service::practice_service::PracticeService;
+use log::{debug, error};
+use mongodb::bson::DateTime;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{get, post};
+
+/// POST request to start a practice session for the authenticated player, on the given topics.
+/// The session starts right away with its first question, asked without time limit unless the session is `timed`.
+/// Returns the session.
+/// Returns an error if no topic is given, if the number of questions is not valid or if there is no question on the topics.
+#[post("/practice", format = "json", data = "<request>")]
+pub async fn start_practice(
+    request: Json<PracticeRequestDto>,
+    player: Player,
+) -> Result<Json<PracticeSessionDto>, Status> {
+    debug!("start_practice resource started");
+    let practice_service = PracticeService::init().await;
+    let request = request.into_inner();
+    let pool = question_pool(&request.topics)
+        .await
+        .into_iter()
+        .map(question_mapper::to_entity)
+        .collect();
+    let session = practice_service
+        .start(
+            player.0.username,
+            request.topics,
+            request.timed.unwrap_or(true),
+            request
+                .question_number
+                .unwrap_or(DEFAULT_PRACTICE_QUESTIONS),
+            pool,
+        )
+        .await;
+    let result = match session {
+        Ok(session) => Ok(Json(practice_mapper::to_dto(session, DateTime::now()))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("start_practice resource ending");
+    result
+}
+
+/// GET request to get a practice session of the authenticated player, with the question to answer.
+/// Returns an error if the session does not exist or belongs to another player.
+/// Returns an error if the user is not allowed to play.
+#[get("/practice/<id>", format = "json")]
+pub async fn get_practice(id: String, player: Player) -> Result<Json<PracticeSessionDto>, Status> {
+    debug!("get_practice resource started");
+    let practice_service = PracticeService::init().await;
+    let result = match practice_service.get_session(id, &player.0.username).await {
+        Ok(session) => Ok(Json(practice_mapper::to_dto(session, DateTime::now()))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_practice resource ending");
+    result
+}
+
+/// POST request to answer the current question of a practice session of the authenticated player.
+/// Returns whether the answer is correct, the correct answer and the session with its next question.
+/// Returns an error if the answer is not between 1 and 4 or if the session is finished.
+/// Returns an error if the session does not exist or belongs to another player.
+/// Returns an error if the user is not allowed to play.
+#[post("/practice/<id>/answer/<answer>")]
+pub async fn answer_practice(
+    id: String,
+    answer: i8,
+    player: Player,
+) -> Result<Json<PracticeFeedbackDto>, Status> {
+    debug!("answer_practice resource started");
+    let practice_service = PracticeService::init().await;
+    let result = match practice_service
+        .answer(id, &player.0.username, answer)
+        .await
+    {
+        Ok(feedback) => Ok(Json(practice_mapper::feedback_to_dto(
+            feedback,
+            DateTime::now(),
+        ))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("answer_practice resource ending");
+    result
+}
+
+/// GET request to get the summary of a practice session of the authenticated player:
+/// accuracy overall and per topic, average answer time of timed sessions and every answer with the correct one.
+/// Returns an error if the session does not exist or belongs to another player.
+/// Returns an error if the user is not allowed to play.
+#[get("/practice/<id>/summary", format = "json")]
+pub async fn get_practice_summary(
+    id: String,
+    player: Player,
+) -> Result<Json<PracticeSummaryDto>, Status> {
+    debug!("get_practice_summary resource started");
+    let practice_service = PracticeService::init().await;
+    let result = match practice_service.get_summary(id, &player.0.username).await {
+        Ok((session, summary)) => Ok(Json(practice_mapper::summary_to_dto(
+            session,
+            summary,
+            DateTime::now(),
+        ))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_practice_summary resource ending");
+    result
+}
+
+fn process_service_error(error: PracticeServiceError) -> Status {
+    error!("Error: {}", error.message);
+    match error.kind {
+        PracticeServiceErrorKind::NotFound => Status::NotFound,
+        PracticeServiceErrorKind::InvalidRequest => Status::BadRequest,
+        PracticeServiceErrorKind::Forbidden => Status::Forbidden,
+        PracticeServiceErrorKind::Conflict => Status::Conflict,
+        PracticeServiceErrorKind::Internal => Status::InternalServerError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::practice_dto::PracticeRequestDto;
+    use crate::resource::practice_resource::{
+        answer_practice, get_practice, get_practice_summary, start_practice,
+    };
+    use crate::resource::test_fixtures::user;
+    use crate::security::guard::Player;
+    use log::info;
+    use rocket::async_test;
+    use rocket::http::Status;
+    use rocket::serde::json::Json;
+    use serial_test::serial;
+    use std::env;
+    use testcontainers::clients::Cli;
+    use testcontainers::GenericImage;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn request(topics: &[&str], question_number: i8) -> Json<PracticeRequestDto> {
+        Json(PracticeRequestDto {
+            topics: topics.iter().map(|topic| topic.to_string()).collect(),
+            question_number: Some(question_number),
+            timed: Some(false),
+        })
+    }
+
+    #[async_test]
+    #[serial]
+    async fn practice_should_give_feedback_and_a_summary() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let error = start_practice(request(&["Cobol"], 3), Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+        let error = start_practice(request(&["Rust"], 0), Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+
+        let session = start_practice(request(&["Rust"], 3), Player(user("alice")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(session.question_number, 3);
+        assert_eq!(session.remaining_time, None);
+        let id = session.id.unwrap();
+        let error = get_practice(id.clone(), Player(user("bob")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+        let error = answer_practice(id.clone(), 5, Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+
+        let mut expected_correct = 0;
+        for index in 0..3 {
+            let session = get_practice(id.clone(), Player(user("alice")))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(session.current_question, index);
+            let correct_answer = session.question.unwrap().good_answer_number;
+            let answer = if index == 1 {
+                correct_answer % 4 + 1
+            } else {
+                expected_correct += 1;
+                correct_answer
+            };
+            let feedback = answer_practice(id.clone(), answer, Player(user("alice")))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(feedback.correct_answer, correct_answer);
+            assert_eq!(feedback.correct, index != 1);
+            assert_eq!(feedback.session.finished, index == 2);
+        }
+        let error = answer_practice(id.clone(), 1, Player(user("alice")))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+
+        let summary = get_practice_summary(id, Player(user("alice")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(summary.answers, 3);
+        assert_eq!(summary.correct_answers, expected_correct);
+        assert_eq!(summary.average_answer_seconds, None);
+        assert_eq!(summary.topics.len(), 1);
+        assert_eq!(summary.results.len(), 3);
+        assert!(!summary.results[1].correct);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::dto::profile_dto::ProfileDto;
+use crate::errors::profile_service_error::{ProfileServiceError, ProfileServiceErrorKind};
+use crate::mapper::profile_mapper;
+use crate::service::profile_service::ProfileService;
+use log::{debug, error};
+use rocket::get;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+
+/// GET request to get the lifetime statistics of a player: games played, wins, accuracy overall and per topic,
+/// average answer time, best streak and recent games.
+/// The statistics are refreshed each time a game of the player finishes.
+/// Returns an error if the player never played and is not a registered user.
+#[get("/users/<username>/profile", format = "json")]
+pub async fn get_profile(username: String) -> Result<Json<ProfileDto>, Status> {
+    debug!("get_profile resource started");
+    let profile_service = ProfileService::init().await;
+    let result = match profile_service.get_profile(username).await {
+        Ok(profile) => Ok(Json(profile_mapper::to_dto(profile))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_profile resource ending");
+    result
+}
+
+fn process_service_error(error: ProfileServiceError) -> Status {
+    error!("Error: {}", error.message);
+    match error.kind {
+        ProfileServiceErrorKind::NotFound => Status::NotFound,
+        ProfileServiceErrorKind::Internal => Status::InternalServerError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::game_dto::GameDto;
+    use crate::model::game::GameStatus;
+    use crate::model::game_answer::GameAnswer;
+    use crate::resource::game_resource::{create_game, game_register_user};
+    use crate::resource::profile_resource::get_profile;
+    use crate::resource::test_fixtures::{answer, user};
+    use crate::security::guard::{GameHost, Player};
+    use crate::service::game_service::GameService;
+    use crate::service::profile_service::ProfileService;
+    use log::info;
+    use rocket::async_test;
+    use rocket::http::Status;
+    use rocket::serde::json::Json;
+    use serial_test::serial;
+    use std::env;
+    use testcontainers::clients::Cli;
+    use testcontainers::GenericImage;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[async_test]
+    #[serial]
+    async fn profiles_should_aggregate_answers_of_finished_games_only() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let game = GameDto {
+            topics: vec!["Java".to_string()],
+            question_number: 3,
+            ..Default::default()
+        };
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let id = game.id.unwrap();
+        let _ = game_register_user(id.clone(), Player(user("alice"))).await;
+        let game_service = GameService::init().await;
+        for status in [GameStatus::Countdown, GameStatus::Running] {
+            game_service
+                .transition_game(id.clone(), status)
+                .await
+                .unwrap();
+        }
+        for (index, correct) in [true, true, false].into_iter().enumerate() {
+            let index = index as i8;
+            game_service
+                .save_game_answer(&answer(&id, "alice", index, correct, "Java"))
+                .await;
+            let timed_answer = GameAnswer {
+                answer_seconds: Some(8),
+                ..answer(&id, "bob", index, index == 0, "Java")
+            };
+            game_service.save_game_answer(&timed_answer).await;
+        }
+        let game = game_service.finish_game(id.clone()).await.unwrap();
+        assert_eq!(game.standings[0].user, "alice");
+        assert_eq!(game.standings[0].score, 2);
+        let aborted = GameDto {
+            topics: vec!["Java".to_string()],
+            question_number: 3,
+            ..Default::default()
+        };
+        let aborted_id = create_game(Json(aborted), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner()
+            .id
+            .unwrap();
+        let _ = game_register_user(aborted_id.clone(), Player(user("alice"))).await;
+        for status in [GameStatus::Countdown, GameStatus::Running] {
+            game_service
+                .transition_game(aborted_id.clone(), status)
+                .await
+                .unwrap();
+        }
+        for index in 0..2 {
+            game_service
+                .save_game_answer(&answer(&aborted_id, "alice", index, false, "Java"))
+                .await;
+        }
+        game_service
+            .transition_game(aborted_id, GameStatus::Aborted)
+            .await
+            .unwrap();
+        ProfileService::init()
+            .await
+            .refresh_profiles(game.users)
+            .await;
+
+        let profile = get_profile("alice".to_string()).await.unwrap().into_inner();
+        assert_eq!(profile.games_played, 1);
+        assert_eq!(profile.wins, 1);
+        assert_eq!(profile.answers, 3);
+        assert_eq!(profile.correct_answers, 2);
+        assert_eq!(profile.best_streak, 2);
+        assert_eq!(profile.average_answer_seconds, 10.0);
+        assert_eq!(profile.topics.len(), 1);
+        assert_eq!(profile.topics[0].topic, "Java");
+        assert_eq!(profile.recent_games.len(), 1);
+        assert_eq!(profile.recent_games[0].game_id, id);
+        assert_eq!(profile.recent_games[0].rank, 1);
+        assert_eq!(profile.recent_games[0].players, 2);
+
+        let profile = get_profile("bob".to_string()).await.unwrap().into_inner();
+        assert_eq!(profile.wins, 0);
+        assert_eq!(profile.average_answer_seconds, 8.0);
+        assert_eq!(profile.recent_games[0].rank, 2);
+
+        let error = get_profile("nobody".to_string()).await.unwrap_err();
+        assert_eq!(error, Status::NotFound);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::dto::question_dto::AuthoredQuestionDto;
+use crate::errors::question_service_error::{QuestionServiceError, QuestionServiceErrorKind};
+use crate::mapper::question_mapper;
+use crate::security::guard::{AuthenticatedUser, QuestionAuthor};
+use crate::service::question_service::QuestionService;
+use log::{debug, error};
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post};
+
+/// POST request to write a new question, asked in the games of its topic.
+/// Returns the created question.
+/// Returns an error if the user is not allowed to write questions or if the question is not valid.
+#[post("/questions", format = "json", data = "<question>")]
+pub async fn create_question(
+    question: Json<AuthoredQuestionDto>,
+    author: QuestionAuthor,
+) -> Result<Json<AuthoredQuestionDto>, Status> {
+    debug!("create_question resource started");
+    let question_service = QuestionService::init().await;
+    let question_entity =
+        question_mapper::authored_to_entity(question.into_inner(), author.0.username);
+    let result = match question_service.create_question(question_entity).await {
+        Ok(question) => Ok(Json(question_mapper::authored_to_dto(question))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("create_question resource ending");
+    result
+}
+
+/// GET request to get the questions the user may manage.
+/// Returns the questions of the user, or all the questions for moderators.
+/// Returns an error if the user is neither a question author nor a moderator.
+#[get("/questions", format = "json")]
+pub async fn get_questions(
+    user: AuthenticatedUser,
+) -> Result<Json<Vec<AuthoredQuestionDto>>, Status> {
+    debug!("get_questions resource started");
+    let question_service = QuestionService::init().await;
+    let result = match question_service.get_questions(&user).await {
+        Ok(questions) => Ok(Json(
+            questions
+                .into_iter()
+                .map(question_mapper::authored_to_dto)
+                .collect(),
+        )),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_questions resource ending");
+    result
+}
+
+/// DELETE request to delete a question.
+/// Returns an error if the question does not exist.
+/// Returns an error if the user is neither the author of the question nor a moderator.
+#[delete("/questions/<id>")]
+pub async fn delete_question(id: String, user: AuthenticatedUser) -> Result<Status, Status> {
+    debug!("delete_question resource started");
+    let question_service = QuestionService::init().await;
+    let result = match question_service.delete_question(id, &user).await {
+        Ok(_) => Ok(Status::NoContent),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("delete_question resource ending");
+    result
+}
+
+fn process_service_error(error: QuestionServiceError) -> Status {
+    error!("Error: {}", error.message);
+    match error.kind {
+        QuestionServiceErrorKind::NotFound => Status::NotFound,
+        QuestionServiceErrorKind::InvalidRequest => Status::BadRequest,
+        QuestionServiceErrorKind::Forbidden => Status::Forbidden,
+        QuestionServiceErrorKind::Internal => Status::InternalServerError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::question_dto::AuthoredQuestionDto;
+    use crate::model::user::UserRole;
+    use crate::resource::question_resource::{create_question, delete_question, get_questions};
+    use crate::resource::test_fixtures::user;
+    use crate::security::guard::{AuthenticatedUser, QuestionAuthor};
+    use log::info;
+    use rocket::async_test;
+    use rocket::http::Status;
+    use rocket::serde::json::Json;
+    use serial_test::serial;
+    use std::env;
+    use testcontainers::clients::Cli;
+    use testcontainers::GenericImage;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn user_with_role(username: &str, role: UserRole) -> AuthenticatedUser {
+        AuthenticatedUser {
+            roles: vec![UserRole::Player, role],
+            ..user(username)
+        }
+    }
+
+    fn new_question() -> AuthoredQuestionDto {
+        AuthoredQuestionDto {
+            question_text: "In Rust, which keyword declares a constant ?".to_string(),
+            answer_1: "let".to_string(),
+            answer_2: "const".to_string(),
+            answer_3: "static".to_string(),
+            answer_4: "final".to_string(),
+            good_answer_number: 2,
+            topic: "Rust".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[async_test]
+    #[serial]
+    async fn questions_should_be_deleted_by_their_author_or_a_moderator_only() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let alice = user_with_role("alice", UserRole::QuestionAuthor);
+        let bob = user_with_role("bob", UserRole::QuestionAuthor);
+        let carol = user_with_role("carol", UserRole::Moderator);
+        let question = create_question(Json(new_question()), QuestionAuthor(alice.clone()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(question.author, Some("alice".to_string()));
+        let _ = create_question(Json(new_question()), QuestionAuthor(bob.clone())).await;
+
+        assert_eq!(get_questions(alice).await.unwrap().len(), 1);
+        assert_eq!(get_questions(carol.clone()).await.unwrap().len(), 2);
+        let error = get_questions(user_with_role("dave", UserRole::Host))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+
+        let question_id = question.id.unwrap();
+        let error = delete_question(question_id.clone(), bob).await.unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+        let status = delete_question(question_id, carol).await.unwrap();
+        assert_eq!(status, Status::NoContent);
+    }
+}
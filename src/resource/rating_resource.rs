@@ -0,0 +1,175 @@
+use crate::dto::rating_dto::{RatingChangeDto, RatingDto};
+use crate::errors::rating_service_error::{RatingServiceError, RatingServiceErrorKind};
+use crate::mapper::rating_mapper;
+use crate::service::rating_service::{RatingService, DEFAULT_PAGE_SIZE};
+use log::{debug, error};
+use rocket::get;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+
+/// GET request to get the leaderboard of a topic, the best rated players first.
+/// Pages start at 1 and hold 20 players unless another `size` is asked for.
+/// Returns an error if the page or its size are not valid.
+#[get("/ratings/<topic>?<page>&<size>", format = "json")]
+pub async fn get_topic_leaderboard(
+    topic: String,
+    page: Option<u64>,
+    size: Option<i64>,
+) -> Result<Json<Vec<RatingDto>>, Status> {
+    debug!("get_topic_leaderboard resource started");
+    let rating_service = RatingService::init().await;
+    let page = page.unwrap_or(1);
+    let size = size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let result = match rating_service.get_leaderboard(topic, page, size).await {
+        Ok(ratings) => {
+            let first_rank = (page - 1) * size as u64 + 1;
+            Ok(Json(
+                ratings
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, rating)| rating_mapper::to_dto(first_rank + index as u64, rating))
+                    .collect(),
+            ))
+        }
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_topic_leaderboard resource ending");
+    result
+}
+
+/// GET request to get the history of the rating of a player on a topic, oldest change first.
+#[get("/users/<username>/ratings/<topic>/history", format = "json")]
+pub async fn get_rating_history(
+    username: String,
+    topic: String,
+) -> Result<Json<Vec<RatingChangeDto>>, Status> {
+    debug!("get_rating_history resource started");
+    let rating_service = RatingService::init().await;
+    let result = match rating_service.get_history(username, topic).await {
+        Ok(history) => Ok(Json(
+            history
+                .into_iter()
+                .map(rating_mapper::change_to_dto)
+                .collect(),
+        )),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_rating_history resource ending");
+    result
+}
+
+fn process_service_error(error: RatingServiceError) -> Status {
+    error!("Error: {}", error.message);
+    match error.kind {
+        RatingServiceErrorKind::InvalidRequest => Status::BadRequest,
+        RatingServiceErrorKind::Internal => Status::InternalServerError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::game_dto::GameDto;
+    use crate::model::game::GameStatus;
+    use crate::resource::game_resource::{create_game, game_register_user};
+    use crate::resource::rating_resource::{get_rating_history, get_topic_leaderboard};
+    use crate::resource::test_fixtures::{answer, user};
+    use crate::security::guard::{GameHost, Player};
+    use crate::service::game_service::GameService;
+    use crate::service::profile_service::ProfileService;
+    use crate::service::rating_service::RatingService;
+    use log::info;
+    use rocket::async_test;
+    use rocket::http::Status;
+    use rocket::serde::json::Json;
+    use serial_test::serial;
+    use std::env;
+    use testcontainers::clients::Cli;
+    use testcontainers::GenericImage;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[async_test]
+    #[serial]
+    async fn finished_games_should_update_topic_ratings() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let game = GameDto {
+            topics: vec!["Rust".to_string()],
+            question_number: 2,
+            ..Default::default()
+        };
+        let game = create_game(Json(game), GameHost(user("bob")))
+            .await
+            .unwrap()
+            .into_inner();
+        let id = game.id.unwrap();
+        let _ = game_register_user(id.clone(), Player(user("alice"))).await;
+        let game_service = GameService::init().await;
+        for status in [GameStatus::Countdown, GameStatus::Running] {
+            game_service
+                .transition_game(id.clone(), status)
+                .await
+                .unwrap();
+        }
+        for index in 0..2 {
+            game_service
+                .save_game_answer(&answer(&id, "alice", index, true, "Rust"))
+                .await;
+            game_service
+                .save_game_answer(&answer(&id, "bob", index, false, "Rust"))
+                .await;
+        }
+        let game = game_service.finish_game(id.clone()).await.unwrap();
+        RatingService::init()
+            .await
+            .update_ratings(&game)
+            .await
+            .unwrap();
+
+        let leaderboard = get_topic_leaderboard("Rust".to_string(), None, None)
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(leaderboard.len(), 2);
+        assert_eq!(leaderboard[0].rank, 1);
+        assert_eq!(leaderboard[0].user, "alice");
+        assert_eq!(leaderboard[0].rating, 1516.0);
+        assert_eq!(leaderboard[1].user, "bob");
+        assert_eq!(leaderboard[1].rating, 1484.0);
+        let second_page = get_topic_leaderboard("Rust".to_string(), Some(2), Some(1))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(second_page[0].rank, 2);
+        assert_eq!(second_page[0].user, "bob");
+        let error = get_topic_leaderboard("Rust".to_string(), Some(0), None)
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+
+        let history = get_rating_history("bob".to_string(), "Rust".to_string())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].game_id, id);
+        assert_eq!(history[0].rating_before, 1500.0);
+
+        let profile = ProfileService::init()
+            .await
+            .refresh_profile("alice".to_string())
+            .await
+            .unwrap();
+        assert_eq!(profile.ratings.len(), 1);
+        assert_eq!(profile.ratings[0].rating, 1516.0);
+        assert_eq!(profile.ratings[0].games, 1);
+    }
+}
@@ -0,0 +1,40 @@
+//! Fixtures shared by the resource tests.
+
+use crate::model::game_answer::GameAnswer;
+use crate::model::game_progress::Question;
+use crate::model::user::default_roles;
+use crate::security::guard::AuthenticatedUser;
+
+/// Registered user with the default roles, as identified by a login token.
+pub fn user(username: &str) -> AuthenticatedUser {
+    AuthenticatedUser {
+        username: username.to_string(),
+        guest: false,
+        roles: default_roles(),
+        scopes: None,
+    }
+}
+
+/// Answer of a player to the question of the given index of a game on a topic, given with 10 seconds left.
+pub fn answer(
+    game_id: &str,
+    user: &str,
+    question_index: i8,
+    correct: bool,
+    topic: &str,
+) -> GameAnswer {
+    GameAnswer {
+        game_id: game_id.to_string(),
+        user: user.to_string(),
+        answer: if correct { 1 } else { 2 },
+        question_index,
+        correct_answer: 1,
+        question: Question {
+            topic: topic.to_string(),
+            good_answer_number: 1,
+            remaining_time: 10,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
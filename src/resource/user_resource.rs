@@ -0,0 +1,428 @@
+use crate::dto::user_dto::{CredentialsDto, GuestDto, TokenDto, UserDto};
+use crate::errors::user_service_error::{UserServiceError, UserServiceErrorKind};
+use crate::mapper::user_mapper;
+use crate::model::user::UserRole;
+use crate::security::guard::{Admin, AuthenticatedUser};
+use crate::service::user_service::UserService;
+use log::{debug, error, info};
+use rocket::http::Status;
+use rocket::response::Redirect;
+use rocket::serde::json::Json;
+use rocket::{get, post, put};
+
+/// POST request to register a new user.
+/// Returns the created user.
+/// Returns an error if the username or the password are not valid.
+/// Returns an error if the username is already taken.
+#[post("/users", format = "json", data = "<credentials>")]
+pub async fn register_user(credentials: Json<CredentialsDto>) -> Result<Json<UserDto>, Status> {
+    debug!("register_user resource started");
+    let user_service = UserService::init().await;
+    let credentials = credentials.into_inner();
+    let user_created = user_service
+        .register(credentials.username, credentials.password)
+        .await;
+    let result = match user_created {
+        Ok(user) => Ok(Json(user_mapper::to_dto(user))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("register_user resource ending");
+    result
+}
+
+/// POST request to log a user in.
+/// Returns a token to send in the `Authorization` header of the requests needing an authenticated user.
+/// Returns an error if the username does not exist or if the password does not match.
+#[post("/login", format = "json", data = "<credentials>")]
+pub async fn login(credentials: Json<CredentialsDto>) -> Result<Json<TokenDto>, Status> {
+    debug!("login resource started");
+    let user_service = UserService::init().await;
+    let credentials = credentials.into_inner();
+    let token = user_service
+        .login(credentials.username, credentials.password)
+        .await;
+    let result = match token {
+        Ok((token, claims)) => Ok(Json(user_mapper::to_token(token, claims))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("login resource ending");
+    result
+}
+
+/// GET request to log in with an OpenID Connect provider.
+/// Redirects the user to the provider, which sends them back to the callback with an authorization code.
+/// Returns an error if the provider is not configured or cannot be reached.
+#[get("/oidc/<provider>/authorize")]
+pub async fn oidc_authorize(provider: String) -> Result<Redirect, Status> {
+    debug!("oidc_authorize resource started");
+    let user_service = UserService::init().await;
+    let result = match user_service.start_oidc_login(provider).await {
+        Ok(url) => Ok(Redirect::to(url)),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("oidc_authorize resource ending");
+    result
+}
+
+/// GET request the OpenID Connect provider sends the user back to after they logged in.
+/// Returns a token to send in the `Authorization` header, the user is created on their first login.
+/// Returns an error if the login is unknown or expired, if the provider response is not valid
+/// or if the username given by the provider is already taken.
+#[get("/oidc/<provider>/callback?<code>&<state>")]
+pub async fn oidc_callback(
+    provider: String,
+    code: String,
+    state: String,
+) -> Result<Json<TokenDto>, Status> {
+    debug!("oidc_callback resource started");
+    let user_service = UserService::init().await;
+    let token = user_service.finish_oidc_login(provider, code, state).await;
+    let result = match token {
+        Ok((token, claims)) => Ok(Json(user_mapper::to_token(token, claims))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("oidc_callback resource ending");
+    result
+}
+
+/// POST request to play as a guest under a nickname, no account is needed.
+/// Returns a token to send in the `Authorization` header, like the one of registered users.
+/// Returns an error if the nickname is not valid.
+#[post("/guests", format = "json", data = "<guest>")]
+pub async fn create_guest(guest: Json<GuestDto>) -> Result<Json<TokenDto>, Status> {
+    debug!("create_guest resource started");
+    let user_service = UserService::init().await;
+    let result = match user_service.create_guest(guest.into_inner().nickname) {
+        Ok((token, claims)) => Ok(Json(user_mapper::to_token(token, claims))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("create_guest resource ending");
+    result
+}
+
+/// POST request to turn the authenticated guest into a registered user, keeping their game history.
+/// A partial upgrade is resumed by sending the same credentials again.
+/// Returns a token identifying the new account.
+/// Returns an error if the caller is not a guest, if the credentials are not valid or if the username is taken.
+#[post("/guests/upgrade", format = "json", data = "<credentials>")]
+pub async fn upgrade_guest(
+    credentials: Json<CredentialsDto>,
+    user: AuthenticatedUser,
+) -> Result<Json<TokenDto>, Status> {
+    debug!("upgrade_guest resource started");
+    let user_service = UserService::init().await;
+    let credentials = credentials.into_inner();
+    let token = user_service
+        .upgrade_guest(
+            user.username,
+            user.guest,
+            credentials.username,
+            credentials.password,
+        )
+        .await;
+    let result = match token {
+        Ok((token, claims)) => Ok(Json(user_mapper::to_token(token, claims))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("upgrade_guest resource ending");
+    result
+}
+
+/// GET request to get the authenticated user.
+/// Returns the user.
+#[get("/users/me", format = "json")]
+pub async fn get_current_user(user: AuthenticatedUser) -> Result<Json<UserDto>, Status> {
+    debug!("get_current_user resource started");
+    let user_service = UserService::init().await;
+    let result = match user_service.get_user(user.username).await {
+        Ok(user) => Ok(Json(user_mapper::to_dto(user))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("get_current_user resource ending");
+    result
+}
+
+/// PUT request to replace the roles of a user, only admins may do it.
+/// The roles apply from the next login of the user.
+/// Returns the user.
+/// Returns an error if the user does not exist.
+#[put("/users/<username>/roles", format = "json", data = "<roles>")]
+pub async fn set_user_roles(
+    username: String,
+    roles: Json<Vec<UserRole>>,
+    admin: Admin,
+) -> Result<Json<UserDto>, Status> {
+    debug!("set_user_roles resource started");
+    let user_service = UserService::init().await;
+    info!("Roles of {} changed by {}", username, admin.0.username);
+    let result = match user_service.set_roles(username, roles.into_inner()).await {
+        Ok(user) => Ok(Json(user_mapper::to_dto(user))),
+        Err(err) => Err(process_service_error(err)),
+    };
+    debug!("set_user_roles resource ending");
+    result
+}
+
+fn process_service_error(error: UserServiceError) -> Status {
+    error!("Error: {}", error.message);
+    match error.kind {
+        UserServiceErrorKind::NotFound => Status::NotFound,
+        UserServiceErrorKind::InvalidRequest => Status::BadRequest,
+        UserServiceErrorKind::Conflict => Status::Conflict,
+        UserServiceErrorKind::Unauthorized => Status::Unauthorized,
+        UserServiceErrorKind::Forbidden => Status::Forbidden,
+        UserServiceErrorKind::Internal => Status::InternalServerError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::game_dto::GameDto;
+    use crate::dto::user_dto::{CredentialsDto, GuestDto};
+    use crate::model::user::default_roles;
+    use crate::resource::game_resource::{create_game, game_register_user, get_game};
+    use crate::resource::user_resource::{
+        create_guest, get_current_user, login, oidc_callback, register_user, upgrade_guest,
+    };
+    use crate::security::guard::{AuthenticatedUser, GameHost, Player};
+    use crate::security::token::decode_token;
+    use crate::service::user_service::UserService;
+    use log::info;
+    use rocket::async_test;
+    use rocket::http::Status;
+    use rocket::serde::json::Json;
+    use serial_test::serial;
+    use std::env;
+    use testcontainers::clients::Cli;
+    use testcontainers::core::WaitFor;
+    use testcontainers::GenericImage;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn credentials(username: &str, password: &str) -> Json<CredentialsDto> {
+        Json(CredentialsDto {
+            username: username.to_string(),
+            password: password.to_string(),
+        })
+    }
+
+    #[async_test]
+    #[serial]
+    async fn login_should_return_a_token_for_the_registered_user() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        let user_created = register_user(credentials("bob", "correct horse"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(user_created.id.is_some());
+        assert_eq!(user_created.username, "bob");
+
+        let error = login(credentials("bob", "wrong horse")).await.unwrap_err();
+        assert_eq!(error, Status::Unauthorized);
+
+        let token = login(credentials("bob", "correct horse"))
+            .await
+            .unwrap()
+            .into_inner();
+        let claims = decode_token(&token.token).unwrap();
+        assert_eq!(claims.sub, "bob");
+
+        let current_user = get_current_user(AuthenticatedUser {
+            username: claims.sub,
+            guest: claims.guest,
+            roles: claims.roles,
+            scopes: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+        assert_eq!(current_user.id, user_created.id);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn register_user_should_reject_taken_usernames() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        UserService::init().await.create_indexes().await.unwrap();
+        let _ = register_user(credentials("bob", "correct horse")).await;
+
+        let error = register_user(credentials("bob", "another horse"))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+
+        let error = register_user(credentials("bob", "short"))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::BadRequest);
+    }
+
+    async fn guest(nickname: &str) -> AuthenticatedUser {
+        let token = create_guest(Json(GuestDto {
+            nickname: nickname.to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+        let claims = decode_token(&token.token).unwrap();
+        AuthenticatedUser {
+            username: claims.sub,
+            guest: claims.guest,
+            roles: claims.roles,
+            scopes: None,
+        }
+    }
+
+    #[async_test]
+    #[serial]
+    async fn guests_should_have_unique_nicknames_and_keep_their_games_when_upgrading_or_resuming() {
+        init();
+        info!("Creating mongo container");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        info!("Mongo container created");
+        UserService::init().await.create_indexes().await.unwrap();
+        let alice = guest("alice").await;
+        assert!(alice.guest);
+        assert!(alice.username.starts_with("alice#"));
+        let game = GameDto {
+            topics: vec!["Java".to_string()],
+            question_number: 10,
+            ..Default::default()
+        };
+        let game_id = create_game(Json(game), GameHost(alice.clone()))
+            .await
+            .unwrap()
+            .into_inner()
+            .id
+            .unwrap();
+
+        let error = game_register_user(game_id.clone(), Player(guest("alice").await))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+
+        let bob = AuthenticatedUser {
+            username: "bob".to_string(),
+            guest: false,
+            roles: default_roles(),
+            scopes: None,
+        };
+        let error = upgrade_guest(credentials("alice", "correct horse"), bob)
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Forbidden);
+
+        let token = upgrade_guest(credentials("alice", "correct horse"), alice.clone())
+            .await
+            .unwrap()
+            .into_inner();
+        let claims = decode_token(&token.token).unwrap();
+        assert_eq!(claims.sub, "alice");
+        assert!(!claims.guest);
+        let game = get_game(game_id).await.unwrap().into_inner();
+        assert_eq!(game.creator, Some("alice".to_string()));
+
+        let token = upgrade_guest(credentials("alice", "correct horse"), alice.clone())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(decode_token(&token.token).unwrap().sub, "alice");
+        let error = upgrade_guest(credentials("alice", "wrong horse"), alice)
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+        let error = upgrade_guest(credentials("alice", "correct horse"), guest("carol").await)
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Conflict);
+    }
+
+    #[async_test]
+    #[serial]
+    async fn oidc_login_should_create_the_user_from_the_id_token_of_the_provider() {
+        init();
+        info!("Creating mongo and mock OIDC containers");
+        let docker = Cli::default();
+        let container = docker.run(GenericImage::new("mongo", "latest"));
+        let port = container.get_host_port_ipv4(27017);
+        let uri = format!("mongodb://localhost:{}", port);
+        env::set_var("MONGO_URI", uri.clone());
+        let oidc_container = docker.run(
+            GenericImage::new("ghcr.io/navikt/mock-oauth2-server", "2.1.10")
+                .with_exposed_port(8080)
+                .with_wait_for(WaitFor::message_on_stdout("started server")),
+        );
+        let oidc_port = oidc_container.get_host_port_ipv4(8080);
+        env::set_var(
+            "OIDC_MOCK_ISSUER",
+            format!("http://localhost:{}/default", oidc_port),
+        );
+        env::set_var("OIDC_MOCK_CLIENT_ID", "quiz");
+        env::set_var("OIDC_MOCK_REDIRECT_URI", "http://localhost/callback");
+        env::set_var("OIDC_MOCK_USERNAME_CLAIM", "sub");
+        info!("Containers created");
+        UserService::init().await.create_indexes().await.unwrap();
+
+        let url = UserService::init()
+            .await
+            .start_oidc_login("mock".to_string())
+            .await
+            .unwrap();
+        // The mock provider logs the user in right away and redirects to the callback
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        let response = client.get(url).send().await.unwrap();
+        let location = response.headers()["location"].to_str().unwrap();
+        let callback = reqwest::Url::parse(location).unwrap();
+        let param = |name: &str| {
+            callback
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.to_string())
+                .unwrap()
+        };
+
+        let token = oidc_callback("mock".to_string(), param("code"), param("state"))
+            .await
+            .unwrap()
+            .into_inner();
+        let claims = decode_token(&token.token).unwrap();
+        let current_user = get_current_user(AuthenticatedUser {
+            username: claims.sub,
+            guest: claims.guest,
+            roles: claims.roles,
+            scopes: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+        assert!(current_user.id.is_some());
+
+        let error = oidc_callback("mock".to_string(), param("code"), param("state"))
+            .await
+            .unwrap_err();
+        assert_eq!(error, Status::Unauthorized);
+    }
+}
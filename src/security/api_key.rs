@@ -0,0 +1,49 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Prefix of the API keys, telling them apart from the tokens of logged in users.
+pub const API_KEY_PREFIX: &str = "cfk_";
+/// Number of characters of a key kept to help its owner recognise it.
+pub const API_KEY_HINT_LENGTH: usize = 8;
+
+/// Generates a new API key.
+pub fn generate_api_key() -> String {
+    let mut bytes = [0; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("{}{}", API_KEY_PREFIX, URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Hashes an API key to store it or to look it up.
+/// Keys are random enough for a fast hash, unlike passwords.
+pub fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+/// Gives the beginning of an API key, shown to its owner to recognise it.
+pub fn api_key_hint(key: &str) -> String {
+    key.chars()
+        .take(API_KEY_PREFIX.len() + API_KEY_HINT_LENGTH)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::security::api_key::{api_key_hint, generate_api_key, hash_api_key, API_KEY_PREFIX};
+
+    #[test]
+    fn generate_api_key_should_give_distinct_prefixed_keys() {
+        let key = generate_api_key();
+        assert!(key.starts_with(API_KEY_PREFIX));
+        assert_ne!(key, generate_api_key());
+        assert_eq!(api_key_hint(&key).len(), 12);
+    }
+
+    #[test]
+    fn hash_api_key_should_be_stable() {
+        assert_eq!(hash_api_key("cfk_key"), hash_api_key("cfk_key"));
+        assert_ne!(hash_api_key("cfk_key"), hash_api_key("cfk_other"));
+        assert_eq!(hash_api_key("cfk_key").len(), 64);
+    }
+}
@@ -0,0 +1,117 @@
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::Request;
+
+use crate::model::api_key::ApiKeyScope;
+use crate::model::user::UserRole;
+use crate::security::api_key::API_KEY_PREFIX;
+use crate::security::token::decode_token;
+use crate::service::api_key_service::ApiKeyService;
+use crate::service::policy::{is_allowed, Permission};
+use crate::service::user_service::UserService;
+
+///Request guard identifying the caller from the bearer token of the `Authorization` header.
+///Both registered users and guests are accepted, `guest` tells them apart.
+///Tokens of guests upgraded to a registered user are refused.
+///The roles are the ones of the user when the token was issued.
+///API keys are accepted as bearer tokens too, the caller is then limited to the `scopes` of the key.
+///Requests without a valid token are rejected with a 401 status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedUser {
+    pub username: String,
+    pub guest: bool,
+    pub roles: Vec<UserRole>,
+    pub scopes: Option<Vec<ApiKeyScope>>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuthenticatedUser {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let token = request
+            .headers()
+            .get_one("Authorization")
+            .and_then(|header| header.strip_prefix("Bearer "));
+        let user = match token {
+            Some(key) if key.starts_with(API_KEY_PREFIX) => {
+                ApiKeyService::init().await.authenticate(key).await.ok()
+            }
+            Some(token) => Self::from_token(token).await,
+            None => None,
+        };
+        match user {
+            Some(user) => Outcome::Success(user),
+            None => Outcome::Error((Status::Unauthorized, ())),
+        }
+    }
+}
+
+impl AuthenticatedUser {
+    /// Identifies the caller from a token issued at login, tokens of upgraded guests being refused.
+    async fn from_token(token: &str) -> Option<Self> {
+        let claims = decode_token(token).ok()?;
+        if claims.guest {
+            let upgraded = UserService::init()
+                .await
+                .is_upgraded_guest(claims.sub.clone())
+                .await;
+            if !matches!(upgraded, Ok(false)) {
+                return None;
+            }
+        }
+        Some(AuthenticatedUser {
+            username: claims.sub,
+            guest: claims.guest,
+            roles: claims.roles,
+            scopes: None,
+        })
+    }
+}
+
+/// Declares a request guard accepting only the authenticated users granted a [Permission] by the policy.
+/// Users missing the permission are rejected with a 403 status.
+macro_rules! permission_guard {
+    ($(#[$doc:meta])* $name:ident, $permission:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(pub AuthenticatedUser);
+
+        #[rocket::async_trait]
+        impl<'r> FromRequest<'r> for $name {
+            type Error = ();
+
+            async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+                match AuthenticatedUser::from_request(request).await {
+                    Outcome::Success(user) if is_allowed(&user, $permission) => {
+                        Outcome::Success($name(user))
+                    }
+                    Outcome::Success(_) => Outcome::Error((Status::Forbidden, ())),
+                    Outcome::Error(error) => Outcome::Error(error),
+                    Outcome::Forward(status) => Outcome::Forward(status),
+                }
+            }
+        }
+    };
+}
+
+permission_guard!(
+    ///Request guard for the users allowed to join and play games.
+    Player,
+    Permission::PlayGames
+);
+permission_guard!(
+    ///Request guard for the users allowed to create and host games.
+    GameHost,
+    Permission::CreateGames
+);
+permission_guard!(
+    ///Request guard for the users allowed to write questions.
+    QuestionAuthor,
+    Permission::WriteQuestions
+);
+permission_guard!(
+    ///Request guard for the users allowed to manage the other users.
+    Admin,
+    Permission::ManageUsers
+);
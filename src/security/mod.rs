@@ -0,0 +1,5 @@
+pub mod api_key;
+pub mod guard;
+pub mod oidc;
+pub mod password;
+pub mod token;
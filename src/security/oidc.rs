@@ -0,0 +1,285 @@
+extern crate dotenv;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use dotenv::dotenv;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use rand::RngCore;
+use reqwest::Url;
+use rocket::serde::json::Value;
+use rocket::serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::env;
+
+pub const OIDC_PREFIX: &str = "OIDC";
+pub const DEFAULT_SCOPES: &str = "openid profile email";
+pub const DEFAULT_USERNAME_CLAIM: &str = "preferred_username";
+pub const DEFAULT_EMAIL_CLAIM: &str = "email";
+pub const DEFAULT_NAME_CLAIM: &str = "name";
+
+/// Signature algorithms accepted for ID tokens.
+/// Symmetric algorithms are left out, they would let anyone knowing the client secret forge tokens.
+const ID_TOKEN_ALGORITHMS: [Algorithm; 9] = [
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+///Configuration of an OpenID Connect identity provider.
+///Each provider is read from `OIDC_<NAME>_*` environment variables, `<NAME>` being the upper case provider name:
+///`ISSUER`, `CLIENT_ID` and `REDIRECT_URI` are required,
+///`CLIENT_SECRET`, `SCOPES`, `USERNAME_CLAIM`, `EMAIL_CLAIM` and `NAME_CLAIM` are optional.
+#[derive(Debug, Clone)]
+pub struct OidcProvider {
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+    pub scopes: String,
+    pub username_claim: String,
+    pub email_claim: String,
+    pub name_claim: String,
+}
+
+///Endpoints of a provider, as published by its discovery document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+///Identity of a user, mapped from the claims of a validated ID token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidcIdentity {
+    pub issuer: String,
+    pub subject: String,
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+impl OidcProvider {
+    /// Reads the configuration of the given provider from the environment.
+    /// Returns none if the provider is not configured.
+    pub fn from_env(name: &str) -> Option<Self> {
+        dotenv().ok();
+        let var = |key: &str| {
+            env::var(format!("{}_{}_{}", OIDC_PREFIX, name.to_uppercase(), key))
+                .ok()
+                .filter(|value| !value.is_empty())
+        };
+        Some(OidcProvider {
+            issuer: var("ISSUER")?.trim_end_matches('/').to_string(),
+            client_id: var("CLIENT_ID")?,
+            client_secret: var("CLIENT_SECRET"),
+            redirect_uri: var("REDIRECT_URI")?,
+            scopes: var("SCOPES").unwrap_or_else(|| DEFAULT_SCOPES.to_string()),
+            username_claim: var("USERNAME_CLAIM")
+                .unwrap_or_else(|| DEFAULT_USERNAME_CLAIM.to_string()),
+            email_claim: var("EMAIL_CLAIM").unwrap_or_else(|| DEFAULT_EMAIL_CLAIM.to_string()),
+            name_claim: var("NAME_CLAIM").unwrap_or_else(|| DEFAULT_NAME_CLAIM.to_string()),
+        })
+    }
+
+    /// Fetches the discovery document of the provider.
+    /// Returns an error if the document cannot be fetched or if it was published for another issuer.
+    pub async fn discover(&self) -> Result<ProviderMetadata, String> {
+        let url = format!("{}/.well-known/openid-configuration", self.issuer);
+        let metadata: ProviderMetadata = reqwest::get(url)
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| err.to_string())?
+            .json()
+            .await
+            .map_err(|err| err.to_string())?;
+        if metadata.issuer.trim_end_matches('/') != self.issuer {
+            return Err(format!(
+                "Discovery document of {} was published for issuer {}",
+                self.issuer, metadata.issuer
+            ));
+        }
+        Ok(metadata)
+    }
+
+    /// Builds the URL of the provider the user is sent to, to log in.
+    pub fn authorization_url(
+        &self,
+        metadata: &ProviderMetadata,
+        state: &str,
+        nonce: &str,
+        code_challenge: &str,
+    ) -> Result<String, String> {
+        let url = Url::parse_with_params(
+            &metadata.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", &self.client_id),
+                ("redirect_uri", &self.redirect_uri),
+                ("scope", &self.scopes),
+                ("state", state),
+                ("nonce", nonce),
+                ("code_challenge", code_challenge),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|err| err.to_string())?;
+        Ok(url.to_string())
+    }
+
+    /// Exchanges an authorization code for the ID token of the user.
+    pub async fn exchange_code(
+        &self,
+        metadata: &ProviderMetadata,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<String, String> {
+        let mut params = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &self.redirect_uri),
+            ("client_id", &self.client_id),
+            ("code_verifier", code_verifier),
+        ];
+        if let Some(client_secret) = &self.client_secret {
+            params.push(("client_secret", client_secret));
+        }
+        let response: TokenResponse = reqwest::Client::new()
+            .post(&metadata.token_endpoint)
+            .form(&params)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| err.to_string())?
+            .json()
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(response.id_token)
+    }
+
+    /// Checks the signature, issuer, audience, expiration and nonce of an ID token and maps its claims.
+    pub async fn validate_id_token(
+        &self,
+        metadata: &ProviderMetadata,
+        id_token: &str,
+        nonce: &str,
+    ) -> Result<OidcIdentity, String> {
+        let header = decode_header(id_token).map_err(|err| err.to_string())?;
+        if !ID_TOKEN_ALGORITHMS.contains(&header.alg) {
+            return Err(format!(
+                "ID token algorithm {:?} is not allowed",
+                header.alg
+            ));
+        }
+        let jwks: JwkSet = reqwest::get(&metadata.jwks_uri)
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| err.to_string())?
+            .json()
+            .await
+            .map_err(|err| err.to_string())?;
+        let jwk = match &header.kid {
+            Some(kid) => jwks.find(kid),
+            None => jwks.keys.first(),
+        }
+        .ok_or_else(|| "No key of the provider matches the ID token".to_string())?;
+        let key = DecodingKey::from_jwk(jwk).map_err(|err| err.to_string())?;
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&metadata.issuer]);
+        validation.set_audience(&[&self.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        let claims = decode::<HashMap<String, Value>>(id_token, &key, &validation)
+            .map_err(|err| err.to_string())?
+            .claims;
+        if claims.get("nonce").and_then(Value::as_str) != Some(nonce) {
+            return Err("ID token nonce does not match".to_string());
+        }
+        self.map_claims(&claims)
+    }
+
+    /// Maps the claims of an ID token to an [OidcIdentity], using the claim names configured for the provider.
+    pub fn map_claims(&self, claims: &HashMap<String, Value>) -> Result<OidcIdentity, String> {
+        let claim = |name: &str| {
+            claims
+                .get(name)
+                .and_then(Value::as_str)
+                .map(|value| value.to_string())
+        };
+        Ok(OidcIdentity {
+            issuer: claim("iss").ok_or_else(|| "ID token has no issuer".to_string())?,
+            subject: claim("sub").ok_or_else(|| "ID token has no subject".to_string())?,
+            username: claim(&self.username_claim),
+            email: claim(&self.email_claim),
+            name: claim(&self.name_claim),
+        })
+    }
+}
+
+/// Generates an unguessable URL safe value, used for states, nonces and PKCE verifiers.
+pub fn random_token() -> String {
+    let mut bytes = [0; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Computes the S256 PKCE challenge of a code verifier.
+pub fn code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::security::oidc::{code_challenge, OidcProvider};
+    use rocket::serde::json::{json, Value};
+    use std::collections::HashMap;
+
+    #[test]
+    fn code_challenge_should_follow_the_pkce_example() {
+        // Example of RFC 7636, appendix B
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn map_claims_should_use_the_configured_claim_names() {
+        let provider = OidcProvider {
+            issuer: "https://sso.example.com".to_string(),
+            client_id: "quiz".to_string(),
+            client_secret: None,
+            redirect_uri: "https://quiz.example.com/oidc/company/callback".to_string(),
+            scopes: "openid".to_string(),
+            username_claim: "upn".to_string(),
+            email_claim: "email".to_string(),
+            name_claim: "name".to_string(),
+        };
+        let claims: HashMap<String, Value> = [
+            ("iss", json!("https://sso.example.com")),
+            ("sub", json!("42")),
+            ("upn", json!("alice")),
+            ("email", json!("alice@example.com")),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+        let identity = provider.map_claims(&claims).unwrap();
+        assert_eq!(identity.subject, "42");
+        assert_eq!(identity.username, Some("alice".to_string()));
+        assert_eq!(identity.email, Some("alice@example.com".to_string()));
+        assert_eq!(identity.name, None);
+    }
+}
@@ -0,0 +1,35 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{Error, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+
+/// Hashes a password with argon2 and a random salt.
+/// Returns the hash in the PHC string format, which embeds the salt and the hashing parameters.
+pub fn hash_password(password: &str) -> Result<String, Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = Argon2::default().hash_password(password.as_bytes(), &salt)?;
+    Ok(hash.to_string())
+}
+
+/// Tells if a password matches a hash produced by [hash_password].
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    match PasswordHash::new(hash) {
+        Ok(hash) => Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::security::password::{hash_password, verify_password};
+
+    #[test]
+    fn verify_password_should_match_only_the_hashed_password() {
+        let hash = hash_password("secret").unwrap();
+        assert_ne!(hash, "secret");
+        assert!(verify_password("secret", &hash));
+        assert!(!verify_password("other", &hash));
+        assert!(!verify_password("secret", "not a hash"));
+    }
+}
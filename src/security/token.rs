@@ -0,0 +1,122 @@
+extern crate dotenv;
+use crate::model::user::{default_roles, UserRole};
+use dotenv::dotenv;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use log::warn;
+use rand::RngCore;
+use rocket::serde::{Deserialize, Serialize};
+use std::env;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const JWT_SECRET: &str = "JWT_SECRET";
+pub const TOKEN_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+static SECRET: OnceLock<Vec<u8>> = OnceLock::new();
+
+///Claims carried by the tokens issued to authenticated users
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub iat: u64,
+    pub exp: u64,
+    #[serde(default)]
+    pub guest: bool,
+    #[serde(default = "default_roles")]
+    pub roles: Vec<UserRole>,
+}
+
+/// Issues a signed token identifying the given registered user with their roles, valid for [TOKEN_DURATION].
+/// Roles changed afterwards apply to the next token of the user.
+pub fn issue_token(
+    subject: &str,
+    roles: &[UserRole],
+) -> Result<(String, Claims), jsonwebtoken::errors::Error> {
+    issue(subject, false, roles.to_vec())
+}
+
+/// Issues a signed token identifying the given guest player, valid for [TOKEN_DURATION].
+/// Guests are given the [default roles](default_roles).
+pub fn issue_guest_token(subject: &str) -> Result<(String, Claims), jsonwebtoken::errors::Error> {
+    issue(subject, true, default_roles())
+}
+
+fn issue(
+    subject: &str,
+    guest: bool,
+    roles: Vec<UserRole>,
+) -> Result<(String, Claims), jsonwebtoken::errors::Error> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let claims = Claims {
+        sub: subject.to_string(),
+        iat: now,
+        exp: now + TOKEN_DURATION.as_secs(),
+        guest,
+        roles,
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret()),
+    )?;
+    Ok((token, claims))
+}
+
+/// Checks the signature and the expiration of a token issued by [issue_token] and returns its claims.
+pub fn decode_token(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    let data = decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(secret()),
+        &Validation::default(),
+    )?;
+    Ok(data.claims)
+}
+
+/// Secret used to sign tokens, read from the `JWT_SECRET` environment variable.
+/// A random secret is used when the variable is not set, tokens are then invalidated on restart.
+fn secret() -> &'static [u8] {
+    SECRET.get_or_init(|| {
+        dotenv().ok();
+        match env::var(JWT_SECRET) {
+            Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+            _ => {
+                warn!("{} is not set, using a random secret", JWT_SECRET);
+                let mut secret = vec![0; 64];
+                rand::thread_rng().fill_bytes(&mut secret);
+                secret
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::user::UserRole;
+    use crate::security::token::{decode_token, issue_guest_token, issue_token};
+
+    #[test]
+    fn decode_token_should_return_the_subject_of_an_issued_token() {
+        let (token, _) = issue_token("bob", &[UserRole::Player]).unwrap();
+        assert_eq!(decode_token(&token).unwrap().sub, "bob");
+    }
+
+    #[test]
+    fn decode_token_should_tell_guests_apart() {
+        let (token, _) = issue_guest_token("alice#1f2e3d4c").unwrap();
+        assert!(decode_token(&token).unwrap().guest);
+        let (token, _) = issue_token("bob", &[UserRole::Admin]).unwrap();
+        let claims = decode_token(&token).unwrap();
+        assert!(!claims.guest);
+        assert_eq!(claims.roles, vec![UserRole::Admin]);
+    }
+
+    #[test]
+    fn decode_token_should_reject_a_tampered_token() {
+        let (token, _) = issue_token("bob", &[UserRole::Player]).unwrap();
+        let tampered = token.replacen('.', ".x", 1);
+        assert!(decode_token(&tampered).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use crate::errors::api_key_service_error::{ApiKeyServiceError, ApiKeyServiceErrorKind};
+use crate::model::api_key::{ApiKey, ApiKeyScope};
+use crate::repository::api_key_repository::ApiKeyRepo;
+use crate::repository::user_repository::UserRepo;
+use crate::security::api_key::{api_key_hint, generate_api_key, hash_api_key};
+use crate::security::guard::AuthenticatedUser;
+use log::debug;
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use mongodb::error::Error;
+use rocket::futures::TryStreamExt;
+use std::str::FromStr;
+
+pub const API_KEY_NAME_MAX_LENGTH: usize = 64;
+
+/// Service for [ApiKey] object to interact with the data layer
+pub struct ApiKeyService {
+    api_key_repo: ApiKeyRepo,
+    user_repo: UserRepo,
+}
+
+impl ApiKeyService {
+    /// Creates a new instance of [ApiKeyService] with the repositories to interact with the data layer
+    pub async fn init() -> Self {
+        let api_key_repo = ApiKeyRepo::init().await;
+        let user_repo = UserRepo::init().await;
+        ApiKeyService {
+            api_key_repo,
+            user_repo,
+        }
+    }
+
+    /// Creates the indexes the service relies on.
+    pub async fn create_indexes(&self) -> Result<(), ApiKeyServiceError> {
+        debug!("create_indexes service started");
+        let result = match self.api_key_repo.create_indexes().await {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("create_indexes service ending");
+        result
+    }
+
+    /// Creates a new [ApiKey] for the caller.
+    /// Returns the key along with the stored key, the key cannot be retrieved afterwards.
+    /// Returns an error if the caller is a guest or authenticated with an API key.
+    /// Returns an error if the name is not valid or if no scope is given.
+    pub async fn create_api_key(
+        &self,
+        caller: &AuthenticatedUser,
+        name: String,
+        scopes: Vec<ApiKeyScope>,
+    ) -> Result<(String, ApiKey), ApiKeyServiceError> {
+        debug!("create_api_key service started");
+        Self::check_logged_in(caller)?;
+        if name.trim().is_empty() || name.len() > API_KEY_NAME_MAX_LENGTH || scopes.is_empty() {
+            return Err(ApiKeyServiceError {
+                message: format!(
+                    "API keys need a name of 1 to {} characters and at least one scope",
+                    API_KEY_NAME_MAX_LENGTH
+                ),
+                kind: ApiKeyServiceErrorKind::InvalidRequest,
+            });
+        }
+        let key = generate_api_key();
+        let mut api_key = ApiKey {
+            id: None,
+            owner: caller.username.clone(),
+            name,
+            hint: api_key_hint(&key),
+            key_hash: hash_api_key(&key),
+            scopes,
+            created_at: DateTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+        let result = match self.api_key_repo.create_api_key(api_key.clone()).await {
+            Ok(insert) => {
+                api_key.id = insert.inserted_id.as_object_id();
+                Ok((key, api_key))
+            }
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("create_api_key service ending");
+        result
+    }
+
+    /// Gets all the [ApiKey]s of the caller, revoked ones included.
+    /// Returns an error if the caller is a guest or authenticated with an API key.
+    pub async fn get_api_keys(
+        &self,
+        caller: &AuthenticatedUser,
+    ) -> Result<Vec<ApiKey>, ApiKeyServiceError> {
+        debug!("get_api_keys service started");
+        Self::check_logged_in(caller)?;
+        let result = match self
+            .api_key_repo
+            .get_api_keys(caller.username.clone())
+            .await
+        {
+            Ok(api_keys) => api_keys
+                .try_collect()
+                .await
+                .map_err(Self::process_internal_error),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("get_api_keys service ending");
+        result
+    }
+
+    /// Revokes an [ApiKey] of the caller.
+    /// Returns an error if the caller is a guest or authenticated with an API key.
+    /// Returns an error if the caller has no such key, or if it is already revoked.
+    pub async fn revoke_api_key(
+        &self,
+        caller: &AuthenticatedUser,
+        id: String,
+    ) -> Result<(), ApiKeyServiceError> {
+        debug!("revoke_api_key service started");
+        Self::check_logged_in(caller)?;
+        let not_found_error = ApiKeyServiceError {
+            message: format!("API key with id {} does not exist", id),
+            kind: ApiKeyServiceErrorKind::NotFound,
+        };
+        let Ok(object_id) = ObjectId::from_str(&id) else {
+            return Err(not_found_error);
+        };
+        let result = match self
+            .api_key_repo
+            .revoke_api_key(object_id, caller.username.clone())
+            .await
+        {
+            Ok(update) if update.modified_count == 1 => Ok(()),
+            Ok(_) => Err(not_found_error),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("revoke_api_key service ending");
+        result
+    }
+
+    /// Identifies the owner of an API key, with their current roles and the scopes of the key.
+    /// The last use time of the key is updated.
+    /// Returns an error if the key does not exist or is revoked.
+    pub async fn authenticate(&self, key: &str) -> Result<AuthenticatedUser, ApiKeyServiceError> {
+        debug!("authenticate service started");
+        let unauthorized_error = ApiKeyServiceError {
+            message: "API key is not valid".to_string(),
+            kind: ApiKeyServiceErrorKind::Unauthorized,
+        };
+        let api_key = match self.api_key_repo.use_api_key(hash_api_key(key)).await {
+            Ok(Some(api_key)) => api_key,
+            Ok(None) => return Err(unauthorized_error),
+            Err(err) => return Err(Self::process_internal_error(err)),
+        };
+        let result = match self.user_repo.get_user_by_username(api_key.owner).await {
+            Ok(Some(user)) => Ok(AuthenticatedUser {
+                username: user.username,
+                guest: false,
+                roles: user.roles,
+                scopes: Some(api_key.scopes),
+            }),
+            Ok(None) => Err(unauthorized_error),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("authenticate service ending");
+        result
+    }
+
+    /// API keys are managed by registered users logged in, not by guests nor with another API key.
+    fn check_logged_in(caller: &AuthenticatedUser) -> Result<(), ApiKeyServiceError> {
+        if caller.guest || caller.scopes.is_some() {
+            return Err(ApiKeyServiceError {
+                message: format!("User {} cannot manage API keys", caller.username),
+                kind: ApiKeyServiceErrorKind::Forbidden,
+            });
+        }
+        Ok(())
+    }
+
+    fn process_internal_error(err: Error) -> ApiKeyServiceError {
+        ApiKeyServiceError {
+            message: err.to_string(),
+            kind: ApiKeyServiceErrorKind::Internal,
+        }
+    }
+}
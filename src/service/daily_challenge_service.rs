@@ -0,0 +1,418 @@
+extern crate dotenv;
+use crate::errors::daily_challenge_service_error::{
+    DailyChallengeServiceError, DailyChallengeServiceErrorKind,
+};
+use crate::model::daily_challenge::{
+    pick_questions, rank_attempts, streaks, DailyAttempt, DailyChallenge, DailyFeedback,
+    DailyLeaderboard, DailyReset, DailyStreak, RankedAttempt, DAILY_CHALLENGE_QUESTIONS,
+};
+use crate::model::game_answer::GameAnswer;
+use crate::model::game_progress::Question;
+use crate::repository::daily_attempt_repository::DailyAttemptRepo;
+use crate::repository::daily_challenge_repository::DailyChallengeRepo;
+use crate::repository::game_answer_repository::GameAnswerRepo;
+use crate::resource::game_resource::QUESTION_SECONDS;
+use crate::service::rating_service::MAX_PAGE_SIZE;
+use dotenv::dotenv;
+use log::{debug, error};
+use mongodb::bson::DateTime;
+use mongodb::error::{Error, ErrorKind, WriteFailure};
+use rocket::futures::TryStreamExt;
+use std::env;
+
+/// Time of the day, in UTC and formatted as `HH:MM`, when the challenge of the next day starts.
+/// Challenges start at midnight when it is not set.
+pub const DAILY_CHALLENGE_TIME: &str = "DAILY_CHALLENGE_TIME";
+const DUPLICATE_KEY_CODE: i32 = 11000;
+
+/// Service for [DailyChallenge] object to interact with the data layer
+pub struct DailyChallengeService {
+    challenge_repo: DailyChallengeRepo,
+    attempt_repo: DailyAttemptRepo,
+    game_answer_repo: GameAnswerRepo,
+    reset: DailyReset,
+}
+
+impl DailyChallengeService {
+    /// Creates a new instance of [DailyChallengeService] with the repositories to interact with the data layer
+    /// and the time of the day challenges start at, configured in the environment.
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let reset = DailyReset::parse(&env::var(DAILY_CHALLENGE_TIME).unwrap_or_default())
+            .unwrap_or_else(|err| {
+                error!(
+                    "Invalid {}, starting challenges at midnight: {}",
+                    DAILY_CHALLENGE_TIME, err
+                );
+                DailyReset::default()
+            });
+        let challenge_repo = DailyChallengeRepo::init().await;
+        let attempt_repo = DailyAttemptRepo::init().await;
+        let game_answer_repo = GameAnswerRepo::init().await;
+        DailyChallengeService {
+            challenge_repo,
+            attempt_repo,
+            game_answer_repo,
+            reset,
+        }
+    }
+
+    /// Creates the indexes the service relies on.
+    pub async fn create_indexes(&self) -> Result<(), DailyChallengeServiceError> {
+        debug!("create_indexes service started");
+        if let Err(err) = self.challenge_repo.create_indexes().await {
+            return Err(Self::process_internal_error(err));
+        }
+        let result = match self.attempt_repo.create_indexes().await {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("create_indexes service ending");
+        result
+    }
+
+    /// Gives the day of the challenge in progress at a date.
+    pub fn day_of(&self, date: DateTime) -> String {
+        self.reset.day_of(date)
+    }
+
+    /// Gives the date the challenge in progress at a date ends, when the challenge of the next day starts.
+    pub fn next_reset(&self, date: DateTime) -> DateTime {
+        match self.reset.bounds(&self.day_of(date)) {
+            Some((_, ends_at)) => ends_at,
+            None => date,
+        }
+    }
+
+    /// Gets the [DailyChallenge] of a day.
+    /// Returns an error if the challenge of the day was not generated.
+    pub async fn get_challenge(
+        &self,
+        day: String,
+    ) -> Result<DailyChallenge, DailyChallengeServiceError> {
+        debug!("get_challenge service started");
+        let result = match self.challenge_repo.get_challenge(day.clone()).await {
+            Ok(Some(challenge)) => Ok(challenge),
+            Ok(None) => Err(Self::process_not_found_error(format!(
+                "There is no daily challenge on {}",
+                day
+            ))),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("get_challenge service ending");
+        result
+    }
+
+    /// Generates the [DailyChallenge] of a day from a pool of questions, unless it already exists.
+    /// The questions are picked from the pool so that generating the challenge of a day again gives the same questions.
+    /// Returns the challenge of the day.
+    /// Returns an error if the day is not valid or if the pool is empty.
+    pub async fn generate(
+        &self,
+        day: String,
+        pool: Vec<Question>,
+    ) -> Result<DailyChallenge, DailyChallengeServiceError> {
+        debug!("generate service started");
+        if let Some(challenge) = self
+            .challenge_repo
+            .get_challenge(day.clone())
+            .await
+            .map_err(Self::process_internal_error)?
+        {
+            return Ok(challenge);
+        }
+        let (starts_at, ends_at) = self.reset.bounds(&day).ok_or_else(|| {
+            Self::process_invalid_request_error(format!("Day {} is not a YYYY-MM-DD date", day))
+        })?;
+        let mut questions = pick_questions(pool, DAILY_CHALLENGE_QUESTIONS, &day);
+        if questions.is_empty() {
+            return Err(Self::process_invalid_request_error(
+                "There is no question for the daily challenge".to_string(),
+            ));
+        }
+        for question in &mut questions {
+            question.remaining_time = QUESTION_SECONDS;
+        }
+        let mut challenge = DailyChallenge {
+            id: None,
+            day: day.clone(),
+            questions,
+            starts_at,
+            ends_at,
+        };
+        let result = match self
+            .challenge_repo
+            .create_challenge(challenge.clone())
+            .await
+        {
+            Ok(insert) => {
+                challenge.id = insert.inserted_id.as_object_id();
+                Ok(challenge)
+            }
+            // Generated at the same time by another request
+            Err(err) if Self::is_duplicate_key_error(&err) => self.get_challenge(day).await,
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("generate service ending");
+        result
+    }
+
+    /// Starts the attempt of a player at the challenge of a day, asking its first question right away.
+    /// Returns the challenge with the attempt.
+    /// Returns an error if the day is not the one of the challenge in progress or if its challenge was not generated.
+    /// Returns an error if the player already attempted the challenge.
+    pub async fn start_attempt(
+        &self,
+        day: String,
+        user: String,
+    ) -> Result<(DailyChallenge, DailyAttempt), DailyChallengeServiceError> {
+        debug!("start_attempt service started");
+        let now = DateTime::now();
+        if day != self.day_of(now) {
+            return Err(Self::process_invalid_request_error(format!(
+                "The daily challenge of {} is not in progress",
+                day
+            )));
+        }
+        let challenge = self.get_challenge(day.clone()).await?;
+        let mut attempt = DailyAttempt {
+            id: None,
+            day: day.clone(),
+            user,
+            current_question: 0,
+            question_started_at: now,
+            score: 0,
+            answer_seconds: 0,
+            started_at: now,
+            finished_at: None,
+        };
+        let result = match self.attempt_repo.create_attempt(attempt.clone()).await {
+            Ok(insert) => {
+                attempt.id = insert.inserted_id.as_object_id();
+                Ok((challenge, attempt))
+            }
+            Err(err) if Self::is_duplicate_key_error(&err) => Err(DailyChallengeServiceError {
+                message: format!("The daily challenge of {} was already attempted", day),
+                kind: DailyChallengeServiceErrorKind::Conflict,
+            }),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("start_attempt service ending");
+        result
+    }
+
+    /// Gets the attempt of a player at the challenge of a day, with the challenge.
+    /// Returns an error if the player did not attempt the challenge.
+    pub async fn get_attempt(
+        &self,
+        day: String,
+        user: String,
+    ) -> Result<(DailyChallenge, DailyAttempt), DailyChallengeServiceError> {
+        debug!("get_attempt service started");
+        let attempt = self.find_attempt(day.clone(), user).await?;
+        let result = self
+            .get_challenge(day)
+            .await
+            .map(|challenge| (challenge, attempt));
+        debug!("get_attempt service ending");
+        result
+    }
+
+    async fn find_attempt(
+        &self,
+        day: String,
+        user: String,
+    ) -> Result<DailyAttempt, DailyChallengeServiceError> {
+        match self.attempt_repo.get_attempt(day.clone(), user).await {
+            Ok(Some(attempt)) => Ok(attempt),
+            Ok(None) => Err(Self::process_not_found_error(format!(
+                "The daily challenge of {} was not attempted",
+                day
+            ))),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    /// Answers the current question of the attempt of a player at the challenge of a day, and moves to the next one.
+    /// Answers given once the time is up count as no answer. Returns the challenge with the result of the answer.
+    /// Returns an error if the answer is not between 1 and 4, if the player did not attempt the challenge
+    /// or if the attempt is finished.
+    pub async fn answer(
+        &self,
+        day: String,
+        user: String,
+        answer: i8,
+    ) -> Result<(DailyChallenge, DailyFeedback), DailyChallengeServiceError> {
+        debug!("answer service started");
+        if !(1..=4).contains(&answer) {
+            return Err(Self::process_invalid_request_error(
+                "Answers are numbered from 1 to 4".to_string(),
+            ));
+        }
+        let (challenge, attempt) = self.get_attempt(day, user.clone()).await?;
+        let attempt_id = attempt.id.unwrap();
+        let conflict = || DailyChallengeServiceError {
+            message: format!(
+                "Daily attempt with id {} is finished or its question was already answered",
+                attempt_id
+            ),
+            kind: DailyChallengeServiceErrorKind::Conflict,
+        };
+        let mut question = match challenge.questions.get(attempt.current_question as usize) {
+            Some(question) if attempt.finished_at.is_none() => question.clone(),
+            _ => return Err(conflict()),
+        };
+        let now = DateTime::now();
+        let remaining_time = attempt.remaining_time(now, QUESTION_SECONDS);
+        let timed_out = remaining_time == 0;
+        let answer = if timed_out { 0 } else { answer };
+        let correct = answer == question.good_answer_number;
+        question.remaining_time = remaining_time;
+        let finished = attempt.current_question as usize + 1 >= challenge.questions.len();
+        let updated = self
+            .attempt_repo
+            .next_question(
+                attempt_id,
+                attempt.current_question,
+                correct as i32,
+                (QUESTION_SECONDS - remaining_time) as i64,
+                finished,
+                now,
+            )
+            .await
+            .map_err(Self::process_internal_error)?
+            .ok_or_else(conflict)?;
+        let game_answer = GameAnswer {
+            game_id: attempt_id.to_string(),
+            user,
+            answer,
+            question_index: attempt.current_question,
+            correct_answer: question.good_answer_number,
+            question,
+            practice: false,
+            daily: true,
+            wager: None,
+            power_up: None,
+            answer_seconds: Some(QUESTION_SECONDS - remaining_time),
+        };
+        if let Err(err) = self.game_answer_repo.save_game_answer(game_answer).await {
+            return Err(Self::process_internal_error(err));
+        }
+        debug!("answer service ending");
+        Ok((
+            challenge,
+            DailyFeedback {
+                answer,
+                correct,
+                timed_out,
+                attempt: updated,
+            },
+        ))
+    }
+
+    /// Gets a page of the leaderboard of a day, the best scores first and the fastest first among them,
+    /// and the attempt of the caller when they have one. Pages start at 1.
+    /// Returns an error if the page or its size are not valid.
+    pub async fn get_leaderboard(
+        &self,
+        day: String,
+        page: u64,
+        size: i64,
+        caller: Option<String>,
+    ) -> Result<DailyLeaderboard, DailyChallengeServiceError> {
+        debug!("get_leaderboard service started");
+        if page == 0 || !(1..=MAX_PAGE_SIZE).contains(&size) {
+            return Err(Self::process_invalid_request_error(format!(
+                "Pages start at 1 and hold between 1 and {} entries",
+                MAX_PAGE_SIZE
+            )));
+        }
+        let skip = (page - 1) * size as u64;
+        let attempts: Vec<DailyAttempt> = self
+            .attempt_repo
+            .get_attempts(day.clone(), skip, size)
+            .await
+            .map_err(Self::process_internal_error)?
+            .try_collect()
+            .await
+            .map_err(Self::process_internal_error)?;
+        let first_rank = match attempts.first() {
+            Some(attempt) => self.get_rank(attempt).await?,
+            None => skip + 1,
+        };
+        let attempts = rank_attempts(first_rank, skip, attempts);
+        let me = match caller {
+            Some(caller) => {
+                let attempt = self
+                    .attempt_repo
+                    .get_attempt(day.clone(), caller)
+                    .await
+                    .map_err(Self::process_internal_error)?;
+                match attempt {
+                    Some(attempt) => Some(RankedAttempt {
+                        rank: self.get_rank(&attempt).await?,
+                        attempt,
+                    }),
+                    None => None,
+                }
+            }
+            None => None,
+        };
+        debug!("get_leaderboard service ending");
+        Ok(DailyLeaderboard { day, attempts, me })
+    }
+
+    /// Gets the streaks of a player, the days in a row they attempted the daily challenge.
+    pub async fn get_streak(
+        &self,
+        user: String,
+    ) -> Result<DailyStreak, DailyChallengeServiceError> {
+        debug!("get_streak service started");
+        let result = match self.attempt_repo.get_attempt_days(user).await {
+            Ok(days) => Ok(streaks(&days, &self.day_of(DateTime::now()))),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("get_streak service ending");
+        result
+    }
+
+    async fn get_rank(&self, attempt: &DailyAttempt) -> Result<u64, DailyChallengeServiceError> {
+        match self
+            .attempt_repo
+            .count_ahead(attempt.day.clone(), attempt.score, attempt.answer_seconds)
+            .await
+        {
+            Ok(ahead) => Ok(ahead + 1),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    fn is_duplicate_key_error(err: &Error) -> bool {
+        matches!(
+            err.kind.as_ref(),
+            ErrorKind::Write(WriteFailure::WriteError(write_error))
+                if write_error.code == DUPLICATE_KEY_CODE
+        )
+    }
+
+    fn process_not_found_error(message: String) -> DailyChallengeServiceError {
+        DailyChallengeServiceError {
+            message,
+            kind: DailyChallengeServiceErrorKind::NotFound,
+        }
+    }
+
+    fn process_invalid_request_error(message: String) -> DailyChallengeServiceError {
+        DailyChallengeServiceError {
+            message,
+            kind: DailyChallengeServiceErrorKind::InvalidRequest,
+        }
+    }
+
+    fn process_internal_error(err: Error) -> DailyChallengeServiceError {
+        DailyChallengeServiceError {
+            message: err.to_string(),
+            kind: DailyChallengeServiceErrorKind::Internal,
+        }
+    }
+}
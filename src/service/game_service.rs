@@ -1,21 +1,53 @@
+use crate::dto::game_progress_dto::BUILT_IN_TOPICS;
 use crate::errors::game_service_error::{GameServiceError, GameServiceErrorKind};
-use crate::model::game::Game;
+use crate::model::buzzer::{buzzer_scores, GameBuzz};
+use crate::model::duel::{duel_damages, rank_duelists, DUEL_PLAYERS};
+use crate::model::game::{
+    knock_out, rank_players, rank_survivors, Game, GameElimination, GameHostAction,
+    GameHostActionKind, GameMode, GameScoreboard, GameStanding, GameStatus, GameStatusChange,
+};
 use crate::model::game_answer::GameAnswer;
 use crate::model::game_progress::GameProgress;
+use crate::model::hint::{hint_penalties, GameHint, MAX_HINT_PENALTY};
+use crate::model::power_up::{earned_power_ups, fifty_fifty, GamePowerUp, PowerUp};
+use crate::model::survival::{
+    count_misses, difficulty_tier, MAX_MISS_LIMIT, SURVIVAL_MAX_QUESTIONS,
+};
+use crate::model::team::{are_teams_valid, rank_teams, smallest_team};
+use crate::model::user::nickname_of;
+use crate::model::wager::{is_wager_valid, wager_balance, wager_scores, MIN_WAGER};
 use crate::repository::game_answer_repository::GameAnswerRepo;
 use crate::repository::game_progress_repository::GameProgressRepo;
 use crate::repository::game_repository::GameRepo;
+use crate::repository::question_repository::QuestionRepo;
+use crate::resource::game_resource::QUESTION_SECONDS;
+use crate::security::guard::AuthenticatedUser;
+use crate::security::password::{hash_password, verify_password};
+use crate::service::policy::can_control_game;
 use log::debug;
 use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{Bson, DateTime, Document};
 use mongodb::error::Error;
+use rand::Rng;
 use rocket::futures::TryStreamExt;
+use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::time::Duration;
+
+/// Time after which a game left in the lobby or paused is expired.
+pub const STALE_GAME_DURATION: Duration = Duration::from_secs(60 * 60);
+
+/// Characters used in join codes, ambiguous ones like `0`, `O`, `1` and `I` are left out.
+pub const JOIN_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+pub const JOIN_CODE_LENGTH: usize = 6;
+const JOIN_CODE_ATTEMPTS: usize = 5;
 
 /// Service for [Game] object to interact with the data layer
 pub struct GameService {
     game_repo: GameRepo,
     game_progress_repo: GameProgressRepo,
     game_answer_repo: GameAnswerRepo,
+    question_repo: QuestionRepo,
 }
 
 impl GameService {
@@ -24,16 +56,110 @@ impl GameService {
         let game_repo = GameRepo::init().await;
         let game_progress_repo = GameProgressRepo::init().await;
         let game_answer_repo = GameAnswerRepo::init().await;
+        let question_repo = QuestionRepo::init().await;
         GameService {
             game_repo,
             game_progress_repo,
             game_answer_repo,
+            question_repo,
         }
     }
 
     /// Creates a new [Game].
-    pub async fn create_game(&self, game: Game) -> Result<Game, GameServiceError> {
+    /// The game always starts in the [GameStatus::Lobby] status, with its creator as first player.
+    /// Duels are always played by two players, whatever the lobby settings.
+    /// Team games need at least two teams with distinct names, the creator joins the first one.
+    /// Survival games are played on a single topic and have no question count, they go on until everyone is out.
+    /// Buzzer and wager games, scored without the answer points, are not played with power-ups.
+    /// Private games get a join code, and may be protected by a password needed to join with the code.
+    /// They get a spectate code too, to be watched by spectators.
+    /// Returns an error if the lobby settings, the teams or the hint penalty are not valid.
+    /// Returns an error if the game has no topic or if a topic has no question.
+    pub async fn create_game(
+        &self,
+        mut game: Game,
+        password: Option<String>,
+    ) -> Result<Game, GameServiceError> {
         debug!("create_games service started");
+        if !game.lobby_settings.is_valid() {
+            return Err(Self::process_invalid_request_error(
+                "Lobby settings are not valid".to_string(),
+            ));
+        }
+        if game.mode == GameMode::Team && !are_teams_valid(&game.teams) {
+            return Err(Self::process_invalid_request_error(
+                "A team game needs at least two teams with distinct names".to_string(),
+            ));
+        }
+        if !(0..=MAX_HINT_PENALTY).contains(&game.hint_penalty()) {
+            return Err(Self::process_invalid_request_error(format!(
+                "A hint costs from 0 to {} points",
+                MAX_HINT_PENALTY
+            )));
+        }
+        self.check_topics(&game.topics).await?;
+        if game.power_ups && matches!(game.mode, GameMode::Buzzer | GameMode::Wager) {
+            return Err(Self::process_invalid_request_error(
+                "Buzzer and wager games are not played with power-ups".to_string(),
+            ));
+        }
+        if game.mode == GameMode::Survival {
+            if game.topics.len() != 1 {
+                return Err(Self::process_invalid_request_error(
+                    "A survival game is played on a single topic".to_string(),
+                ));
+            }
+            if !(1..=MAX_MISS_LIMIT).contains(&game.miss_limit()) {
+                return Err(Self::process_invalid_request_error(format!(
+                    "A survival game allows from 1 to {} misses",
+                    MAX_MISS_LIMIT
+                )));
+            }
+            game.question_number = SURVIVAL_MAX_QUESTIONS;
+        } else {
+            game.miss_limit = None;
+        }
+        if game.mode == GameMode::Duel {
+            game.lobby_settings.min_players = DUEL_PLAYERS;
+            game.lobby_settings.max_players = Some(DUEL_PLAYERS);
+        }
+        if game.mode != GameMode::Team {
+            game.teams = vec![];
+        }
+        game.join_code = None;
+        game.join_password_hash = None;
+        game.spectate_code = None;
+        if game.is_private {
+            game.join_code = Some(self.generate_code().await?);
+            game.spectate_code = Some(self.generate_code().await?);
+            if let Some(password) = password.filter(|password| !password.is_empty()) {
+                game.join_password_hash = Some(
+                    hash_password(&password)
+                        .map_err(|err| Self::process_hashing_error(err.to_string()))?,
+                );
+            }
+        }
+        game.users = game.creator.clone().into_iter().collect();
+        for team in &mut game.teams {
+            team.users = vec![];
+        }
+        if let Some(team) = game.teams.first_mut() {
+            team.users = game.users.clone();
+        }
+        game.team_standings = vec![];
+        game.ready_users = vec![];
+        game.kicked_users = vec![];
+        game.spectators = vec![];
+        game.host_actions = vec![];
+        game.used_power_ups = vec![];
+        game.revealed_hints = vec![];
+        let now = DateTime::now();
+        game.status = GameStatus::Lobby;
+        game.status_changed_at = Some(now);
+        game.status_history = vec![GameStatusChange {
+            status: GameStatus::Lobby,
+            changed_at: now,
+        }];
         let insert = self.game_repo.create_game(game.clone()).await;
         let result = match insert {
             Ok(insert) => {
@@ -47,10 +173,15 @@ impl GameService {
         result
     }
 
-    /// Gets all the [Game]s.
-    pub async fn get_games(&self) -> Result<Vec<Game>, GameServiceError> {
+    /// Gets all the public [Game]s having one of the given statuses.
+    /// Stale games are expired beforehand so they are not listed as joinable.
+    pub async fn get_games(
+        &self,
+        statuses: Vec<GameStatus>,
+    ) -> Result<Vec<Game>, GameServiceError> {
         debug!("get_games service started");
-        let result = match self.game_repo.get_games().await {
+        self.expire_stale_games().await?;
+        let result = match self.game_repo.get_games(statuses).await {
             Ok(mut games) => {
                 let mut games_output = vec![];
                 while let Some(game) = games.try_next().await.unwrap().or(None) {
@@ -70,7 +201,7 @@ impl GameService {
     pub async fn get_game(&self, id: String) -> Result<Game, GameServiceError> {
         debug!("get_game service started");
         let object_id =
-            ObjectId::from_str(id.clone().as_str()).expect("Failed to create object id");
+            ObjectId::from_str(&id).map_err(|_| Self::process_not_found_error(id.clone()))?;
         let result = match self.game_repo.get_game(object_id).await {
             Ok(game) => match game {
                 None => Err(Self::process_not_found_error(id)),
@@ -82,26 +213,1187 @@ impl GameService {
         result
     }
 
-    /// Patches a [Game] by id.
+    /// Moves a [Game] to the `next` status.
     /// Returns an error if the game does not exist.
-    /// Returns an error if the id is not a valid ObjectId.
-    pub async fn patch_game(&self, id: String) -> Result<String, GameServiceError> {
-        debug!("patch_game service started");
+    /// Returns an error if the move is not allowed from the current status of the game.
+    pub async fn transition_game(
+        &self,
+        id: String,
+        next: GameStatus,
+    ) -> Result<Game, GameServiceError> {
+        debug!("transition_game service started");
+        let mut game = self.get_game(id.clone()).await?;
+        if !game.status.can_transition_to(next) {
+            return Err(Self::process_invalid_transition_error(
+                id,
+                game.status,
+                next,
+            ));
+        }
+        let changed_at = DateTime::now();
+        let update = self
+            .game_repo
+            .update_status(game.id.unwrap(), game.status, next, changed_at)
+            .await;
+        let result = match update {
+            Ok(update) if update.modified_count == 1 => {
+                game.status = next;
+                game.status_changed_at = Some(changed_at);
+                game.status_history.push(GameStatusChange {
+                    status: next,
+                    changed_at,
+                });
+                Ok(game)
+            }
+            // The status changed concurrently between the read and the update
+            Ok(_) => Err(Self::process_invalid_transition_error(
+                id,
+                game.status,
+                next,
+            )),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("transition_game service ending");
+        result
+    }
+
+    /// Finishes a running [Game] and saves its final standings, computed from the answers of the players.
+    /// Players who did not answer correctly are ranked with a score of zero, less the penalties of the hints they asked for.
+    /// Returns an error if the game does not exist or cannot be finished from its current status.
+    pub async fn finish_game(&self, id: String) -> Result<Game, GameServiceError> {
+        debug!("finish_game service started");
+        let mut game = self
+            .transition_game(id.clone(), GameStatus::Finished)
+            .await?;
+        let scoreboard = self.compute_scoreboard(&game).await?;
+        game.standings = scoreboard.standings;
+        game.team_standings = scoreboard.team_standings;
+        let result = match self
+            .game_repo
+            .set_standings(
+                game.id.unwrap(),
+                game.standings.clone(),
+                game.team_standings.clone(),
+            )
+            .await
+        {
+            Ok(_) => Ok(game),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("finish_game service ending");
+        result
+    }
+
+    /// Gets the standings of a [Game] from the answers given so far, the final standings once it is finished.
+    /// Team games give the standings of their teams too.
+    /// Returns an error if the game does not exist.
+    pub async fn get_scoreboard(&self, id: String) -> Result<GameScoreboard, GameServiceError> {
+        debug!("get_scoreboard service started");
+        let game = self.get_game(id).await?;
+        let result = match game.status {
+            GameStatus::Finished => Ok(GameScoreboard {
+                standings: game.standings,
+                team_standings: game.team_standings,
+            }),
+            _ => self.compute_scoreboard(&game).await,
+        };
+        debug!("get_scoreboard service ending");
+        result
+    }
+
+    async fn compute_scoreboard(&self, game: &Game) -> Result<GameScoreboard, GameServiceError> {
+        let mut scores: Vec<(String, i32)> = match game.mode {
+            GameMode::Buzzer => buzzer_scores(&game.buzzes),
+            GameMode::Wager => wager_scores(
+                &game.users,
+                &self.get_game_result(game.id.unwrap().to_string()).await?,
+            ),
+            _ => self.aggregate_scores(game).await?,
+        };
+        scores.retain(|(user, _)| game.users.contains(user));
+        for user in &game.users {
+            if !scores.iter().any(|(scored, _)| scored == user) {
+                scores.push((user.clone(), 0));
+            }
+        }
+        for (user, score) in scores.iter_mut() {
+            *score -= hint_penalties(user, &game.revealed_hints);
+        }
+        let standings: Vec<GameStanding> = match game.mode {
+            GameMode::Classic | GameMode::Team | GameMode::Buzzer | GameMode::Wager => {
+                rank_players(scores)
+            }
+            GameMode::Elimination | GameMode::Survival => {
+                rank_survivors(scores, &game.eliminations)
+            }
+            GameMode::Duel => rank_duelists(scores, &game.damages),
+        };
+        let team_standings = match game.mode {
+            GameMode::Team => rank_teams(&game.teams, &standings, game.team_scoring),
+            _ => vec![],
+        };
+        Ok(GameScoreboard {
+            standings,
+            team_standings,
+        })
+    }
+
+    async fn aggregate_scores(&self, game: &Game) -> Result<Vec<(String, i32)>, GameServiceError> {
+        let scores: Vec<Document> = self
+            .game_answer_repo
+            .aggregate_scores(game.id.unwrap().to_string())
+            .await
+            .map_err(Self::process_internal_error)?
+            .try_collect()
+            .await
+            .map_err(Self::process_internal_error)?;
+        Ok(scores
+            .iter()
+            .filter_map(|score| {
+                let user = score.get_str("_id").ok()?.to_string();
+                let points = match score.get("score") {
+                    Some(Bson::Int32(points)) => *points,
+                    Some(Bson::Int64(points)) => *points as i32,
+                    _ => 0,
+                };
+                Some((user, points))
+            })
+            .collect())
+    }
+
+    /// Checks the stake of a player answering a question of a wager [Game] against their points,
+    /// the points won and lost on the previous questions. Returns the stake to store with the answer.
+    /// Games of the other modes take no stake, none is returned whatever the given stake.
+    /// Returns an error if no stake is given, or if it is less than [MIN_WAGER] or more than the points of the player.
+    pub async fn check_wager(
+        &self,
+        game: &Game,
+        user: &str,
+        question_index: i8,
+        wager: Option<i32>,
+    ) -> Result<Option<i32>, GameServiceError> {
+        if game.mode != GameMode::Wager {
+            return Ok(None);
+        }
+        let wager = wager.ok_or_else(|| {
+            Self::process_invalid_request_error("A stake is needed to answer".to_string())
+        })?;
+        let previous: Vec<GameAnswer> = self
+            .get_game_result(game.id.unwrap().to_string())
+            .await?
+            .into_iter()
+            .filter(|answer| answer.question_index != question_index)
+            .collect();
+        let balance = wager_balance(user, &previous);
+        if !is_wager_valid(wager, balance) {
+            return Err(Self::process_invalid_request_error(format!(
+                "User {} can stake from {} to {} points",
+                user,
+                MIN_WAGER,
+                balance.max(MIN_WAGER)
+            )));
+        }
+        Ok(Some(wager))
+    }
+
+    /// Gives the difficulty tier of the questions of the given topics answered in games, by question text.
+    /// The tier of a question comes from the share of correct answers it got.
+    pub async fn get_question_tiers(
+        &self,
+        topics: Vec<String>,
+    ) -> Result<BTreeMap<String, u8>, GameServiceError> {
+        debug!("get_question_tiers service started");
+        let rates: Vec<Document> = self
+            .game_answer_repo
+            .aggregate_question_rates(topics)
+            .await
+            .map_err(Self::process_internal_error)?
+            .try_collect()
+            .await
+            .map_err(Self::process_internal_error)?;
+        let tiers = rates
+            .iter()
+            .filter_map(|rate| {
+                let question_text = rate.get_str("_id").ok()?.to_string();
+                let correct_rate = rate.get_f64("correct_rate").ok();
+                Some((question_text, difficulty_tier(correct_rate)))
+            })
+            .collect();
+        debug!("get_question_tiers service ending");
+        Ok(tiers)
+    }
+
+    /// Spends a power-up of a player of a [Game] played with power-ups, on the current question.
+    /// Players start with a power-up and earn one more with each streak of correct answers, see [earned_power_ups].
+    /// A single power-up is used per question, before answering it and while the player has time left.
+    /// [PowerUp::Freeze] needs an opponent still in the game as target, the other power-ups take none.
+    /// Returns the use of the power-up, with the answers removed by a [PowerUp::FiftyFifty].
+    /// Returns an error if the game is not played with power-ups, or if the player has no power-up left or an invalid target.
+    /// Returns an error if the player is not in the game, or cannot use a power-up on the question anymore.
+    pub async fn use_power_up(
+        &self,
+        id: String,
+        user: String,
+        power_up: PowerUp,
+        target: Option<String>,
+    ) -> Result<GamePowerUp, GameServiceError> {
+        debug!("use_power_up service started");
+        let game = self.get_game(id.clone()).await?;
+        if !game.power_ups {
+            return Err(Self::process_invalid_request_error(format!(
+                "Game with id {} is not played with power-ups",
+                id
+            )));
+        }
+        if !game.survivors().contains(&user) || game.kicked_users.contains(&user) {
+            return Err(GameServiceError {
+                message: format!("User {} is not playing game with id {}", user, id),
+                kind: GameServiceErrorKind::Forbidden,
+            });
+        }
+        if game.status != GameStatus::Running {
+            return Err(Self::process_not_running_error(id));
+        }
+        let progress = self.get_game_progress(id.clone()).await?;
+        let question_index = progress.current_question;
+        let answers = self.get_game_result(id.clone()).await?;
+        let answered = answers
+            .iter()
+            .any(|answer| answer.user == user && answer.question_index == question_index);
+        let time_left = game.time_left(
+            &user,
+            question_index,
+            progress.question_content.remaining_time,
+            progress.overtime,
+        );
+        if answered || time_left == 0 {
+            return Err(Self::process_power_up_conflict_error(
+                &user,
+                question_index,
+                &id,
+            ));
+        }
+        let earned = earned_power_ups(&user, &answers, question_index, |index| {
+            game.is_question_skipped(index)
+        });
+        if earned <= game.power_ups_used_by(&user) as i32 {
+            return Err(Self::process_invalid_request_error(format!(
+                "User {} has no power-up left",
+                user
+            )));
+        }
+        let target = match power_up {
+            PowerUp::Freeze => Some(
+                target
+                    .filter(|target| {
+                        *target != user
+                            && game.survivors().contains(target)
+                            && !game.kicked_users.contains(target)
+                    })
+                    .ok_or_else(|| {
+                        Self::process_invalid_request_error(
+                            "A freeze needs an opponent still in the game as target".to_string(),
+                        )
+                    })?,
+            ),
+            _ => None,
+        };
+        let removed_answers = match power_up {
+            PowerUp::FiftyFifty => fifty_fifty(progress.question_content.good_answer_number),
+            _ => vec![],
+        };
+        let used = GamePowerUp {
+            user,
+            power_up,
+            question_index,
+            target,
+            removed_answers,
+            used_at: DateTime::now(),
+        };
+        let result = match self
+            .game_repo
+            .push_power_up(game.id.unwrap(), used.clone())
+            .await
+        {
+            Ok(Some(_)) => Ok(used),
+            Ok(None) => Err(Self::process_power_up_conflict_error(
+                &used.user,
+                question_index,
+                &id,
+            )),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("use_power_up service ending");
+        result
+    }
+
+    /// Reveals to a player of a running [Game] the next hint of the current question, at the cost of the hint penalty
+    /// of the game, taken from their score. Hints are revealed before answering, while the player has time left.
+    /// Returns the revealed hint.
+    /// Returns an error if the question has no hint left for the player.
+    /// Returns an error if the player is not in the game, or cannot ask for a hint on the question anymore.
+    pub async fn reveal_hint(
+        &self,
+        id: String,
+        user: String,
+    ) -> Result<GameHint, GameServiceError> {
+        debug!("reveal_hint service started");
+        let game = self.get_game(id.clone()).await?;
+        if !game.survivors().contains(&user) || game.kicked_users.contains(&user) {
+            return Err(GameServiceError {
+                message: format!("User {} is not playing game with id {}", user, id),
+                kind: GameServiceErrorKind::Forbidden,
+            });
+        }
+        if game.status != GameStatus::Running {
+            return Err(Self::process_not_running_error(id));
+        }
+        let progress = self.get_game_progress(id.clone()).await?;
+        let question_index = progress.current_question;
+        let answered = self
+            .get_game_result(id.clone())
+            .await?
+            .iter()
+            .any(|answer| answer.user == user && answer.question_index == question_index);
+        let time_left = game.time_left(
+            &user,
+            question_index,
+            progress.question_content.remaining_time,
+            progress.overtime,
+        );
+        if answered || time_left == 0 {
+            return Err(Self::process_hint_conflict_error(
+                &user,
+                question_index,
+                &id,
+            ));
+        }
+        let hint_index = game.hints_revealed_to(&user, question_index);
+        let hint = progress
+            .question_content
+            .hints
+            .get(hint_index)
+            .cloned()
+            .ok_or_else(|| {
+                Self::process_invalid_request_error(format!(
+                    "Question {} of game with id {} has no hint left",
+                    question_index, id
+                ))
+            })?;
+        let hint = GameHint {
+            user,
+            question_index,
+            hint_index: hint_index as i32,
+            hint,
+            penalty: game.hint_penalty(),
+            revealed_at: DateTime::now(),
+        };
+        let result = match self
+            .game_repo
+            .push_hint(game.id.unwrap(), hint.clone())
+            .await
+        {
+            Ok(Some(_)) => Ok(hint),
+            Ok(None) => Err(Self::process_hint_conflict_error(
+                &hint.user,
+                question_index,
+                &id,
+            )),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("reveal_hint service ending");
+        result
+    }
+
+    /// Records the answer of a player of a buzzer [Game], in the order answers are received.
+    /// Only the first correct answer to a question wins it, and a wrong answer locks the player out of the question.
+    /// Returns the updated game.
+    /// Returns an error if the player already answered the question, if it was already won or if the game is not running.
+    /// Returns an error if the user does not play the game.
+    pub async fn buzz(&self, answer: &GameAnswer) -> Result<Game, GameServiceError> {
+        debug!("buzz service started");
+        let id = answer.game_id.clone();
         let object_id =
-            ObjectId::from_str(id.clone().as_str()).expect("Failed to create object id");
-        let result = match self.game_repo.patch_game(object_id).await {
-            Ok(_) => Ok("".to_string()),
+            ObjectId::from_str(&id).map_err(|_| Self::process_not_found_error(id.clone()))?;
+        let buzz = GameBuzz {
+            user: answer.user.clone(),
+            question_index: answer.question_index,
+            answer: answer.answer,
+            correct: answer.answer == answer.correct_answer,
+            buzzed_at: DateTime::now(),
+        };
+        let result = match self.game_repo.push_buzz(object_id, buzz).await {
+            Ok(Some(game)) => Ok(game),
+            Ok(None) => Err(GameServiceError {
+                message: format!(
+                    "User {} cannot answer question {} of game with id {} anymore",
+                    answer.user, answer.question_index, id
+                ),
+                kind: GameServiceErrorKind::Conflict,
+            }),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("buzz service ending");
+        result
+    }
+
+    /// Applies the rules of the mode of a running [Game] once a question is over, and returns the updated game.
+    /// In [GameMode::Elimination], the survivors who did not answer the question correctly are knocked out.
+    /// In [GameMode::Duel], the answers deal their damages.
+    /// In [GameMode::Survival], the survivors who reached the miss limit are out.
+    /// Nothing happens when the host skipped the question.
+    pub async fn end_question(
+        &self,
+        id: String,
+        question_index: i8,
+    ) -> Result<Game, GameServiceError> {
+        debug!("end_question service started");
+        let game = self.get_game(id.clone()).await?;
+        if game.is_question_skipped(question_index) {
+            return Ok(game);
+        }
+        if matches!(
+            game.mode,
+            GameMode::Classic | GameMode::Team | GameMode::Buzzer | GameMode::Wager
+        ) {
+            return Ok(game);
+        }
+        let answers = self.get_game_result(id.clone()).await?;
+        let question_answers: Vec<GameAnswer> = answers
+            .iter()
+            .filter(|answer| answer.question_index == question_index)
+            .cloned()
+            .collect();
+        let result = match game.mode {
+            GameMode::Duel => self.deal_damages(game, &question_answers).await,
+            GameMode::Survival => {
+                let out: Vec<String> = game
+                    .survivors()
+                    .into_iter()
+                    .filter(|user| {
+                        let misses = count_misses(user, &answers, question_index, |index| {
+                            game.is_question_skipped(index)
+                        });
+                        misses >= game.miss_limit()
+                    })
+                    .collect();
+                self.eliminate_players(game, question_index, out).await
+            }
+            _ => {
+                let correct_players: Vec<String> = question_answers
+                    .iter()
+                    .filter(|answer| answer.answer == answer.correct_answer)
+                    .map(|answer| answer.user.clone())
+                    .collect();
+                let out = knock_out(&game.survivors(), &correct_players);
+                self.eliminate_players(game, question_index, out).await
+            }
+        };
+        debug!("end_question service ending");
+        result
+    }
+
+    async fn eliminate_players(
+        &self,
+        game: Game,
+        question_index: i8,
+        out: Vec<String>,
+    ) -> Result<Game, GameServiceError> {
+        let now = DateTime::now();
+        let eliminations: Vec<GameElimination> = out
+            .into_iter()
+            .map(|user| GameElimination {
+                user,
+                question_index,
+                eliminated_at: now,
+            })
+            .collect();
+        if eliminations.is_empty() {
+            return Ok(game);
+        }
+        let id = game.id.unwrap();
+        match self.game_repo.push_eliminations(id, eliminations).await {
+            Ok(Some(game)) => Ok(game),
+            Ok(None) => Err(Self::process_not_found_error(id.to_string())),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    async fn deal_damages(
+        &self,
+        game: Game,
+        answers: &[GameAnswer],
+    ) -> Result<Game, GameServiceError> {
+        let damages = duel_damages(
+            &game.users,
+            answers,
+            &game.damages,
+            QUESTION_SECONDS,
+            DateTime::now(),
+        );
+        if damages.is_empty() {
+            return Ok(game);
+        }
+        let id = game.id.unwrap();
+        match self.game_repo.push_damages(id, damages).await {
+            Ok(Some(game)) => Ok(game),
+            Ok(None) => Err(Self::process_not_found_error(id.to_string())),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    /// Starts a [Game] on behalf of its host, the game enters its countdown.
+    /// Returns an error if the caller is neither the host of the game nor a moderator.
+    /// Returns an error if there are not enough players or if some players are not ready.
+    pub async fn start_game(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+    ) -> Result<Game, GameServiceError> {
+        let game = self.get_hosted_game(id.clone(), &caller).await?;
+        if game.status == GameStatus::Lobby && !game.can_start() {
+            return Err(Self::process_invalid_request_error(format!(
+                "Game with id {} is waiting for players",
+                id
+            )));
+        }
+        self.control_game(id, caller, GameHostActionKind::Start, GameStatus::Countdown)
+            .await
+    }
+
+    /// Pauses a running [Game] on behalf of its host.
+    /// Returns an error if the caller is neither the host of the game nor a moderator.
+    pub async fn pause_game(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+    ) -> Result<Game, GameServiceError> {
+        self.control_game(id, caller, GameHostActionKind::Pause, GameStatus::Paused)
+            .await
+    }
+
+    /// Resumes a paused [Game] on behalf of its host.
+    /// Returns an error if the caller is neither the host of the game nor a moderator.
+    pub async fn resume_game(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+    ) -> Result<Game, GameServiceError> {
+        self.control_game(id, caller, GameHostActionKind::Resume, GameStatus::Running)
+            .await
+    }
+
+    /// Aborts a [Game] on behalf of its host.
+    /// Returns an error if the caller is neither the host of the game nor a moderator.
+    pub async fn abort_game(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+    ) -> Result<Game, GameServiceError> {
+        self.control_game(id, caller, GameHostActionKind::Abort, GameStatus::Aborted)
+            .await
+    }
+
+    /// Ends the current question of a running [Game] on behalf of its host.
+    /// Returns an error if the caller is neither the host of the game nor a moderator.
+    /// Returns an error if the game is not running.
+    pub async fn skip_question(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+    ) -> Result<Game, GameServiceError> {
+        debug!("skip_question service started");
+        let mut game = self.get_hosted_game(id.clone(), &caller).await?;
+        if game.status != GameStatus::Running {
+            return Err(Self::process_invalid_request_error(format!(
+                "Game with id {} is not running",
+                id
+            )));
+        }
+        let progress = self.get_game_progress(id).await?;
+        let mut action = Self::host_action(GameHostActionKind::Skip, caller.username, None);
+        action.question_index = Some(progress.current_question);
+        let result = self
+            .record_host_action(&mut game, action)
+            .await
+            .map(|_| game);
+        debug!("skip_question service ending");
+        result
+    }
+
+    /// Removes a player from a [Game] on behalf of its host, the player cannot join the game again.
+    /// Returns an error if the caller is neither the host of the game nor a moderator.
+    /// Returns an error if the player is not part of the game.
+    pub async fn kick_player(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+        player: String,
+    ) -> Result<Game, GameServiceError> {
+        debug!("kick_player service started");
+        let mut game = self.get_hosted_game(id.clone(), &caller).await?;
+        if game.status.is_over() || player == caller.username || !game.users.contains(&player) {
+            return Err(Self::process_invalid_request_error(format!(
+                "Player {} cannot be kicked from game with id {}",
+                player, id
+            )));
+        }
+        let action = Self::host_action(
+            GameHostActionKind::Kick,
+            caller.username,
+            Some(player.clone()),
+        );
+        let result = match self
+            .game_repo
+            .kick_user(game.id.unwrap(), action.clone())
+            .await
+        {
+            Ok(_) => {
+                self.remove_from_team(&mut game, &player).await?;
+                game.users.retain(|user| user != &player);
+                game.kicked_users.push(player);
+                game.host_actions.push(action);
+                Ok(game)
+            }
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("kick_player service ending");
+        result
+    }
+
+    /// Hands the host role of a [Game] over to one of its players.
+    /// Returns an error if the caller is neither the host of the game nor a moderator.
+    /// Returns an error if the new host is not part of the game.
+    pub async fn transfer_host(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+        player: String,
+    ) -> Result<Game, GameServiceError> {
+        debug!("transfer_host service started");
+        let mut game = self.get_hosted_game(id.clone(), &caller).await?;
+        let invalid_error = Self::process_invalid_request_error(format!(
+            "Player {} cannot become host of game with id {}",
+            player, id
+        ));
+        if game.status.is_over() || !game.users.contains(&player) {
+            return Err(invalid_error);
+        }
+        let action = Self::host_action(
+            GameHostActionKind::TransferHost,
+            caller.username,
+            Some(player.clone()),
+        );
+        let result = match self
+            .game_repo
+            .transfer_host(game.id.unwrap(), game.creator.clone(), action.clone())
+            .await
+        {
+            Ok(update) if update.modified_count == 1 => {
+                game.creator = Some(player);
+                game.host_actions.push(action);
+                Ok(game)
+            }
+            Ok(_) => Err(invalid_error),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("transfer_host service ending");
+        result
+    }
+
+    async fn control_game(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+        kind: GameHostActionKind,
+        next: GameStatus,
+    ) -> Result<Game, GameServiceError> {
+        debug!("control_game service started");
+        self.get_hosted_game(id.clone(), &caller).await?;
+        let mut game = self.transition_game(id, next).await?;
+        let action = Self::host_action(kind, caller.username, None);
+        let result = self
+            .record_host_action(&mut game, action)
+            .await
+            .map(|_| game);
+        debug!("control_game service ending");
+        result
+    }
+
+    /// Gets a [Game] the caller may control, as its host or as a moderator.
+    async fn get_hosted_game(
+        &self,
+        id: String,
+        caller: &AuthenticatedUser,
+    ) -> Result<Game, GameServiceError> {
+        let game = self.get_game(id.clone()).await?;
+        if !can_control_game(caller, &game) {
+            return Err(GameServiceError {
+                message: format!(
+                    "User {} is not allowed to control game with id {}",
+                    caller.username, id
+                ),
+                kind: GameServiceErrorKind::Forbidden,
+            });
+        }
+        Ok(game)
+    }
+
+    async fn record_host_action(
+        &self,
+        game: &mut Game,
+        action: GameHostAction,
+    ) -> Result<(), GameServiceError> {
+        self.game_repo
+            .push_host_action(game.id.unwrap(), action.clone())
+            .await
+            .map_err(Self::process_internal_error)?;
+        game.host_actions.push(action);
+        Ok(())
+    }
+
+    fn host_action(
+        kind: GameHostActionKind,
+        host: String,
+        player: Option<String>,
+    ) -> GameHostAction {
+        GameHostAction {
+            kind,
+            host,
+            player,
+            question_index: None,
+            performed_at: DateTime::now(),
+        }
+    }
+
+    /// Expires the games left in the lobby or paused for longer than [STALE_GAME_DURATION].
+    pub async fn expire_stale_games(&self) -> Result<(), GameServiceError> {
+        debug!("expire_stale_games service started");
+        let stale_before = DateTime::from_millis(
+            DateTime::now().timestamp_millis() - STALE_GAME_DURATION.as_millis() as i64,
+        );
+        for status in [GameStatus::Lobby, GameStatus::Paused] {
+            self.game_repo
+                .update_stale_statuses(status, GameStatus::Expired, stale_before)
+                .await
+                .map_err(Self::process_internal_error)?;
+        }
+        debug!("expire_stale_games service ending");
+        Ok(())
+    }
+
+    /// Migrates the games stored before the status lifecycle existed.
+    /// Returns the number of migrated games.
+    pub async fn migrate_games(&self) -> Result<u64, GameServiceError> {
+        debug!("migrate_games service started");
+        let result = self
+            .game_repo
+            .migrate_legacy_statuses()
+            .await
+            .map_err(Self::process_internal_error);
+        debug!("migrate_games service ending");
+        result
+    }
+
+    /// Registers a player in the lobby of a public [Game].
+    /// Registering a player already in the game changes nothing.
+    /// Returns an error if the game is private, private games are joined with their join code.
+    /// Returns an error if the game is not in the lobby, is full or if the player was kicked from it.
+    pub async fn register_user(&self, id: String, user: String) -> Result<Game, GameServiceError> {
+        debug!("register_user service started");
+        let game = self.get_game(id.clone()).await?;
+        if game.is_private && !game.users.contains(&user) {
+            return Err(GameServiceError {
+                message: format!("Game with id {} must be joined with its join code", id),
+                kind: GameServiceErrorKind::Forbidden,
+            });
+        }
+        let result = self.add_user(game, user).await;
+        debug!("register_user service ending");
+        result
+    }
+
+    /// Registers a player in the lobby of the [Game] having the given join code.
+    /// Returns an error if no game has this join code.
+    /// Returns an error if the game is protected by a password and the given password does not match.
+    /// Returns an error if the game is not in the lobby, is full or if the player was kicked from it.
+    pub async fn join_game(
+        &self,
+        code: String,
+        user: String,
+        password: Option<String>,
+    ) -> Result<Game, GameServiceError> {
+        debug!("join_game service started");
+        let code = code.to_uppercase();
+        let game = match self.game_repo.get_game_by_join_code(code.clone()).await {
+            Ok(Some(game)) => game,
+            Ok(None) => {
+                return Err(GameServiceError {
+                    message: format!("Game with join code {} does not exist", code),
+                    kind: GameServiceErrorKind::NotFound,
+                })
+            }
+            Err(err) => return Err(Self::process_internal_error(err)),
+        };
+        if let Some(hash) = &game.join_password_hash {
+            if !verify_password(password.as_deref().unwrap_or_default(), hash) {
+                return Err(GameServiceError {
+                    message: format!("Wrong password for game with join code {}", code),
+                    kind: GameServiceErrorKind::Forbidden,
+                });
+            }
+        }
+        let result = self.add_user(game, user).await;
+        debug!("join_game service ending");
+        result
+    }
+
+    /// Registers a spectator of a public [Game], who watches it without playing.
+    /// Returns the game.
+    /// Returns an error if the game is private, it is spectated with its spectate code.
+    /// Returns an error if the user plays or was kicked from the game, or if the game is over.
+    pub async fn spectate_game(&self, id: String, user: String) -> Result<Game, GameServiceError> {
+        debug!("spectate_game service started");
+        let game = self.get_game(id.clone()).await?;
+        if game.is_private {
+            return Err(GameServiceError {
+                message: format!(
+                    "Game with id {} must be spectated with its spectate code",
+                    id
+                ),
+                kind: GameServiceErrorKind::Forbidden,
+            });
+        }
+        let result = self.add_spectator(game, user).await;
+        debug!("spectate_game service ending");
+        result
+    }
+
+    /// Registers a spectator of the [Game] having the given spectate code.
+    /// Returns the game.
+    /// Returns an error if no game has this spectate code.
+    /// Returns an error if the user plays or was kicked from the game, or if the game is over.
+    pub async fn spectate_by_code(
+        &self,
+        code: String,
+        user: String,
+    ) -> Result<Game, GameServiceError> {
+        debug!("spectate_by_code service started");
+        let code = code.to_uppercase();
+        let game = match self.game_repo.get_game_by_spectate_code(code.clone()).await {
+            Ok(Some(game)) => game,
+            Ok(None) => {
+                return Err(GameServiceError {
+                    message: format!("Game with spectate code {} does not exist", code),
+                    kind: GameServiceErrorKind::NotFound,
+                })
+            }
+            Err(err) => return Err(Self::process_internal_error(err)),
+        };
+        let result = self.add_spectator(game, user).await;
+        debug!("spectate_by_code service ending");
+        result
+    }
+
+    /// Removes a spectator from a [Game] at their own request.
+    /// Returns the game.
+    /// Returns an error if the user is not spectating the game.
+    pub async fn stop_spectating(
+        &self,
+        id: String,
+        user: String,
+    ) -> Result<Game, GameServiceError> {
+        debug!("stop_spectating service started");
+        let game = self.get_game(id.clone()).await?;
+        if !game.spectators.contains(&user) {
+            return Err(Self::process_invalid_request_error(format!(
+                "User {} is not spectating game with id {}",
+                user, id
+            )));
+        }
+        let result = match self
+            .game_repo
+            .remove_spectator(game.id.unwrap(), user)
+            .await
+        {
+            Ok(Some(game)) => Ok(game),
+            Ok(None) => Err(Self::process_not_found_error(id)),
             Err(err) => Err(Self::process_internal_error(err)),
         };
-        debug!("patch_game service ending");
+        debug!("stop_spectating service ending");
         result
     }
 
-    /// Saves the game
-    pub async fn save_users_in_game(&self, game: &Game) {
-        debug!("save_game service started");
-        let _ = self.game_repo.save_users_in_game(game.clone()).await;
-        debug!("save_game service ending");
+    async fn add_spectator(&self, game: Game, user: String) -> Result<Game, GameServiceError> {
+        let id = game.id.unwrap();
+        if game.spectators.contains(&user) {
+            return Ok(game);
+        }
+        if game.users.contains(&user) {
+            return Err(GameServiceError {
+                message: format!("User {} plays game with id {}", user, id),
+                kind: GameServiceErrorKind::Conflict,
+            });
+        }
+        if game.kicked_users.contains(&user) {
+            return Err(GameServiceError {
+                message: format!("User {} was kicked from game with id {}", user, id),
+                kind: GameServiceErrorKind::Forbidden,
+            });
+        }
+        match self.game_repo.add_spectator(id, user.clone()).await {
+            Ok(Some(game)) => Ok(game),
+            Ok(None) => Err(Self::process_invalid_request_error(format!(
+                "User {} cannot spectate game with id {}",
+                user, id
+            ))),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    /// Replaces the join code of a private [Game] on behalf of its host, the previous code stops working.
+    /// Returns an error if the caller is neither the host of the game nor a moderator.
+    /// Returns an error if the game is not private or is over.
+    pub async fn rotate_join_code(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+    ) -> Result<Game, GameServiceError> {
+        debug!("rotate_join_code service started");
+        let code = Some(self.generate_code().await?);
+        let result = self.update_join_code(id, caller, code).await;
+        debug!("rotate_join_code service ending");
+        result
+    }
+
+    /// Removes the join code of a private [Game] on behalf of its host, nobody can join it anymore.
+    /// Returns an error if the caller is neither the host of the game nor a moderator.
+    /// Returns an error if the game is not private or is over.
+    pub async fn revoke_join_code(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+    ) -> Result<Game, GameServiceError> {
+        debug!("revoke_join_code service started");
+        let result = self.update_join_code(id, caller, None).await;
+        debug!("revoke_join_code service ending");
+        result
+    }
+
+    async fn update_join_code(
+        &self,
+        id: String,
+        caller: AuthenticatedUser,
+        code: Option<String>,
+    ) -> Result<Game, GameServiceError> {
+        let mut game = self.get_hosted_game(id.clone(), &caller).await?;
+        if !game.is_private || game.status.is_over() {
+            return Err(Self::process_invalid_request_error(format!(
+                "Join code of game with id {} cannot be changed",
+                id
+            )));
+        }
+        match self
+            .game_repo
+            .set_join_code(game.id.unwrap(), code.clone())
+            .await
+        {
+            Ok(_) => {
+                game.join_code = code;
+                Ok(game)
+            }
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    /// Generates a join or spectate code, used by no game as join code nor as spectate code.
+    async fn generate_code(&self) -> Result<String, GameServiceError> {
+        for _ in 0..JOIN_CODE_ATTEMPTS {
+            let code: String = (0..JOIN_CODE_LENGTH)
+                .map(|_| {
+                    let index = rand::thread_rng().gen_range(0..JOIN_CODE_ALPHABET.len());
+                    JOIN_CODE_ALPHABET[index] as char
+                })
+                .collect();
+            let joinable = self.game_repo.get_game_by_join_code(code.clone()).await;
+            let watchable = self.game_repo.get_game_by_spectate_code(code.clone()).await;
+            match (joinable, watchable) {
+                (Ok(None), Ok(None)) => return Ok(code),
+                (Err(err), _) | (_, Err(err)) => return Err(Self::process_internal_error(err)),
+                _ => continue,
+            }
+        }
+        Err(GameServiceError {
+            message: "Failed to generate a unique code".to_string(),
+            kind: GameServiceErrorKind::Internal,
+        })
+    }
+
+    async fn add_user(&self, game: Game, user: String) -> Result<Game, GameServiceError> {
+        if game.users.contains(&user) {
+            return Ok(game);
+        }
+        let id = game.id.unwrap();
+        match self.game_repo.add_user(id, user.clone()).await {
+            Ok(Some(game)) => self.balance_into_team(game, user).await,
+            Ok(None) => {
+                // Either the lobby is closed or full, or the player joined concurrently
+                let game = self.get_game(id.to_string()).await?;
+                let nickname = nickname_of(&user);
+                if game.users.contains(&user) {
+                    Ok(game)
+                } else if game
+                    .users
+                    .iter()
+                    .any(|player| nickname_of(player) == nickname)
+                {
+                    Err(GameServiceError {
+                        message: format!(
+                            "Nickname {} is already taken in game with id {}",
+                            nickname, id
+                        ),
+                        kind: GameServiceErrorKind::Conflict,
+                    })
+                } else {
+                    Err(Self::process_invalid_request_error(format!(
+                        "User {} cannot join game with id {}",
+                        user, id
+                    )))
+                }
+            }
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    /// Puts a new player of a team game into the team with the fewest players.
+    async fn balance_into_team(&self, game: Game, user: String) -> Result<Game, GameServiceError> {
+        if game.mode != GameMode::Team || game.team_of(&user).is_some() {
+            return Ok(game);
+        }
+        let team = match smallest_team(&game.teams) {
+            Some(team) => team.name.clone(),
+            None => return Ok(game),
+        };
+        let id = game.id.unwrap();
+        match self.game_repo.set_user_team(id, user, team).await {
+            Ok(Some(game)) => Ok(game),
+            // The player left or the game started meanwhile
+            Ok(None) => Ok(game),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    async fn remove_from_team(&self, game: &mut Game, user: &str) -> Result<(), GameServiceError> {
+        if game.mode != GameMode::Team {
+            return Ok(());
+        }
+        self.game_repo
+            .remove_team_user(game.id.unwrap(), user.to_string())
+            .await
+            .map_err(Self::process_internal_error)?;
+        for team in &mut game.teams {
+            team.users.retain(|player| player != user);
+        }
+        Ok(())
+    }
+
+    /// Moves a player of a team game to the team of their choice, out of the team they were balanced into.
+    /// Returns an error if the game is not a team game or is not in the lobby.
+    /// Returns an error if the player is not part of the game or if the team does not exist.
+    pub async fn choose_team(
+        &self,
+        id: String,
+        user: String,
+        team: String,
+    ) -> Result<Game, GameServiceError> {
+        debug!("choose_team service started");
+        let game = self.get_game(id.clone()).await?;
+        let invalid_error = Self::process_invalid_request_error(format!(
+            "User {} cannot join team {} in game with id {}",
+            user, team, id
+        ));
+        if game.mode != GameMode::Team {
+            return Err(invalid_error);
+        }
+        let result = match self
+            .game_repo
+            .set_user_team(game.id.unwrap(), user, team)
+            .await
+        {
+            Ok(Some(game)) => Ok(game),
+            Ok(None) => Err(invalid_error),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("choose_team service ending");
+        result
+    }
+
+    /// Removes a player from a [Game] at their own request.
+    /// When the host leaves, the host role goes to the next player, the game is aborted if nobody is left.
+    /// Returns an error if the player is not part of the game or if the game is over.
+    pub async fn leave_game(&self, id: String, user: String) -> Result<Game, GameServiceError> {
+        debug!("leave_game service started");
+        let mut game = self.get_game(id.clone()).await?;
+        if game.status.is_over() || !game.users.contains(&user) {
+            return Err(Self::process_invalid_request_error(format!(
+                "User {} cannot leave game with id {}",
+                user, id
+            )));
+        }
+        game.users.retain(|player| player != &user);
+        game.ready_users.retain(|player| player != &user);
+        let next_creator = match game.is_hosted_by(&user) {
+            true => game.users.first().cloned(),
+            false => None,
+        };
+        if let Err(err) = self
+            .game_repo
+            .remove_user(game.id.unwrap(), user.clone(), next_creator.clone())
+            .await
+        {
+            return Err(Self::process_internal_error(err));
+        }
+        self.remove_from_team(&mut game, &user).await?;
+        let result = match next_creator {
+            Some(next_creator) => {
+                game.creator = Some(next_creator);
+                Ok(game)
+            }
+            None if game.is_hosted_by(&user) => self.transition_game(id, GameStatus::Aborted).await,
+            None => Ok(game),
+        };
+        debug!("leave_game service ending");
+        result
+    }
+
+    /// Marks a player of a [Game] lobby as ready, or not ready anymore if they already were.
+    /// Returns an error if the player is not part of the game or if the game is not in the lobby.
+    pub async fn toggle_ready(&self, id: String, user: String) -> Result<Game, GameServiceError> {
+        debug!("toggle_ready service started");
+        let mut game = self.get_game(id.clone()).await?;
+        if game.status != GameStatus::Lobby || !game.users.contains(&user) {
+            return Err(Self::process_invalid_request_error(format!(
+                "User {} cannot get ready in game with id {}",
+                user, id
+            )));
+        }
+        let ready = !game.ready_users.contains(&user);
+        let update = self
+            .game_repo
+            .set_user_ready(game.id.unwrap(), user.clone(), ready)
+            .await;
+        let result = match update {
+            Ok(_) => {
+                match ready {
+                    true => game.ready_users.push(user),
+                    false => game.ready_users.retain(|player| player != &user),
+                }
+                Ok(game)
+            }
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("toggle_ready service ending");
+        result
     }
 
     /// Saves the game_progress of a particular game
@@ -172,6 +1464,95 @@ impl GameService {
         }
     }
 
+    fn process_invalid_transition_error(
+        id: String,
+        from: GameStatus,
+        next: GameStatus,
+    ) -> GameServiceError {
+        GameServiceError {
+            message: format!(
+                "Game with id {} cannot go from {} to {}",
+                id,
+                from.as_str(),
+                next.as_str()
+            ),
+            kind: GameServiceErrorKind::InvalidTransition,
+        }
+    }
+
+    /// Checks that a game has topics and that each of them has questions, built-in or written by question authors.
+    async fn check_topics(&self, topics: &[String]) -> Result<(), GameServiceError> {
+        if topics.is_empty() {
+            return Err(Self::process_invalid_request_error(
+                "A game needs at least one topic".to_string(),
+            ));
+        }
+        let authored_topics = self
+            .question_repo
+            .get_question_topics(topics.to_vec())
+            .await
+            .map_err(Self::process_internal_error)?;
+        let missing: Vec<&str> = topics
+            .iter()
+            .map(String::as_str)
+            .filter(|topic| {
+                !BUILT_IN_TOPICS.contains(topic) && !authored_topics.contains(&Bson::from(*topic))
+            })
+            .collect();
+        if !missing.is_empty() {
+            return Err(Self::process_invalid_request_error(format!(
+                "There is no question on {}",
+                missing.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    fn process_invalid_request_error(message: String) -> GameServiceError {
+        GameServiceError {
+            message,
+            kind: GameServiceErrorKind::InvalidRequest,
+        }
+    }
+
+    fn process_not_running_error(id: String) -> GameServiceError {
+        GameServiceError {
+            message: format!("Game with id {} is not running", id),
+            kind: GameServiceErrorKind::Conflict,
+        }
+    }
+
+    fn process_power_up_conflict_error(
+        user: &str,
+        question_index: i8,
+        id: &str,
+    ) -> GameServiceError {
+        GameServiceError {
+            message: format!(
+                "User {} cannot use a power-up on question {} of game with id {} anymore",
+                user, question_index, id
+            ),
+            kind: GameServiceErrorKind::Conflict,
+        }
+    }
+
+    fn process_hint_conflict_error(user: &str, question_index: i8, id: &str) -> GameServiceError {
+        GameServiceError {
+            message: format!(
+                "User {} cannot ask for a hint on question {} of game with id {} anymore",
+                user, question_index, id
+            ),
+            kind: GameServiceErrorKind::Conflict,
+        }
+    }
+
+    fn process_hashing_error(message: String) -> GameServiceError {
+        GameServiceError {
+            message,
+            kind: GameServiceErrorKind::Internal,
+        }
+    }
+
     fn process_internal_error(err: Error) -> GameServiceError {
         GameServiceError {
             message: err.to_string(),
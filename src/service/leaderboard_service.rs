@@ -0,0 +1,265 @@
+extern crate dotenv;
+use crate::errors::leaderboard_service_error::{
+    LeaderboardServiceError, LeaderboardServiceErrorKind,
+};
+use crate::model::game::Game;
+use crate::model::game_answer::GameAnswer;
+use crate::model::leaderboard::{
+    rank_entries, Leaderboard, LeaderboardEntry, RankedEntry, Seasons, ALL_TIME, ALL_TOPICS,
+};
+use crate::repository::game_answer_repository::GameAnswerRepo;
+use crate::repository::game_repository::GameRepo;
+use crate::repository::leaderboard_repository::LeaderboardRepo;
+use crate::service::rating_service::MAX_PAGE_SIZE;
+use dotenv::dotenv;
+use log::{debug, error};
+use mongodb::bson::DateTime;
+use mongodb::error::Error;
+use rocket::futures::TryStreamExt;
+use std::collections::BTreeMap;
+use std::env;
+
+/// Comma separated start dates of the leaderboard seasons, formatted as `YYYY-MM-DD`.
+/// Seasons are calendar months when it is not set.
+pub const LEADERBOARD_SEASONS: &str = "LEADERBOARD_SEASONS";
+/// Season asked for to get the leaderboards of the season in progress.
+pub const CURRENT_SEASON: &str = "current";
+
+/// Service for [LeaderboardEntry] object to interact with the data layer
+pub struct LeaderboardService {
+    leaderboard_repo: LeaderboardRepo,
+    game_repo: GameRepo,
+    game_answer_repo: GameAnswerRepo,
+    seasons: Seasons,
+}
+
+impl LeaderboardService {
+    /// Creates a new instance of [LeaderboardService] with the repositories to interact with the data layer
+    /// and the seasons configured in the environment.
+    pub async fn init() -> Self {
+        dotenv().ok();
+        let seasons = Seasons::parse(&env::var(LEADERBOARD_SEASONS).unwrap_or_default())
+            .unwrap_or_else(|err| {
+                error!(
+                    "Invalid {}, using monthly seasons: {}",
+                    LEADERBOARD_SEASONS, err
+                );
+                Seasons::Monthly
+            });
+        let leaderboard_repo = LeaderboardRepo::init().await;
+        let game_repo = GameRepo::init().await;
+        let game_answer_repo = GameAnswerRepo::init().await;
+        LeaderboardService {
+            leaderboard_repo,
+            game_repo,
+            game_answer_repo,
+            seasons,
+        }
+    }
+
+    /// Creates the indexes the service relies on.
+    pub async fn create_indexes(&self) -> Result<(), LeaderboardServiceError> {
+        debug!("create_indexes service started");
+        let result = match self.leaderboard_repo.create_indexes().await {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("create_indexes service ending");
+        result
+    }
+
+    /// Records the games finished before the leaderboards existed.
+    /// Does nothing once a game was recorded, returns the number of recorded games.
+    pub async fn backfill(&self) -> Result<u64, LeaderboardServiceError> {
+        debug!("backfill service started");
+        let is_empty = self
+            .leaderboard_repo
+            .is_empty()
+            .await
+            .map_err(Self::process_internal_error)?;
+        let mut recorded = 0;
+        if is_empty {
+            let mut games = self
+                .game_repo
+                .get_finished_games()
+                .await
+                .map_err(Self::process_internal_error)?;
+            while let Some(game) = games
+                .try_next()
+                .await
+                .map_err(Self::process_internal_error)?
+            {
+                self.record_game(&game).await?;
+                recorded += 1;
+            }
+        }
+        debug!("backfill service ending");
+        Ok(recorded)
+    }
+
+    /// Adds the results of the players of a finished [Game] to the all-time leaderboards
+    /// and to the leaderboards of the season the game finished in, overall and for each topic of its questions.
+    pub async fn record_game(&self, game: &Game) -> Result<(), LeaderboardServiceError> {
+        debug!("record_game service started");
+        let answers: Vec<GameAnswer> = self
+            .game_answer_repo
+            .get_game_answers(game.id.unwrap().to_string())
+            .await
+            .map_err(Self::process_internal_error)?
+            .try_collect()
+            .await
+            .map_err(Self::process_internal_error)?;
+        // Score and number of answers of each player on each topic
+        let mut topic_results: BTreeMap<(String, String), (i32, i32)> = BTreeMap::new();
+        for answer in answers {
+            let results = topic_results
+                .entry((answer.user, answer.question.topic))
+                .or_default();
+            results.1 += 1;
+            if answer.answer == answer.correct_answer {
+                results.0 += 1;
+            }
+        }
+        let finished_at = game.status_changed_at.unwrap_or_else(DateTime::now);
+        let mut seasons = vec![ALL_TIME.to_string()];
+        seasons.extend(self.seasons.season_of(finished_at));
+        for standing in &game.standings {
+            let results = |topic: &str, score: i32, answers: i32| LeaderboardEntry {
+                id: None,
+                season: String::new(),
+                topic: topic.to_string(),
+                user: standing.user.clone(),
+                score,
+                answers,
+                games: 1,
+                wins: (standing.rank == 1) as i32,
+                updated_at: DateTime::now(),
+            };
+            let player_results: Vec<(&String, &(i32, i32))> = topic_results
+                .iter()
+                .filter(|((user, _), _)| *user == standing.user)
+                .map(|((_, topic), results)| (topic, results))
+                .collect();
+            let answers = player_results.iter().map(|(_, (_, answers))| answers).sum();
+            let mut entries = vec![results(ALL_TOPICS, standing.score, answers)];
+            entries.extend(
+                player_results
+                    .iter()
+                    .map(|(topic, (score, answers))| results(topic, *score, *answers)),
+            );
+            for season in &seasons {
+                for entry in &entries {
+                    let entry = LeaderboardEntry {
+                        season: season.clone(),
+                        ..entry.clone()
+                    };
+                    self.leaderboard_repo
+                        .add_results(entry)
+                        .await
+                        .map_err(Self::process_internal_error)?;
+                }
+            }
+        }
+        debug!("record_game service ending");
+        Ok(())
+    }
+
+    /// Gets a page of a leaderboard, the best scores first, and the entry of the caller when they have one.
+    /// Leaderboards are all-time and for every topic unless a season or a topic is asked for,
+    /// [CURRENT_SEASON] giving the season in progress. Pages start at 1.
+    /// Returns an error if the page or its size are not valid.
+    /// Returns an error if no season is in progress.
+    pub async fn get_leaderboard(
+        &self,
+        topic: Option<String>,
+        season: Option<String>,
+        page: u64,
+        size: i64,
+        caller: Option<String>,
+    ) -> Result<Leaderboard, LeaderboardServiceError> {
+        debug!("get_leaderboard service started");
+        if page == 0 || !(1..=MAX_PAGE_SIZE).contains(&size) {
+            return Err(LeaderboardServiceError {
+                message: format!(
+                    "Pages start at 1 and hold between 1 and {} entries",
+                    MAX_PAGE_SIZE
+                ),
+                kind: LeaderboardServiceErrorKind::InvalidRequest,
+            });
+        }
+        let season = match season.as_deref() {
+            None => ALL_TIME.to_string(),
+            Some(CURRENT_SEASON) => {
+                self.seasons
+                    .season_of(DateTime::now())
+                    .ok_or_else(|| LeaderboardServiceError {
+                        message: "No season is in progress".to_string(),
+                        kind: LeaderboardServiceErrorKind::NotFound,
+                    })?
+            }
+            Some(season) => season.to_string(),
+        };
+        let topic = topic.unwrap_or_else(|| ALL_TOPICS.to_string());
+        let skip = (page - 1) * size as u64;
+        let entries: Vec<LeaderboardEntry> = self
+            .leaderboard_repo
+            .get_entries(season.clone(), topic.clone(), skip, size)
+            .await
+            .map_err(Self::process_internal_error)?
+            .try_collect()
+            .await
+            .map_err(Self::process_internal_error)?;
+        let first_rank = match entries.first() {
+            Some(entry) => self.get_rank(&season, &topic, entry.score).await?,
+            None => skip + 1,
+        };
+        let entries = rank_entries(first_rank, skip, entries);
+        let me = match caller {
+            Some(caller) => {
+                let entry = self
+                    .leaderboard_repo
+                    .get_entry(season.clone(), topic.clone(), caller)
+                    .await
+                    .map_err(Self::process_internal_error)?;
+                match entry {
+                    Some(entry) => Some(RankedEntry {
+                        rank: self.get_rank(&season, &topic, entry.score).await?,
+                        entry,
+                    }),
+                    None => None,
+                }
+            }
+            None => None,
+        };
+        debug!("get_leaderboard service ending");
+        Ok(Leaderboard {
+            season,
+            topic,
+            entries,
+            me,
+        })
+    }
+
+    async fn get_rank(
+        &self,
+        season: &str,
+        topic: &str,
+        score: i32,
+    ) -> Result<u64, LeaderboardServiceError> {
+        match self
+            .leaderboard_repo
+            .count_ahead(season.to_string(), topic.to_string(), score)
+            .await
+        {
+            Ok(ahead) => Ok(ahead + 1),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    fn process_internal_error(err: Error) -> LeaderboardServiceError {
+        LeaderboardServiceError {
+            message: err.to_string(),
+            kind: LeaderboardServiceErrorKind::Internal,
+        }
+    }
+}
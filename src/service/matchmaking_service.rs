@@ -0,0 +1,290 @@
+use crate::errors::game_service_error::GameServiceError;
+use crate::errors::matchmaking_service_error::{
+    MatchmakingServiceError, MatchmakingServiceErrorKind,
+};
+use crate::model::game::{Game, GameStatus, LobbySettings};
+use crate::model::matchmaking::{
+    find_groups, MatchGroup, MatchmakingTicket, TicketStatus, MATCHMAKING_TIMEOUT_SECONDS,
+};
+use crate::model::rating::{PlayerRating, INITIAL_RATING};
+use crate::repository::matchmaking_ticket_repository::MatchmakingTicketRepo;
+use crate::repository::player_rating_repository::PlayerRatingRepo;
+use crate::service::game_service::GameService;
+use log::{debug, error, info};
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use mongodb::error::{Error, ErrorKind, WriteFailure};
+use rocket::futures::TryStreamExt;
+use std::str::FromStr;
+
+/// Number of questions of the games created by the matchmaking.
+pub const MATCHED_GAME_QUESTIONS: i8 = 10;
+const DUPLICATE_KEY_CODE: i32 = 11000;
+
+/// Service for [MatchmakingTicket] object to interact with the data layer
+pub struct MatchmakingService {
+    ticket_repo: MatchmakingTicketRepo,
+    rating_repo: PlayerRatingRepo,
+    game_service: GameService,
+}
+
+impl MatchmakingService {
+    /// Creates a new instance of [MatchmakingService] with the repositories to interact with the data layer
+    pub async fn init() -> Self {
+        let ticket_repo = MatchmakingTicketRepo::init().await;
+        let rating_repo = PlayerRatingRepo::init().await;
+        let game_service = GameService::init().await;
+        MatchmakingService {
+            ticket_repo,
+            rating_repo,
+            game_service,
+        }
+    }
+
+    /// Creates the indexes the service relies on.
+    pub async fn create_indexes(&self) -> Result<(), MatchmakingServiceError> {
+        debug!("create_indexes service started");
+        let result = match self.ticket_repo.create_indexes().await {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("create_indexes service ending");
+        result
+    }
+
+    /// Puts a player in the matchmaking queue for games on one of the given topics.
+    /// The player is matched according to their average rating on these topics.
+    /// Returns an error if no topic is given or if the player is already waiting in the queue.
+    pub async fn enqueue(
+        &self,
+        user: String,
+        topics: Vec<String>,
+    ) -> Result<MatchmakingTicket, MatchmakingServiceError> {
+        debug!("enqueue service started");
+        let mut topics: Vec<String> = topics
+            .into_iter()
+            .map(|topic| topic.trim().to_string())
+            .filter(|topic| !topic.is_empty())
+            .collect();
+        topics.dedup();
+        if topics.is_empty() {
+            return Err(MatchmakingServiceError {
+                message: "At least one topic is needed to be matched".to_string(),
+                kind: MatchmakingServiceErrorKind::InvalidRequest,
+            });
+        }
+        let ratings: Vec<PlayerRating> = self
+            .rating_repo
+            .get_user_ratings(user.clone())
+            .await
+            .map_err(Self::process_internal_error)?
+            .try_collect()
+            .await
+            .map_err(Self::process_internal_error)?;
+        let rating = topics
+            .iter()
+            .map(|topic| {
+                ratings
+                    .iter()
+                    .find(|rating| rating.topic == *topic)
+                    .map_or(INITIAL_RATING, |rating| rating.rating)
+            })
+            .sum::<f64>()
+            / topics.len() as f64;
+        let now = DateTime::now();
+        let mut ticket = MatchmakingTicket {
+            id: None,
+            user: user.clone(),
+            topics,
+            rating,
+            status: TicketStatus::Waiting,
+            game_id: None,
+            created_at: now,
+            status_changed_at: now,
+        };
+        let result = match self.ticket_repo.create_ticket(ticket.clone()).await {
+            Ok(insert) => {
+                ticket.id = insert.inserted_id.as_object_id();
+                Ok(ticket)
+            }
+            Err(err) if Self::is_duplicate_key_error(&err) => Err(MatchmakingServiceError {
+                message: format!("Player {} is already waiting in the queue", user),
+                kind: MatchmakingServiceErrorKind::Conflict,
+            }),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("enqueue service ending");
+        result
+    }
+
+    /// Gets a [MatchmakingTicket] by id.
+    /// Returns an error if the ticket does not exist.
+    pub async fn get_ticket(
+        &self,
+        id: String,
+    ) -> Result<MatchmakingTicket, MatchmakingServiceError> {
+        debug!("get_ticket service started");
+        let object_id = Self::to_object_id(&id)?;
+        let result = match self.ticket_repo.get_ticket(object_id).await {
+            Ok(Some(ticket)) => Ok(ticket),
+            Ok(None) => Err(Self::process_not_found_error(id)),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("get_ticket service ending");
+        result
+    }
+
+    /// Takes a player out of the matchmaking queue.
+    /// Returns an error if the player has no such ticket waiting in the queue.
+    pub async fn cancel(
+        &self,
+        id: String,
+        user: String,
+    ) -> Result<MatchmakingTicket, MatchmakingServiceError> {
+        debug!("cancel service started");
+        let object_id = Self::to_object_id(&id)?;
+        let result = match self.ticket_repo.cancel_ticket(object_id, user).await {
+            Ok(Some(ticket)) => Ok(ticket),
+            Ok(None) => Err(Self::process_not_found_error(id)),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("cancel service ending");
+        result
+    }
+
+    /// Expires the tickets waiting for too long, groups the waiting players and creates a game for each group.
+    /// The games are created private in the name of the longest waiting player of the group, the others joining them
+    /// by their code, and are moved to their countdown. Returns the created games, they still have to be run.
+    pub async fn match_players(&self) -> Result<Vec<Game>, MatchmakingServiceError> {
+        debug!("match_players service started");
+        let now = DateTime::now();
+        let timeout =
+            DateTime::from_millis(now.timestamp_millis() - MATCHMAKING_TIMEOUT_SECONDS * 1000);
+        self.ticket_repo
+            .expire_tickets(timeout)
+            .await
+            .map_err(Self::process_internal_error)?;
+        let tickets: Vec<MatchmakingTicket> = self
+            .ticket_repo
+            .get_waiting_tickets()
+            .await
+            .map_err(Self::process_internal_error)?
+            .try_collect()
+            .await
+            .map_err(Self::process_internal_error)?;
+        let mut games = vec![];
+        for group in find_groups(&tickets, now) {
+            let claimed = self
+                .ticket_repo
+                .claim_tickets(group.tickets.clone())
+                .await
+                .map_err(Self::process_internal_error)?;
+            // Some players left the queue meanwhile, the others wait for another group
+            if claimed.modified_count as usize != group.tickets.len() {
+                self.release(group.tickets).await?;
+                continue;
+            }
+            match self.create_match_game(&group).await {
+                Ok(game) => {
+                    info!("{} players matched", group.users.len());
+                    self.ticket_repo
+                        .set_game(group.tickets, game.id.unwrap().to_string())
+                        .await
+                        .map_err(Self::process_internal_error)?;
+                    games.push(game);
+                }
+                Err(err) => {
+                    error!("Failed to create a matched game: {}", err.message);
+                    self.release(group.tickets).await?;
+                }
+            }
+        }
+        debug!("match_players service ending");
+        Ok(games)
+    }
+
+    async fn create_match_game(&self, group: &MatchGroup) -> Result<Game, MatchmakingServiceError> {
+        let players = group.users.len() as i8;
+        let game = Game {
+            topics: group.topics.clone(),
+            question_number: MATCHED_GAME_QUESTIONS,
+            creator: group.users.first().cloned(),
+            // Never listed as an open lobby, the other players of the group join it by its code
+            is_private: true,
+            lobby_settings: LobbySettings {
+                max_players: Some(players),
+                min_players: players,
+                ready_check: false,
+            },
+            ..Default::default()
+        };
+        let to_error = |err: GameServiceError| MatchmakingServiceError {
+            message: err.message,
+            kind: MatchmakingServiceErrorKind::Internal,
+        };
+        let game = self
+            .game_service
+            .create_game(game, None)
+            .await
+            .map_err(to_error)?;
+        let id = game.id.unwrap().to_string();
+        let code = game.join_code.clone().unwrap_or_default();
+        let mut started = Ok(game);
+        for user in group.users.iter().skip(1) {
+            started = self
+                .game_service
+                .join_game(code.clone(), user.clone(), None)
+                .await;
+            if started.is_err() {
+                break;
+            }
+        }
+        if started.is_ok() {
+            started = self
+                .game_service
+                .transition_game(id.clone(), GameStatus::Countdown)
+                .await;
+        }
+        if started.is_err() {
+            // Nobody would play the lobby left behind
+            let _ = self
+                .game_service
+                .transition_game(id, GameStatus::Aborted)
+                .await;
+        }
+        started.map_err(to_error)
+    }
+
+    async fn release(&self, tickets: Vec<ObjectId>) -> Result<(), MatchmakingServiceError> {
+        match self.ticket_repo.release_tickets(tickets).await {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    fn to_object_id(id: &str) -> Result<ObjectId, MatchmakingServiceError> {
+        ObjectId::from_str(id).map_err(|_| Self::process_not_found_error(id.to_string()))
+    }
+
+    fn is_duplicate_key_error(err: &Error) -> bool {
+        matches!(
+            err.kind.as_ref(),
+            ErrorKind::Write(WriteFailure::WriteError(write_error))
+                if write_error.code == DUPLICATE_KEY_CODE
+        )
+    }
+
+    fn process_not_found_error(id: String) -> MatchmakingServiceError {
+        MatchmakingServiceError {
+            message: format!("Matchmaking ticket with id {} does not exist", id),
+            kind: MatchmakingServiceErrorKind::NotFound,
+        }
+    }
+
+    fn process_internal_error(err: Error) -> MatchmakingServiceError {
+        MatchmakingServiceError {
+            message: err.to_string(),
+            kind: MatchmakingServiceErrorKind::Internal,
+        }
+    }
+}
@@ -1 +1,11 @@
+pub mod api_key_service;
+pub mod daily_challenge_service;
 pub mod game_service;
+pub mod leaderboard_service;
+pub mod matchmaking_service;
+pub mod policy;
+pub mod practice_service;
+pub mod profile_service;
+pub mod question_service;
+pub mod rating_service;
+pub mod user_service;
@@ -0,0 +1,126 @@
+use crate::model::api_key::ApiKeyScope;
+use crate::model::game::Game;
+use crate::model::question::AuthoredQuestion;
+use crate::model::user::UserRole;
+use crate::security::guard::AuthenticatedUser;
+
+///Operations needing a privilege, granted by the [UserRole]s of the caller
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    PlayGames,
+    CreateGames,
+    HostGames,
+    WriteQuestions,
+    ModerateGames,
+    ModerateQuestions,
+    ManageUsers,
+}
+
+/// Tells if a role grants a permission, admins are granted every permission.
+pub fn grants(role: UserRole, permission: Permission) -> bool {
+    use Permission::*;
+    match role {
+        UserRole::Player => permission == PlayGames,
+        UserRole::Host => matches!(permission, CreateGames | HostGames),
+        UserRole::QuestionAuthor => permission == WriteQuestions,
+        UserRole::Moderator => matches!(permission, ModerateGames | ModerateQuestions),
+        UserRole::Admin => true,
+    }
+}
+
+/// Tells if an API key scope covers a permission.
+/// Playing and managing users are never covered, they need the user to log in.
+pub fn covers(scope: ApiKeyScope, permission: Permission) -> bool {
+    use Permission::*;
+    match scope {
+        ApiKeyScope::GamesCreate => permission == CreateGames,
+        ApiKeyScope::GamesControl => matches!(permission, HostGames | ModerateGames),
+        ApiKeyScope::QuestionsWrite => matches!(permission, WriteQuestions | ModerateQuestions),
+    }
+}
+
+/// Tells if one of the roles grants the permission.
+pub fn roles_grant(roles: &[UserRole], permission: Permission) -> bool {
+    roles.iter().any(|role| grants(*role, permission))
+}
+
+/// Tells if the user is granted the permission by their roles,
+/// and by the scopes of the API key when they authenticated with one.
+pub fn is_allowed(user: &AuthenticatedUser, permission: Permission) -> bool {
+    roles_grant(&user.roles, permission)
+        && user
+            .scopes
+            .as_ref()
+            .is_none_or(|scopes| scopes.iter().any(|scope| covers(*scope, permission)))
+}
+
+/// Tells if the user may control a [Game]: its host while allowed to host games, or a moderator.
+pub fn can_control_game(user: &AuthenticatedUser, game: &Game) -> bool {
+    (game.is_hosted_by(&user.username) && is_allowed(user, Permission::HostGames))
+        || is_allowed(user, Permission::ModerateGames)
+}
+
+/// Tells if the user may delete an [AuthoredQuestion]: its author while allowed to write questions, or a moderator.
+pub fn can_delete_question(user: &AuthenticatedUser, question: &AuthoredQuestion) -> bool {
+    (question.author == user.username && is_allowed(user, Permission::WriteQuestions))
+        || is_allowed(user, Permission::ModerateQuestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::api_key::ApiKeyScope;
+    use crate::model::game::Game;
+    use crate::model::user::{default_roles, UserRole};
+    use crate::security::guard::AuthenticatedUser;
+    use crate::service::policy::{can_control_game, is_allowed, roles_grant, Permission};
+
+    fn user(username: &str, roles: Vec<UserRole>) -> AuthenticatedUser {
+        AuthenticatedUser {
+            username: username.to_string(),
+            guest: false,
+            roles,
+            scopes: None,
+        }
+    }
+
+    #[test]
+    fn roles_grant_should_grant_every_permission_to_admins() {
+        assert!(roles_grant(&[UserRole::Admin], Permission::ManageUsers));
+        assert!(roles_grant(&[UserRole::Admin], Permission::WriteQuestions));
+        assert!(!roles_grant(&default_roles(), Permission::ManageUsers));
+        assert!(!roles_grant(
+            &[UserRole::Moderator],
+            Permission::WriteQuestions
+        ));
+    }
+
+    #[test]
+    fn can_control_game_should_allow_the_host_and_moderators_only() {
+        let game = Game {
+            creator: Some("bob".to_string()),
+            ..Default::default()
+        };
+        assert!(can_control_game(&user("bob", default_roles()), &game));
+        assert!(!can_control_game(
+            &user("bob", vec![UserRole::Player]),
+            &game
+        ));
+        assert!(!can_control_game(&user("alice", default_roles()), &game));
+        assert!(can_control_game(
+            &user("alice", vec![UserRole::Moderator]),
+            &game
+        ));
+    }
+
+    #[test]
+    fn is_allowed_should_limit_api_keys_to_their_scopes() {
+        let mut admin = user("carol", vec![UserRole::Admin]);
+        admin.scopes = Some(vec![ApiKeyScope::GamesCreate]);
+        assert!(is_allowed(&admin, Permission::CreateGames));
+        assert!(!is_allowed(&admin, Permission::HostGames));
+        assert!(!is_allowed(&admin, Permission::ManageUsers));
+        let mut player = user("bob", vec![UserRole::Player]);
+        player.scopes = Some(vec![ApiKeyScope::QuestionsWrite]);
+        assert!(!is_allowed(&player, Permission::WriteQuestions));
+    }
+}
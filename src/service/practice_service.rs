@@ -0,0 +1,233 @@
+use crate::errors::practice_service_error::{PracticeServiceError, PracticeServiceErrorKind};
+use crate::model::game_answer::GameAnswer;
+use crate::model::game_progress::Question;
+use crate::model::practice::{
+    summarize, PracticeFeedback, PracticeSession, PracticeSummary, MAX_PRACTICE_QUESTIONS,
+};
+use crate::repository::game_answer_repository::GameAnswerRepo;
+use crate::repository::practice_session_repository::PracticeSessionRepo;
+use crate::resource::game_resource::QUESTION_SECONDS;
+use log::debug;
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime;
+use mongodb::error::Error;
+use rand::seq::SliceRandom;
+use rocket::futures::TryStreamExt;
+use std::str::FromStr;
+
+/// Service for [PracticeSession] object to interact with the data layer
+pub struct PracticeService {
+    session_repo: PracticeSessionRepo,
+    game_answer_repo: GameAnswerRepo,
+}
+
+impl PracticeService {
+    /// Creates a new instance of [PracticeService] with the repositories to interact with the data layer
+    pub async fn init() -> Self {
+        let session_repo = PracticeSessionRepo::init().await;
+        let game_answer_repo = GameAnswerRepo::init().await;
+        PracticeService {
+            session_repo,
+            game_answer_repo,
+        }
+    }
+
+    /// Starts a [PracticeSession] for a player, with questions picked at random from the given pool.
+    /// Questions are not repeated unless the pool is too small for the session.
+    /// Returns an error if no topic is given, if the number of questions is not valid or if the pool is empty.
+    pub async fn start(
+        &self,
+        user: String,
+        topics: Vec<String>,
+        timed: bool,
+        question_number: i8,
+        pool: Vec<Question>,
+    ) -> Result<PracticeSession, PracticeServiceError> {
+        debug!("start service started");
+        if topics.is_empty() {
+            return Err(Self::process_invalid_request_error(
+                "At least one topic is needed to practice".to_string(),
+            ));
+        }
+        if !(1..=MAX_PRACTICE_QUESTIONS).contains(&question_number) {
+            return Err(Self::process_invalid_request_error(format!(
+                "A practice session has between 1 and {} questions",
+                MAX_PRACTICE_QUESTIONS
+            )));
+        }
+        if pool.is_empty() {
+            return Err(Self::process_invalid_request_error(format!(
+                "There is no question on {}",
+                topics.join(", ")
+            )));
+        }
+        let mut questions = vec![];
+        while questions.len() < question_number as usize {
+            let mut round = pool.clone();
+            round.shuffle(&mut rand::thread_rng());
+            questions.extend(round);
+        }
+        questions.truncate(question_number as usize);
+        for question in &mut questions {
+            question.remaining_time = QUESTION_SECONDS;
+        }
+        let now = DateTime::now();
+        let mut session = PracticeSession {
+            id: None,
+            user,
+            topics,
+            timed,
+            questions,
+            current_question: 0,
+            question_started_at: now,
+            started_at: now,
+            finished_at: None,
+        };
+        let result = match self.session_repo.create_session(session.clone()).await {
+            Ok(insert) => {
+                session.id = insert.inserted_id.as_object_id();
+                Ok(session)
+            }
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("start service ending");
+        result
+    }
+
+    /// Gets a [PracticeSession] of a player.
+    /// Returns an error if the session does not exist or belongs to another player.
+    pub async fn get_session(
+        &self,
+        id: String,
+        user: &str,
+    ) -> Result<PracticeSession, PracticeServiceError> {
+        debug!("get_session service started");
+        let object_id =
+            ObjectId::from_str(&id).map_err(|_| Self::process_not_found_error(id.clone()))?;
+        let result = match self.session_repo.get_session(object_id).await {
+            Ok(Some(session)) if session.user == user => Ok(session),
+            Ok(Some(_)) => Err(PracticeServiceError {
+                message: format!("Practice session with id {} belongs to another player", id),
+                kind: PracticeServiceErrorKind::Forbidden,
+            }),
+            Ok(None) => Err(Self::process_not_found_error(id)),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("get_session service ending");
+        result
+    }
+
+    /// Answers the current question of a [PracticeSession] and moves the session to the next question.
+    /// In timed sessions, an answer given after the time of the question ran out counts as no answer.
+    /// Returns whether the answer is correct and the correct answer.
+    /// Returns an error if the answer is not between 1 and 4 or if the session is finished.
+    /// Returns an error if the question was answered meanwhile.
+    pub async fn answer(
+        &self,
+        id: String,
+        user: &str,
+        answer: i8,
+    ) -> Result<PracticeFeedback, PracticeServiceError> {
+        debug!("answer service started");
+        if !(1..=4).contains(&answer) {
+            return Err(Self::process_invalid_request_error(
+                "Answers are numbered from 1 to 4".to_string(),
+            ));
+        }
+        let session = self.get_session(id.clone(), user).await?;
+        let mut question = match session.current() {
+            Some(question) => question.clone(),
+            None => return Err(Self::process_conflict_error(id)),
+        };
+        let now = DateTime::now();
+        let remaining_time = session.remaining_time(now, QUESTION_SECONDS);
+        let timed_out = remaining_time == Some(0);
+        let answer = if timed_out { 0 } else { answer };
+        question.remaining_time = remaining_time.unwrap_or(0);
+        let finished = session.current_question as usize + 1 >= session.questions.len();
+        let updated = self
+            .session_repo
+            .next_question(session.id.unwrap(), session.current_question, finished, now)
+            .await
+            .map_err(Self::process_internal_error)?
+            .ok_or_else(|| Self::process_conflict_error(id.clone()))?;
+        let correct_answer = question.good_answer_number;
+        let game_answer = GameAnswer {
+            game_id: id,
+            user: user.to_string(),
+            answer,
+            question_index: session.current_question,
+            correct_answer,
+            question,
+            practice: true,
+            daily: false,
+            wager: None,
+            power_up: None,
+            answer_seconds: remaining_time.map(|remaining_time| QUESTION_SECONDS - remaining_time),
+        };
+        if let Err(err) = self.game_answer_repo.save_game_answer(game_answer).await {
+            return Err(Self::process_internal_error(err));
+        }
+        debug!("answer service ending");
+        Ok(PracticeFeedback {
+            answer,
+            correct_answer,
+            correct: answer == correct_answer,
+            timed_out,
+            session: updated,
+        })
+    }
+
+    /// Gets the summary of a [PracticeSession] from the answers given so far.
+    /// Returns an error if the session does not exist or belongs to another player.
+    pub async fn get_summary(
+        &self,
+        id: String,
+        user: &str,
+    ) -> Result<(PracticeSession, PracticeSummary), PracticeServiceError> {
+        debug!("get_summary service started");
+        let session = self.get_session(id.clone(), user).await?;
+        let answers: Vec<GameAnswer> = self
+            .game_answer_repo
+            .get_game_answers(id)
+            .await
+            .map_err(Self::process_internal_error)?
+            .try_collect()
+            .await
+            .map_err(Self::process_internal_error)?;
+        let summary = summarize(&session, &answers, QUESTION_SECONDS);
+        debug!("get_summary service ending");
+        Ok((session, summary))
+    }
+
+    fn process_not_found_error(id: String) -> PracticeServiceError {
+        PracticeServiceError {
+            message: format!("Practice session with id {} does not exist", id),
+            kind: PracticeServiceErrorKind::NotFound,
+        }
+    }
+
+    fn process_conflict_error(id: String) -> PracticeServiceError {
+        PracticeServiceError {
+            message: format!(
+                "Practice session with id {} is finished or its question was already answered",
+                id
+            ),
+            kind: PracticeServiceErrorKind::Conflict,
+        }
+    }
+
+    fn process_invalid_request_error(message: String) -> PracticeServiceError {
+        PracticeServiceError {
+            message,
+            kind: PracticeServiceErrorKind::InvalidRequest,
+        }
+    }
+
+    fn process_internal_error(err: Error) -> PracticeServiceError {
+        PracticeServiceError {
+            message: err.to_string(),
+            kind: PracticeServiceErrorKind::Internal,
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::errors::profile_service_error::{ProfileServiceError, ProfileServiceErrorKind};
+use crate::model::profile::{
+    accuracy, best_streak, PlayerProfile, RecentGame, SurvivalBest, TopicRating, TopicStats,
+};
+use crate::repository::game_answer_repository::GameAnswerRepo;
+use crate::repository::game_repository::GameRepo;
+use crate::repository::player_profile_repository::PlayerProfileRepo;
+use crate::repository::player_rating_repository::PlayerRatingRepo;
+use crate::repository::user_repository::UserRepo;
+use crate::resource::game_resource::QUESTION_SECONDS;
+use log::{debug, error};
+use mongodb::bson::{from_bson, Bson, DateTime, Document};
+use mongodb::error::Error;
+use rocket::futures::TryStreamExt;
+
+/// Number of finished games listed in a profile.
+pub const RECENT_GAMES: i64 = 10;
+
+/// Service for [PlayerProfile] object to interact with the data layer
+pub struct ProfileService {
+    profile_repo: PlayerProfileRepo,
+    game_repo: GameRepo,
+    game_answer_repo: GameAnswerRepo,
+    user_repo: UserRepo,
+    rating_repo: PlayerRatingRepo,
+}
+
+impl ProfileService {
+    /// Creates a new instance of [ProfileService] with the repositories to interact with the data layer
+    pub async fn init() -> Self {
+        let profile_repo = PlayerProfileRepo::init().await;
+        let game_repo = GameRepo::init().await;
+        let game_answer_repo = GameAnswerRepo::init().await;
+        let user_repo = UserRepo::init().await;
+        let rating_repo = PlayerRatingRepo::init().await;
+        ProfileService {
+            profile_repo,
+            game_repo,
+            game_answer_repo,
+            user_repo,
+            rating_repo,
+        }
+    }
+
+    /// Gets the [PlayerProfile] of a player, it is computed the first time it is asked for.
+    /// Returns an error if the player never played and is not a registered user.
+    pub async fn get_profile(&self, user: String) -> Result<PlayerProfile, ProfileServiceError> {
+        debug!("get_profile service started");
+        let result = match self.profile_repo.get_profile(user.clone()).await {
+            Ok(Some(profile)) => Ok(profile),
+            Ok(None) => self.refresh_profile(user.clone()).await,
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        let result = match result {
+            Ok(profile) if profile.answers == 0 && profile.games_played == 0 => {
+                match self.user_repo.get_user_by_username(user.clone()).await {
+                    Ok(Some(_)) => Ok(profile),
+                    Ok(None) => Err(ProfileServiceError {
+                        message: format!("Player {} does not exist", user),
+                        kind: ProfileServiceErrorKind::NotFound,
+                    }),
+                    Err(err) => Err(Self::process_internal_error(err)),
+                }
+            }
+            result => result,
+        };
+        debug!("get_profile service ending");
+        result
+    }
+
+    /// Computes the [PlayerProfile]s of the players of a finished game again.
+    /// Failures are logged, they do not prevent the other profiles from being refreshed.
+    pub async fn refresh_profiles(&self, users: Vec<String>) {
+        debug!("refresh_profiles service started");
+        for user in users {
+            if let Err(err) = self.refresh_profile(user.clone()).await {
+                error!("Failed to refresh profile of {}: {}", user, err.message);
+            }
+        }
+        debug!("refresh_profiles service ending");
+    }
+
+    /// Computes the [PlayerProfile] of a player from their answers, finished games and ratings, and saves it.
+    pub async fn refresh_profile(
+        &self,
+        user: String,
+    ) -> Result<PlayerProfile, ProfileServiceError> {
+        debug!("refresh_profile service started");
+        let answer_stats = self
+            .game_answer_repo
+            .aggregate_player_stats(user.clone(), QUESTION_SECONDS as i64)
+            .await
+            .map_err(Self::process_internal_error)?
+            .unwrap_or_default();
+        let game_stats = self
+            .game_repo
+            .aggregate_player_games(user.clone(), RECENT_GAMES)
+            .await
+            .map_err(Self::process_internal_error)?
+            .unwrap_or_default();
+        let ratings = self
+            .rating_repo
+            .get_user_ratings(user.clone())
+            .await
+            .map_err(Self::process_internal_error)?
+            .try_collect::<Vec<_>>()
+            .await
+            .map_err(Self::process_internal_error)?;
+        let mut profile = Self::to_profile(user, &answer_stats, &game_stats);
+        profile.ratings = ratings
+            .into_iter()
+            .map(|rating| TopicRating {
+                topic: rating.topic,
+                rating: rating.rating,
+                games: rating.games,
+            })
+            .collect();
+        let result = match self.profile_repo.save_profile(profile.clone()).await {
+            Ok(_) => Ok(profile),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("refresh_profile service ending");
+        result
+    }
+
+    /// Builds a [PlayerProfile] from the results of the answer and game aggregations.
+    fn to_profile(user: String, answer_stats: &Document, game_stats: &Document) -> PlayerProfile {
+        let overall = Self::facet(answer_stats, "overall")
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        let answers = Self::number(&overall, "answers");
+        let correct_answers = Self::number(&overall, "correct_answers");
+        let outcomes: Vec<bool> = overall
+            .get_array("outcomes")
+            .map(|outcomes| outcomes.iter().filter_map(Bson::as_bool).collect())
+            .unwrap_or_default();
+        let topics = Self::facet(answer_stats, "topics")
+            .iter()
+            .map(|topic| {
+                let answers = Self::number(topic, "answers");
+                let correct_answers = Self::number(topic, "correct_answers");
+                TopicStats {
+                    topic: topic.get_str("_id").unwrap_or_default().to_string(),
+                    answers,
+                    correct_answers,
+                    accuracy: accuracy(correct_answers, answers),
+                }
+            })
+            .collect();
+        let count = |name: &str| {
+            Self::facet(game_stats, name)
+                .first()
+                .map_or(0, |count| Self::number(count, "count"))
+        };
+        let recent_games = Self::facet(game_stats, "recent")
+            .into_iter()
+            .filter_map(|game| from_bson::<RecentGame>(Bson::Document(game)).ok())
+            .collect();
+        let survival_bests = Self::facet(game_stats, "survival_bests")
+            .into_iter()
+            .filter_map(|best| from_bson::<SurvivalBest>(Bson::Document(best)).ok())
+            .collect();
+        PlayerProfile {
+            user,
+            games_played: count("played"),
+            wins: count("wins"),
+            answers,
+            correct_answers,
+            accuracy: accuracy(correct_answers, answers),
+            average_answer_seconds: overall.get_f64("average_answer_seconds").unwrap_or(0.0),
+            best_streak: best_streak(&outcomes),
+            topics,
+            recent_games,
+            ratings: vec![],
+            survival_bests,
+            updated_at: DateTime::now(),
+        }
+    }
+
+    fn facet(stats: &Document, name: &str) -> Vec<Document> {
+        stats
+            .get_array(name)
+            .map(|documents| {
+                documents
+                    .iter()
+                    .filter_map(|document| document.as_document().cloned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Reads a count of an aggregation result, Mongo gives 32 or 64 bits integers depending on their size.
+    fn number(document: &Document, name: &str) -> i32 {
+        match document.get(name) {
+            Some(Bson::Int32(value)) => *value,
+            Some(Bson::Int64(value)) => *value as i32,
+            _ => 0,
+        }
+    }
+
+    fn process_internal_error(err: Error) -> ProfileServiceError {
+        ProfileServiceError {
+            message: err.to_string(),
+            kind: ProfileServiceErrorKind::Internal,
+        }
+    }
+}
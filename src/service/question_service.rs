@@ -0,0 +1,141 @@
+use crate::errors::question_service_error::{QuestionServiceError, QuestionServiceErrorKind};
+use crate::model::hint::are_hints_valid;
+use crate::model::question::AuthoredQuestion;
+use crate::repository::question_repository::QuestionRepo;
+use crate::security::guard::AuthenticatedUser;
+use crate::service::policy::{can_delete_question, is_allowed, Permission};
+use log::debug;
+use mongodb::bson::oid::ObjectId;
+use mongodb::error::Error;
+use rocket::futures::TryStreamExt;
+use std::str::FromStr;
+
+/// Service for [AuthoredQuestion] object to interact with the data layer
+pub struct QuestionService {
+    question_repo: QuestionRepo,
+}
+
+impl QuestionService {
+    /// Creates a new instance of [QuestionService] with the repository to interact with the data layer
+    pub async fn init() -> Self {
+        let question_repo = QuestionRepo::init().await;
+        QuestionService { question_repo }
+    }
+
+    /// Creates a new [AuthoredQuestion].
+    /// Returns an error if the question has no text, no topic, or if the good answer is not one of the four answers.
+    /// Returns an error if the question has more than [MAX_HINTS](crate::model::hint::MAX_HINTS) hints or a blank one.
+    pub async fn create_question(
+        &self,
+        mut question: AuthoredQuestion,
+    ) -> Result<AuthoredQuestion, QuestionServiceError> {
+        debug!("create_question service started");
+        if question.question_text.trim().is_empty()
+            || question.topic.trim().is_empty()
+            || !(1..=4).contains(&question.good_answer_number)
+            || !are_hints_valid(&question.hints)
+        {
+            return Err(QuestionServiceError {
+                message: "Question is not valid".to_string(),
+                kind: QuestionServiceErrorKind::InvalidRequest,
+            });
+        }
+        let result = match self.question_repo.create_question(question.clone()).await {
+            Ok(insert) => {
+                question.id = insert.inserted_id.as_object_id();
+                Ok(question)
+            }
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("create_question service ending");
+        result
+    }
+
+    /// Gets the [AuthoredQuestion]s the caller may manage: all of them for moderators, their own ones otherwise.
+    /// Returns an error if the caller is neither a question author nor a moderator.
+    pub async fn get_questions(
+        &self,
+        caller: &AuthenticatedUser,
+    ) -> Result<Vec<AuthoredQuestion>, QuestionServiceError> {
+        debug!("get_questions service started");
+        let author = if is_allowed(caller, Permission::ModerateQuestions) {
+            None
+        } else if is_allowed(caller, Permission::WriteQuestions) {
+            Some(caller.username.clone())
+        } else {
+            return Err(Self::process_forbidden_error(&caller.username));
+        };
+        let result = match self.question_repo.get_questions(author).await {
+            Ok(questions) => questions
+                .try_collect()
+                .await
+                .map_err(Self::process_internal_error),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("get_questions service ending");
+        result
+    }
+
+    /// Gets the [AuthoredQuestion]s having one of the given topics, to be asked in a game.
+    pub async fn get_topic_questions(
+        &self,
+        topics: Vec<String>,
+    ) -> Result<Vec<AuthoredQuestion>, QuestionServiceError> {
+        debug!("get_topic_questions service started");
+        let result = match self.question_repo.get_questions_by_topics(topics).await {
+            Ok(questions) => questions
+                .try_collect()
+                .await
+                .map_err(Self::process_internal_error),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("get_topic_questions service ending");
+        result
+    }
+
+    /// Deletes an [AuthoredQuestion] on behalf of its author or of a moderator.
+    /// Returns an error if the question does not exist.
+    /// Returns an error if the caller is neither its author nor a moderator.
+    pub async fn delete_question(
+        &self,
+        id: String,
+        caller: &AuthenticatedUser,
+    ) -> Result<(), QuestionServiceError> {
+        debug!("delete_question service started");
+        let not_found_error = QuestionServiceError {
+            message: format!("Question with id {} does not exist", id),
+            kind: QuestionServiceErrorKind::NotFound,
+        };
+        let Ok(object_id) = ObjectId::from_str(&id) else {
+            return Err(not_found_error);
+        };
+        let question = match self.question_repo.get_question(object_id).await {
+            Ok(Some(question)) => question,
+            Ok(None) => return Err(not_found_error),
+            Err(err) => return Err(Self::process_internal_error(err)),
+        };
+        if !can_delete_question(caller, &question) {
+            return Err(Self::process_forbidden_error(&caller.username));
+        }
+        let result = match self.question_repo.delete_question(object_id).await {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("delete_question service ending");
+        result
+    }
+
+    fn process_forbidden_error(user: &str) -> QuestionServiceError {
+        QuestionServiceError {
+            message: format!("User {} is not allowed to manage this question", user),
+            kind: QuestionServiceErrorKind::Forbidden,
+        }
+    }
+
+    fn process_internal_error(err: Error) -> QuestionServiceError {
+        QuestionServiceError {
+            message: err.to_string(),
+            kind: QuestionServiceErrorKind::Internal,
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::errors::rating_service_error::{RatingServiceError, RatingServiceErrorKind};
+use crate::model::game::Game;
+use crate::model::game_answer::GameAnswer;
+use crate::model::rating::{rate_players, PlayerRating, RatedPlayer, RatingChange, INITIAL_RATING};
+use crate::repository::game_answer_repository::GameAnswerRepo;
+use crate::repository::player_rating_repository::PlayerRatingRepo;
+use crate::repository::rating_history_repository::RatingHistoryRepo;
+use log::debug;
+use mongodb::bson::DateTime;
+use mongodb::error::Error;
+use rocket::futures::TryStreamExt;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+/// Service for [PlayerRating] object to interact with the data layer
+pub struct RatingService {
+    rating_repo: PlayerRatingRepo,
+    rating_history_repo: RatingHistoryRepo,
+    game_answer_repo: GameAnswerRepo,
+}
+
+impl RatingService {
+    /// Creates a new instance of [RatingService] with the repositories to interact with the data layer
+    pub async fn init() -> Self {
+        let rating_repo = PlayerRatingRepo::init().await;
+        let rating_history_repo = RatingHistoryRepo::init().await;
+        let game_answer_repo = GameAnswerRepo::init().await;
+        RatingService {
+            rating_repo,
+            rating_history_repo,
+            game_answer_repo,
+        }
+    }
+
+    /// Creates the indexes the service relies on.
+    pub async fn create_indexes(&self) -> Result<(), RatingServiceError> {
+        debug!("create_indexes service started");
+        let created = match self.rating_repo.create_indexes().await {
+            Ok(_) => self.rating_history_repo.create_indexes().await,
+            Err(err) => Err(err),
+        };
+        let result = match created {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("create_indexes service ending");
+        result
+    }
+
+    /// Updates the ratings of the players of a finished [Game] on each topic of its questions,
+    /// from the final standings and the questions of the topic each player answered correctly.
+    /// The changes are saved in the rating history of the players.
+    /// Games with a single player do not change ratings.
+    pub async fn update_ratings(&self, game: &Game) -> Result<(), RatingServiceError> {
+        debug!("update_ratings service started");
+        if game.standings.len() < 2 {
+            return Ok(());
+        }
+        let game_id = game.id.unwrap().to_string();
+        let answers: Vec<GameAnswer> = self
+            .game_answer_repo
+            .get_game_answers(game_id.clone())
+            .await
+            .map_err(Self::process_internal_error)?
+            .try_collect()
+            .await
+            .map_err(Self::process_internal_error)?;
+        let mut questions: BTreeMap<String, BTreeSet<i8>> = BTreeMap::new();
+        let mut correct_answers: HashSet<(String, i8)> = HashSet::new();
+        for answer in answers {
+            questions
+                .entry(answer.question.topic.clone())
+                .or_default()
+                .insert(answer.question_index);
+            if answer.answer == answer.correct_answer {
+                correct_answers.insert((answer.user, answer.question_index));
+            }
+        }
+        let users: Vec<String> = game
+            .standings
+            .iter()
+            .map(|standing| standing.user.clone())
+            .collect();
+        for (topic, question_indexes) in questions {
+            let ratings: HashMap<String, f64> = self
+                .get_players_ratings(topic.clone(), users.clone())
+                .await?
+                .into_iter()
+                .map(|rating| (rating.user, rating.rating))
+                .collect();
+            let players: Vec<RatedPlayer> = game
+                .standings
+                .iter()
+                .map(|standing| RatedPlayer {
+                    rating: *ratings.get(&standing.user).unwrap_or(&INITIAL_RATING),
+                    rank: standing.rank,
+                    outcomes: question_indexes
+                        .iter()
+                        .map(|index| correct_answers.contains(&(standing.user.clone(), *index)))
+                        .collect(),
+                })
+                .collect();
+            let changed_at = DateTime::now();
+            let mut changes = vec![];
+            for ((user, player), rating) in users.iter().zip(&players).zip(rate_players(&players)) {
+                self.rating_repo
+                    .save_rating(user.clone(), topic.clone(), rating, changed_at)
+                    .await
+                    .map_err(Self::process_internal_error)?;
+                changes.push(RatingChange {
+                    id: None,
+                    user: user.clone(),
+                    topic: topic.clone(),
+                    game_id: game_id.clone(),
+                    rating_before: player.rating,
+                    rating,
+                    changed_at,
+                });
+            }
+            self.rating_history_repo
+                .save_changes(changes)
+                .await
+                .map_err(Self::process_internal_error)?;
+        }
+        debug!("update_ratings service ending");
+        Ok(())
+    }
+
+    /// Gets a page of the leaderboard of a topic, the best rated players first.
+    /// Pages start at 1.
+    /// Returns an error if the page or its size are not valid.
+    pub async fn get_leaderboard(
+        &self,
+        topic: String,
+        page: u64,
+        size: i64,
+    ) -> Result<Vec<PlayerRating>, RatingServiceError> {
+        debug!("get_leaderboard service started");
+        if page == 0 || !(1..=MAX_PAGE_SIZE).contains(&size) {
+            return Err(RatingServiceError {
+                message: format!(
+                    "Pages start at 1 and hold between 1 and {} ratings",
+                    MAX_PAGE_SIZE
+                ),
+                kind: RatingServiceErrorKind::InvalidRequest,
+            });
+        }
+        let skip = (page - 1) * size as u64;
+        let result = match self.rating_repo.get_topic_ratings(topic, skip, size).await {
+            Ok(ratings) => ratings
+                .try_collect()
+                .await
+                .map_err(Self::process_internal_error),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("get_leaderboard service ending");
+        result
+    }
+
+    /// Gets the history of the rating of a player on a topic, oldest change first.
+    pub async fn get_history(
+        &self,
+        user: String,
+        topic: String,
+    ) -> Result<Vec<RatingChange>, RatingServiceError> {
+        debug!("get_history service started");
+        let result = match self.rating_history_repo.get_history(user, topic).await {
+            Ok(history) => history
+                .try_collect()
+                .await
+                .map_err(Self::process_internal_error),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("get_history service ending");
+        result
+    }
+
+    async fn get_players_ratings(
+        &self,
+        topic: String,
+        users: Vec<String>,
+    ) -> Result<Vec<PlayerRating>, RatingServiceError> {
+        match self.rating_repo.get_players_ratings(topic, users).await {
+            Ok(ratings) => ratings
+                .try_collect()
+                .await
+                .map_err(Self::process_internal_error),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    fn process_internal_error(err: Error) -> RatingServiceError {
+        RatingServiceError {
+            message: err.to_string(),
+            kind: RatingServiceErrorKind::Internal,
+        }
+    }
+}
@@ -0,0 +1,562 @@
+use crate::errors::user_service_error::{UserServiceError, UserServiceErrorKind};
+use crate::model::oidc_login::OidcLogin;
+use crate::model::user::{default_roles, User, UserIdentity, UserRole, GUEST_TAG_SEPARATOR};
+use crate::repository::daily_attempt_repository::DailyAttemptRepo;
+use crate::repository::game_answer_repository::GameAnswerRepo;
+use crate::repository::game_repository::GameRepo;
+use crate::repository::leaderboard_repository::LeaderboardRepo;
+use crate::repository::oidc_login_repository::OidcLoginRepo;
+use crate::repository::player_rating_repository::PlayerRatingRepo;
+use crate::repository::practice_session_repository::PracticeSessionRepo;
+use crate::repository::rating_history_repository::RatingHistoryRepo;
+use crate::repository::user_repository::UserRepo;
+use crate::security::oidc::{code_challenge, random_token, OidcIdentity, OidcProvider};
+use crate::security::password::{hash_password, verify_password};
+use crate::security::token::{issue_guest_token, issue_token, Claims};
+use log::debug;
+use mongodb::bson::DateTime;
+use mongodb::error::{Error, ErrorKind, WriteFailure};
+use rand::Rng;
+
+pub const USERNAME_MAX_LENGTH: usize = 32;
+pub const PASSWORD_MIN_LENGTH: usize = 8;
+/// Number of hexadecimal digits of the tag telling apart guests playing under the same nickname.
+pub const GUEST_TAG_LENGTH: usize = 8;
+const DUPLICATE_KEY_CODE: i32 = 11000;
+
+/// Service for [User] object to interact with the data layer
+pub struct UserService {
+    user_repo: UserRepo,
+    game_repo: GameRepo,
+    game_answer_repo: GameAnswerRepo,
+    oidc_login_repo: OidcLoginRepo,
+    rating_repo: PlayerRatingRepo,
+    rating_history_repo: RatingHistoryRepo,
+    leaderboard_repo: LeaderboardRepo,
+    practice_session_repo: PracticeSessionRepo,
+    daily_attempt_repo: DailyAttemptRepo,
+}
+
+impl UserService {
+    /// Creates a new instance of [UserService] with the repository to interact with the data layer
+    pub async fn init() -> Self {
+        let user_repo = UserRepo::init().await;
+        let game_repo = GameRepo::init().await;
+        let game_answer_repo = GameAnswerRepo::init().await;
+        let oidc_login_repo = OidcLoginRepo::init().await;
+        let rating_repo = PlayerRatingRepo::init().await;
+        let rating_history_repo = RatingHistoryRepo::init().await;
+        let leaderboard_repo = LeaderboardRepo::init().await;
+        let practice_session_repo = PracticeSessionRepo::init().await;
+        let daily_attempt_repo = DailyAttemptRepo::init().await;
+        UserService {
+            user_repo,
+            game_repo,
+            game_answer_repo,
+            oidc_login_repo,
+            rating_repo,
+            rating_history_repo,
+            leaderboard_repo,
+            practice_session_repo,
+            daily_attempt_repo,
+        }
+    }
+
+    /// Creates the indexes the service relies on.
+    pub async fn create_indexes(&self) -> Result<(), UserServiceError> {
+        debug!("create_indexes service started");
+        let created = match self.user_repo.create_indexes().await {
+            Ok(_) => self.oidc_login_repo.create_indexes().await,
+            Err(err) => Err(err),
+        };
+        let result = match created {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("create_indexes service ending");
+        result
+    }
+
+    /// Registers a new [User] with a hashed password.
+    /// Returns an error if the username or the password are not valid.
+    /// Returns an error if the username is already taken.
+    pub async fn register(
+        &self,
+        username: String,
+        password: String,
+    ) -> Result<User, UserServiceError> {
+        debug!("register service started");
+        let result = self.create_user(username, password, None).await;
+        debug!("register service ending");
+        result
+    }
+
+    async fn create_user(
+        &self,
+        username: String,
+        password: String,
+        upgraded_from: Option<String>,
+    ) -> Result<User, UserServiceError> {
+        Self::validate_credentials(&username, &password)?;
+        let password_hash =
+            hash_password(&password).map_err(|err| Self::process_hashing_error(err.to_string()))?;
+        let mut user = User {
+            id: None,
+            username,
+            password_hash: Some(password_hash),
+            email: None,
+            display_name: None,
+            identities: vec![],
+            roles: default_roles(),
+            upgraded_from,
+            upgraded_at: None,
+            created_at: DateTime::now(),
+        };
+        match self.user_repo.create_user(user.clone()).await {
+            Ok(insert) => {
+                user.id = insert.inserted_id.as_object_id();
+                Ok(user)
+            }
+            Err(err) if Self::is_duplicate_key_error(&err) => Err(UserServiceError {
+                message: format!("Username {} is already taken", user.username),
+                kind: UserServiceErrorKind::Conflict,
+            }),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    /// Checks the credentials of a [User] and issues a token identifying them.
+    /// Returns an error if the username does not exist or if the password does not match.
+    pub async fn login(
+        &self,
+        username: String,
+        password: String,
+    ) -> Result<(String, Claims), UserServiceError> {
+        debug!("login service started");
+        let user = match self.user_repo.get_user_by_username(username).await {
+            Ok(user) => user,
+            Err(err) => return Err(Self::process_internal_error(err)),
+        };
+        let result = match user {
+            Some(user)
+                if user
+                    .password_hash
+                    .as_deref()
+                    .is_some_and(|hash| verify_password(&password, hash)) =>
+            {
+                issue_token(&user.username, &user.roles)
+                    .map_err(|err| Self::process_token_error(err.to_string()))
+            }
+            _ => Err(UserServiceError {
+                message: "Wrong username or password".to_string(),
+                kind: UserServiceErrorKind::Unauthorized,
+            }),
+        };
+        debug!("login service ending");
+        result
+    }
+
+    /// Starts an OpenID Connect login at the given provider, using PKCE.
+    /// Returns the URL of the provider the user is sent to, to log in.
+    /// Returns an error if the provider is not configured or cannot be reached.
+    pub async fn start_oidc_login(&self, provider: String) -> Result<String, UserServiceError> {
+        debug!("start_oidc_login service started");
+        let oidc_provider = Self::get_oidc_provider(&provider)?;
+        let metadata = oidc_provider
+            .discover()
+            .await
+            .map_err(Self::process_provider_error)?;
+        let login = OidcLogin {
+            state: random_token(),
+            provider,
+            code_verifier: random_token(),
+            nonce: random_token(),
+            created_at: DateTime::now(),
+        };
+        let url = oidc_provider
+            .authorization_url(
+                &metadata,
+                &login.state,
+                &login.nonce,
+                &code_challenge(&login.code_verifier),
+            )
+            .map_err(Self::process_provider_error)?;
+        let result = match self.oidc_login_repo.create_login(login).await {
+            Ok(_) => Ok(url),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("start_oidc_login service ending");
+        result
+    }
+
+    /// Completes an OpenID Connect login with the authorization code sent back by the provider.
+    /// The user linked to the provider account is logged in, a new user is created from the ID token claims
+    /// on their first login.
+    /// Returns a token identifying the user.
+    /// Returns an error if the login is unknown or expired, if the ID token is not valid
+    /// or if the username given by the provider is already taken by another user.
+    pub async fn finish_oidc_login(
+        &self,
+        provider: String,
+        code: String,
+        state: String,
+    ) -> Result<(String, Claims), UserServiceError> {
+        debug!("finish_oidc_login service started");
+        let oidc_provider = Self::get_oidc_provider(&provider)?;
+        let login = match self.oidc_login_repo.take_login(state).await {
+            Ok(Some(login)) if login.provider == provider => login,
+            Ok(_) => return Err(Self::process_unauthorized_error("Unknown or expired login")),
+            Err(err) => return Err(Self::process_internal_error(err)),
+        };
+        let metadata = oidc_provider
+            .discover()
+            .await
+            .map_err(Self::process_provider_error)?;
+        let id_token = oidc_provider
+            .exchange_code(&metadata, &code, &login.code_verifier)
+            .await
+            .map_err(|err| Self::process_unauthorized_error(&err))?;
+        let identity = oidc_provider
+            .validate_id_token(&metadata, &id_token, &login.nonce)
+            .await
+            .map_err(|err| Self::process_unauthorized_error(&err))?;
+        let user = self.get_or_create_oidc_user(identity).await?;
+        let result = issue_token(&user.username, &user.roles)
+            .map_err(|err| Self::process_token_error(err.to_string()));
+        debug!("finish_oidc_login service ending");
+        result
+    }
+
+    async fn get_or_create_oidc_user(
+        &self,
+        identity: OidcIdentity,
+    ) -> Result<User, UserServiceError> {
+        let user = self
+            .user_repo
+            .get_user_by_identity(identity.issuer.clone(), identity.subject.clone())
+            .await
+            .map_err(Self::process_internal_error)?;
+        if let Some(user) = user {
+            return Ok(user);
+        }
+        let username = identity
+            .username
+            .as_deref()
+            .map(Self::to_username)
+            .filter(|username| !username.is_empty())
+            .ok_or_else(|| {
+                Self::process_invalid_request_error("Provider gave no username".to_string())
+            })?;
+        let mut user = User {
+            id: None,
+            username,
+            password_hash: None,
+            email: identity.email,
+            display_name: identity.name,
+            identities: vec![UserIdentity {
+                issuer: identity.issuer,
+                subject: identity.subject,
+            }],
+            roles: default_roles(),
+            upgraded_from: None,
+            upgraded_at: None,
+            created_at: DateTime::now(),
+        };
+        match self.user_repo.create_user(user.clone()).await {
+            Ok(insert) => {
+                user.id = insert.inserted_id.as_object_id();
+                Ok(user)
+            }
+            Err(err) if Self::is_duplicate_key_error(&err) => Err(UserServiceError {
+                message: format!("Username {} is already taken", user.username),
+                kind: UserServiceErrorKind::Conflict,
+            }),
+            Err(err) => Err(Self::process_internal_error(err)),
+        }
+    }
+
+    /// Turns a username given by a provider, like an email address, into a valid username.
+    fn to_username(claim: &str) -> String {
+        claim
+            .chars()
+            .map(
+                |c| match c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    true => c,
+                    false => '_',
+                },
+            )
+            .take(USERNAME_MAX_LENGTH)
+            .collect()
+    }
+
+    fn get_oidc_provider(provider: &str) -> Result<OidcProvider, UserServiceError> {
+        OidcProvider::from_env(provider).ok_or_else(|| UserServiceError {
+            message: format!("Provider {} is not configured", provider),
+            kind: UserServiceErrorKind::NotFound,
+        })
+    }
+
+    /// Issues a guest token for the given nickname.
+    /// The player name of the guest is the nickname followed by a random tag, like `alice#1f2e3d4c`,
+    /// so guests never impersonate registered users or other guests.
+    /// Returns an error if the nickname is not valid.
+    pub fn create_guest(&self, nickname: String) -> Result<(String, Claims), UserServiceError> {
+        debug!("create_guest service started");
+        Self::validate_username(&nickname)?;
+        let tag: u64 = rand::thread_rng().gen_range(0..1 << (4 * GUEST_TAG_LENGTH));
+        let player = format!(
+            "{}{}{:0width$x}",
+            nickname,
+            GUEST_TAG_SEPARATOR,
+            tag,
+            width = GUEST_TAG_LENGTH
+        );
+        let result =
+            issue_guest_token(&player).map_err(|err| Self::process_token_error(err.to_string()));
+        debug!("create_guest service ending");
+        result
+    }
+
+    /// Registers a guest as a new [User] and moves the games, answers, ratings, leaderboard entries, practice sessions and daily challenge attempts
+    /// of the guest to the new account.
+    /// An upgrade left partial by a failure is resumed by upgrading again with the same credentials.
+    /// Once the upgrade is complete, the tokens of the guest are refused.
+    /// Returns a token identifying the new account.
+    /// Returns an error if the caller is not a guest or if the credentials are not valid.
+    /// Returns an error telling the upgrade is partial if the history could not be moved entirely.
+    pub async fn upgrade_guest(
+        &self,
+        guest: String,
+        is_guest: bool,
+        username: String,
+        password: String,
+    ) -> Result<(String, Claims), UserServiceError> {
+        debug!("upgrade_guest service started");
+        if !is_guest {
+            return Err(UserServiceError {
+                message: format!("User {} is not a guest", guest),
+                kind: UserServiceErrorKind::Forbidden,
+            });
+        }
+        let user = match self
+            .create_user(username.clone(), password.clone(), Some(guest.clone()))
+            .await
+        {
+            Err(err) if matches!(err.kind, UserServiceErrorKind::Conflict) => {
+                self.get_upgraded_user(&guest, username, &password).await?
+            }
+            user => user?,
+        };
+        let moved = match self.move_guest_history(&guest, &user.username).await {
+            Ok(_) => self
+                .user_repo
+                .complete_upgrade(user.username.clone(), DateTime::now())
+                .await
+                .map(|_| ()),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = moved {
+            return Err(UserServiceError {
+                message: format!(
+                    "Upgrade of guest {} to {} is partial, retry it to move the rest of the history: {}",
+                    guest, user.username, err
+                ),
+                kind: UserServiceErrorKind::Internal,
+            });
+        }
+        let result = issue_token(&user.username, &user.roles)
+            .map_err(|err| Self::process_token_error(err.to_string()));
+        debug!("upgrade_guest service ending");
+        result
+    }
+
+    /// Tells if a guest was upgraded to a [User], the tokens of the guest are then refused.
+    /// A guest whose upgrade is partial is not upgraded yet, so they can resume it.
+    pub async fn is_upgraded_guest(&self, guest: String) -> Result<bool, UserServiceError> {
+        debug!("is_upgraded_guest service started");
+        let result = self
+            .user_repo
+            .is_upgraded_guest(guest)
+            .await
+            .map_err(Self::process_internal_error);
+        debug!("is_upgraded_guest service ending");
+        result
+    }
+
+    /// Gets the [User] a guest was already registered as, when resuming a partial upgrade.
+    /// Returns an error if the username is taken by another user or if the password does not match.
+    async fn get_upgraded_user(
+        &self,
+        guest: &str,
+        username: String,
+        password: &str,
+    ) -> Result<User, UserServiceError> {
+        let user = self
+            .user_repo
+            .get_user_by_username(username.clone())
+            .await
+            .map_err(Self::process_internal_error)?;
+        match user {
+            Some(user)
+                if user.upgraded_from.as_deref() == Some(guest)
+                    && user
+                        .password_hash
+                        .as_deref()
+                        .is_some_and(|hash| verify_password(password, hash)) =>
+            {
+                Ok(user)
+            }
+            _ => Err(UserServiceError {
+                message: format!("Username {} is already taken", username),
+                kind: UserServiceErrorKind::Conflict,
+            }),
+        }
+    }
+
+    /// Renames a guest to the new user in every collection holding their history.
+    /// Each rename only touches what is still named after the guest, so the move may be run again after a failure.
+    async fn move_guest_history(&self, guest: &str, username: &str) -> Result<(), Error> {
+        let (from, to) = (guest.to_string(), username.to_string());
+        self.game_repo.rename_user(from.clone(), to.clone()).await?;
+        self.game_answer_repo
+            .rename_user(from.clone(), to.clone())
+            .await?;
+        self.rating_repo
+            .rename_user(from.clone(), to.clone())
+            .await?;
+        self.rating_history_repo
+            .rename_user(from.clone(), to.clone())
+            .await?;
+        self.leaderboard_repo
+            .rename_user(from.clone(), to.clone())
+            .await?;
+        self.practice_session_repo
+            .rename_user(from.clone(), to.clone())
+            .await?;
+        self.daily_attempt_repo.rename_user(from, to).await?;
+        Ok(())
+    }
+
+    /// Replaces the roles of a [User], they apply from the next token of the user.
+    /// Returns an error if the user does not exist.
+    pub async fn set_roles(
+        &self,
+        username: String,
+        roles: Vec<UserRole>,
+    ) -> Result<User, UserServiceError> {
+        debug!("set_roles service started");
+        let result = match self.user_repo.set_roles(username.clone(), roles).await {
+            Ok(Some(user)) => Ok(user),
+            Ok(None) => Err(UserServiceError {
+                message: format!("User {} does not exist", username),
+                kind: UserServiceErrorKind::NotFound,
+            }),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("set_roles service ending");
+        result
+    }
+
+    /// Makes the given users admins, so a first admin can manage the roles of the other users.
+    pub async fn promote_admins(&self, usernames: Vec<String>) -> Result<u64, UserServiceError> {
+        debug!("promote_admins service started");
+        let result = match self.user_repo.add_role(usernames, UserRole::Admin).await {
+            Ok(update) => Ok(update.modified_count),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("promote_admins service ending");
+        result
+    }
+
+    /// Gets a [User] by username.
+    /// Returns an error if the user does not exist.
+    pub async fn get_user(&self, username: String) -> Result<User, UserServiceError> {
+        debug!("get_user service started");
+        let result = match self.user_repo.get_user_by_username(username.clone()).await {
+            Ok(Some(user)) => Ok(user),
+            Ok(None) => Err(UserServiceError {
+                message: format!("User {} does not exist", username),
+                kind: UserServiceErrorKind::NotFound,
+            }),
+            Err(err) => Err(Self::process_internal_error(err)),
+        };
+        debug!("get_user service ending");
+        result
+    }
+
+    fn validate_credentials(username: &str, password: &str) -> Result<(), UserServiceError> {
+        Self::validate_username(username)?;
+        if password.len() < PASSWORD_MIN_LENGTH {
+            return Err(Self::process_invalid_request_error(format!(
+                "Password must be at least {} characters long",
+                PASSWORD_MIN_LENGTH
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_username(username: &str) -> Result<(), UserServiceError> {
+        let valid_username = !username.is_empty()
+            && username.len() <= USERNAME_MAX_LENGTH
+            && username
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_username {
+            return Err(Self::process_invalid_request_error(format!(
+                "Username must be 1 to {} letters, digits, '_' or '-'",
+                USERNAME_MAX_LENGTH
+            )));
+        }
+        Ok(())
+    }
+
+    fn is_duplicate_key_error(err: &Error) -> bool {
+        matches!(
+            err.kind.as_ref(),
+            ErrorKind::Write(WriteFailure::WriteError(write_error))
+                if write_error.code == DUPLICATE_KEY_CODE
+        )
+    }
+
+    fn process_invalid_request_error(message: String) -> UserServiceError {
+        UserServiceError {
+            message,
+            kind: UserServiceErrorKind::InvalidRequest,
+        }
+    }
+
+    fn process_unauthorized_error(message: &str) -> UserServiceError {
+        UserServiceError {
+            message: message.to_string(),
+            kind: UserServiceErrorKind::Unauthorized,
+        }
+    }
+
+    fn process_provider_error(message: String) -> UserServiceError {
+        UserServiceError {
+            message,
+            kind: UserServiceErrorKind::Internal,
+        }
+    }
+
+    fn process_hashing_error(message: String) -> UserServiceError {
+        UserServiceError {
+            message,
+            kind: UserServiceErrorKind::Internal,
+        }
+    }
+
+    fn process_token_error(message: String) -> UserServiceError {
+        UserServiceError {
+            message,
+            kind: UserServiceErrorKind::Internal,
+        }
+    }
+
+    fn process_internal_error(err: Error) -> UserServiceError {
+        UserServiceError {
+            message: err.to_string(),
+            kind: UserServiceErrorKind::Internal,
+        }
+    }
+}